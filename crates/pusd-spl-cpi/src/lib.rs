@@ -0,0 +1,22 @@
+//! CPI helpers for programs that mint PUSD as a registered AuthorizedContract.
+//!
+//! A consumer program is registered with `register_authorized_program` using
+//! its program ID and a seed set. It then mints by invoking `mint_by_program`
+//! with the PDA of those seeds as signer.
+
+use anchor_lang::prelude::*;
+
+pub use pusd_spl::cpi::accounts::MintByProgram;
+pub use pusd_spl::program::PusdSpl;
+pub use pusd_spl::ID as PUSD_PROGRAM_ID;
+
+/// Returns the authority PDA (and bump) a program signs with for `seeds`
+pub fn program_authority(program_id: &Pubkey, seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, program_id)
+}
+
+/// Mint PUSD to `recipient` from a registered program
+/// The context must be created with the program authority's signer seeds
+pub fn mint<'info>(ctx: CpiContext<'_, '_, '_, 'info, MintByProgram<'info>>, amount: u64) -> Result<()> {
+    pusd_spl::cpi::mint_by_program(ctx, amount)
+}
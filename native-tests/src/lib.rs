@@ -0,0 +1,238 @@
+//! Drives the PUSD programs on solana-program-test from synchronous tests.
+//!
+//! Programs run natively through `processor!`, with the Token-2022 and
+//! associated token programs executing as the BPF builds bundled with
+//! solana-program-test. Native program code is not metered, only the BPF
+//! programs it calls into are.
+
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+
+use solana_program_test::{ProgramTestContext, ProgramTestError};
+use solana_sdk::account::AccountSharedData;
+use solana_sdk::clock::Clock;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::program_error::ProgramError;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+use tokio::runtime::Runtime;
+
+pub use solana_program_test::{processor, ProgramTest};
+pub use solana_sdk::account_info::AccountInfo;
+pub use solana_sdk::entrypoint::ProgramResult;
+pub use solana_sdk::pubkey::Pubkey;
+
+/// Registers an Anchor `entry` function as a native program.
+///
+/// Anchor ties the slice and account lifetimes together, which the
+/// `processor!` signature does not, so the slice is re-borrowed here.
+#[macro_export]
+macro_rules! anchor_processor {
+    ($entry:path) => {{
+        fn process(
+            program_id: &$crate::Pubkey,
+            accounts: &[$crate::AccountInfo],
+            data: &[u8],
+        ) -> $crate::ProgramResult {
+            // SAFETY: only lifetimes change; program-test keeps every
+            // account alive until the instruction returns
+            let accounts = unsafe { ::std::mem::transmute::<&[$crate::AccountInfo], &[$crate::AccountInfo]>(accounts) };
+            $entry(program_id, accounts, data)
+        }
+        $crate::processor!(process)
+    }};
+}
+
+// Clock every deployment starts from
+const GENESIS_TIMESTAMP: i64 = 1_700_000_000;
+
+thread_local! {
+    static KEYPAIRS: RefCell<HashMap<Pubkey, Keypair>> = RefCell::new(HashMap::new());
+}
+
+/// Creates a keypair and returns its address; `Svm::send` signs for it
+pub fn new_key() -> Pubkey {
+    let keypair = Keypair::new();
+    let key = keypair.pubkey();
+    KEYPAIRS.with(|keypairs| keypairs.borrow_mut().insert(key, keypair));
+    key
+}
+
+/// Stored state of an account
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Account {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: Pubkey,
+    pub executable: bool,
+}
+
+impl Account {
+    pub fn new(lamports: u64, data: Vec<u8>, owner: Pubkey) -> Self {
+        Account { lamports, data, owner, executable: false }
+    }
+}
+
+/// The PUSD program registered as a native program
+pub fn program_test() -> ProgramTest {
+    let mut program_test = ProgramTest::default();
+    // Failed transactions print their own logs; keep the runtime's quiet
+    solana_logger::setup_with("error");
+    program_test.prefer_bpf(false);
+    program_test.add_program("pusd_spl", pusd_spl::ID, anchor_processor!(pusd_spl::entry));
+    program_test
+}
+
+/// A running bank that instructions execute against
+pub struct Svm {
+    runtime: Runtime,
+    context: ProgramTestContext,
+    clock: Clock,
+    sent: HashSet<Signature>,
+    compute_units: u64,
+}
+
+impl Default for Svm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Svm {
+    /// Starts a bank with the PUSD program loaded
+    pub fn new() -> Self {
+        Self::start(program_test())
+    }
+
+    /// Starts a bank with the programs registered on `program_test`
+    pub fn start(program_test: ProgramTest) -> Self {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        let context = runtime.block_on(program_test.start_with_context());
+        let mut clock: Clock = runtime.block_on(context.banks_client.get_sysvar()).unwrap();
+        clock.unix_timestamp = GENESIS_TIMESTAMP;
+        context.set_sysvar(&clock);
+        Svm { runtime, context, clock, sent: HashSet::new(), compute_units: 0 }
+    }
+
+    pub fn set_account(&mut self, key: Pubkey, account: Account) {
+        let Account { lamports, data, owner, executable } = account;
+        let account = solana_sdk::account::Account { lamports, data, owner, executable, rent_epoch: 0 };
+        self.context.set_account(&key, &AccountSharedData::from(account));
+    }
+
+    pub fn account(&self, key: &Pubkey) -> Option<Account> {
+        let account = self.runtime.block_on(self.context.banks_client.get_account(*key)).unwrap()?;
+        Some(Account {
+            lamports: account.lamports,
+            data: account.data,
+            owner: account.owner,
+            executable: account.executable,
+        })
+    }
+
+    /// Credits lamports to a (possibly new) system account
+    pub fn airdrop(&mut self, key: &Pubkey, lamports: u64) {
+        let mut account = self.account(key).unwrap_or_else(|| Account::new(0, Vec::new(), anchor_lang::system_program::ID));
+        account.lamports += lamports;
+        self.set_account(*key, account);
+    }
+
+    /// Moves the clock forward by `seconds` and `slots`
+    pub fn warp(&mut self, seconds: i64, slots: u64) {
+        self.clock.unix_timestamp += seconds;
+        self.clock.slot += slots;
+        self.context.set_sysvar(&self.clock);
+    }
+
+    pub fn clock(&self) -> Clock {
+        self.clock.clone()
+    }
+
+    /// Compute units consumed by the last executed transaction
+    pub fn compute_units(&self) -> u64 {
+        self.compute_units
+    }
+
+    /// The bank's funded fee payer
+    pub fn payer(&self) -> Pubkey {
+        self.context.payer.pubkey()
+    }
+
+    /// The instructions as a transaction paid for by `payer` and signed by
+    /// every signer of the instructions
+    pub fn transaction(&self, payer: &Pubkey, instructions: &[Instruction]) -> Transaction {
+        let mut signers = vec![self.keypair(payer)];
+        for meta in instructions.iter().flat_map(|instruction| &instruction.accounts) {
+            if meta.is_signer && signers.iter().all(|signer| signer.pubkey() != meta.pubkey) {
+                signers.push(self.keypair(&meta.pubkey));
+            }
+        }
+        Transaction::new_signed_with_payer(
+            instructions,
+            Some(payer),
+            &signers.iter().collect::<Vec<_>>(),
+            self.context.last_blockhash,
+        )
+    }
+
+    /// Executes the instructions as one transaction; on error no account changes
+    pub fn send_transaction(&mut self, instructions: &[Instruction]) -> Result<(), TransactionError> {
+        self.send_with_payer(&self.payer(), instructions)
+    }
+
+    /// Executes the instructions as one transaction paid for by `payer`
+    pub fn send_with_payer(&mut self, payer: &Pubkey, instructions: &[Instruction]) -> Result<(), TransactionError> {
+        let mut transaction = self.transaction(payer, instructions);
+        // Resending an identical transaction needs a new blockhash
+        if self.sent.contains(&transaction.signatures[0]) {
+            self.next_blockhash().unwrap();
+            transaction = self.transaction(payer, instructions);
+        }
+        self.sent.insert(transaction.signatures[0]);
+
+        let result = self
+            .runtime
+            .block_on(self.context.banks_client.process_transaction_with_metadata(transaction))
+            .unwrap();
+        if let Some(metadata) = &result.metadata {
+            self.compute_units = metadata.compute_units_consumed;
+            if result.result.is_err() {
+                for log in &metadata.log_messages {
+                    println!("{log}");
+                }
+            }
+        }
+        result.result
+    }
+
+    /// Executes the instructions, surfacing the failing instruction's error
+    pub fn send(&mut self, instructions: &[Instruction]) -> Result<(), ProgramError> {
+        self.send_transaction(instructions).map_err(|error| match error {
+            TransactionError::InstructionError(_, error) => {
+                ProgramError::try_from(error).unwrap_or_else(|error| panic!("{error}"))
+            }
+            error => panic!("{error}"),
+        })
+    }
+
+    fn keypair(&self, key: &Pubkey) -> Keypair {
+        if *key == self.context.payer.pubkey() {
+            return self.context.payer.insecure_clone();
+        }
+        KEYPAIRS.with(|keypairs| {
+            keypairs
+                .borrow()
+                .get(key)
+                .unwrap_or_else(|| panic!("{key} signs but was not created with new_key"))
+                .insecure_clone()
+        })
+    }
+
+    // Moves the bank to the next slot, keeping the clock the tests set
+    fn next_blockhash(&mut self) -> Result<(), ProgramTestError> {
+        let slot = self.runtime.block_on(self.context.banks_client.get_root_slot()).unwrap();
+        self.context.warp_to_slot(slot + 1)?;
+        self.context.set_sysvar(&self.clock);
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_spl::token_2022::spl_token_2022;
+use common::*;
+use pusd_spl::{PusdError, MAX_BATCH_MINT_SIZE};
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::packet::PACKET_DATA_SIZE;
+
+// Compute unit limit an operator requests for a full batch
+const BATCH_COMPUTE_UNIT_LIMIT: u32 = 400_000;
+
+fn batch(pusd: &Pusd, mint: Pubkey, amounts: Vec<u64>, recipients: &[Pubkey]) -> Instruction {
+    with_remaining(
+        ix(
+            pusd_spl::accounts::BatchMintByOperator {
+                operator_role: role_pda(&pusd.operator),
+                program_state: program_state_pda(),
+                operator: pusd.operator,
+                mint_window: mint_window_pda(&pusd.operator),
+                mint,
+                reserve_attestation: reserve_attestation_pda(&mint),
+                mint_authority: mint_authority_pda(),
+                token_program: spl_token_2022::ID,
+                system_program: anchor_lang::system_program::ID,
+            },
+            pusd_spl::instruction::BatchMintByOperator { amounts },
+        ),
+        recipients,
+    )
+}
+
+/// The batch as the operator would submit it: paying itself, with an explicit compute unit limit
+fn operator_transaction(pusd: &Pusd, amounts: Vec<u64>, recipients: &[Pubkey]) -> Vec<Instruction> {
+    vec![
+        ComputeBudgetInstruction::set_compute_unit_limit(BATCH_COMPUTE_UNIT_LIMIT),
+        batch(pusd, pusd.mint, amounts, recipients),
+    ]
+}
+
+fn recipients(pusd: &mut Pusd, mint: Pubkey, count: usize) -> Vec<Pubkey> {
+    (0..count).map(|_| pusd.create_ata(&new_key(), &mint)).collect()
+}
+
+#[test]
+fn mints_a_full_batch() {
+    let mut pusd = Pusd::new();
+    let mint = pusd.mint;
+    let recipients = recipients(&mut pusd, mint, MAX_BATCH_MINT_SIZE);
+    let amounts: Vec<u64> = (1..=MAX_BATCH_MINT_SIZE as u64).map(|i| i * 1_000).collect();
+
+    let operator = pusd.operator;
+    let instructions = operator_transaction(&pusd, amounts.clone(), &recipients);
+    pusd.svm.send_with_payer(&operator, &instructions).unwrap();
+    // Only the Token-2022 CPIs are metered here; PUSD itself runs natively
+    assert!(pusd.svm.compute_units() <= BATCH_COMPUTE_UNIT_LIMIT as u64);
+
+    for (recipient, amount) in recipients.iter().zip(&amounts) {
+        assert_eq!(pusd.balance(recipient), *amount);
+    }
+    assert_eq!(pusd.supply(), amounts.iter().sum::<u64>());
+}
+
+#[test]
+fn a_full_batch_fits_a_legacy_transaction() {
+    let mut pusd = Pusd::new();
+    let mint = pusd.mint;
+    let recipients = recipients(&mut pusd, mint, MAX_BATCH_MINT_SIZE + 1);
+    let operator = pusd.operator;
+    let size = |pusd: &Pusd, count: usize| {
+        let instructions = operator_transaction(pusd, vec![1; count], &recipients[..count]);
+        bincode::serialize(&pusd.svm.transaction(&operator, &instructions)).unwrap().len()
+    };
+
+    assert!(size(&pusd, MAX_BATCH_MINT_SIZE) <= PACKET_DATA_SIZE);
+    assert!(size(&pusd, MAX_BATCH_MINT_SIZE + 1) > PACKET_DATA_SIZE);
+}
+
+#[test]
+fn rejects_batches_over_the_limit() {
+    let mut pusd = Pusd::new();
+    let mint = pusd.mint;
+    let recipients = recipients(&mut pusd, mint, MAX_BATCH_MINT_SIZE + 1);
+
+    assert_eq!(
+        pusd.send(batch(&pusd, mint, vec![1; MAX_BATCH_MINT_SIZE + 1], &recipients)).unwrap_err(),
+        pusd_error(PusdError::BatchTooLarge)
+    );
+    assert_eq!(pusd.supply(), 0);
+}
+
+#[test]
+fn rejects_recipients_of_another_mint() {
+    let mut pusd = Pusd::new();
+    let (mint, other_mint) = (pusd.mint, new_key());
+    let authority = pusd.upgrade_authority;
+    pusd.create_mint(other_mint, authority, DECIMALS);
+    let mut recipients = recipients(&mut pusd, mint, 2);
+    recipients.extend(self::recipients(&mut pusd, other_mint, 1));
+
+    assert_eq!(
+        pusd.send(batch(&pusd, mint, vec![1; 3], &recipients)).unwrap_err(),
+        pusd_error(PusdError::InvalidRecipientAccount)
+    );
+    // the whole batch is rolled back
+    assert_eq!(pusd.supply(), 0);
+}
+
+#[test]
+fn rejects_a_mint_other_than_the_canonical_one() {
+    let mut pusd = Pusd::new();
+    let other_mint = new_key();
+    let authority = pusd.upgrade_authority;
+    pusd.create_mint(other_mint, authority, DECIMALS);
+    let recipients = recipients(&mut pusd, other_mint, 1);
+
+    // the PUSD attestation would otherwise cover the other mint's supply
+    let mut instruction = batch(&pusd, other_mint, vec![1], &recipients);
+    instruction.accounts[5].pubkey = reserve_attestation_pda(&pusd.mint);
+    assert_eq!(
+        pusd.send(instruction).unwrap_err(),
+        pusd_error(PusdError::InvalidMint)
+    );
+}
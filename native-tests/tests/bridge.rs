@@ -0,0 +1,146 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_spl::token_2022::spl_token_2022;
+use common::{ed25519_instruction, Signer as Guardian, *};
+use pusd_spl::bridge::{bridge_message, count_guardian_approvals, parse_ed25519_signers};
+use pusd_spl::{OutboundBridgeMessage, PusdError};
+
+fn sample_message(amount: u64) -> Vec<u8> {
+    bridge_message(2, 1, 42, &Pubkey::new_from_array([7; 32]), &Pubkey::new_from_array([9; 32]), amount)
+}
+
+#[test]
+fn threshold_counts_distinct_guardians_on_exact_message() {
+    let guardians: Vec<Guardian> = (1..=3).map(Guardian::new).collect();
+    let guardian_keys: Vec<Pubkey> = guardians.iter().map(Guardian::pubkey).collect();
+    let outsider = Guardian::new(99);
+    let message = sample_message(1_000_000);
+    let tampered = sample_message(2_000_000);
+
+    let data = ed25519_instruction(&[
+        (&guardians[0].public, &message, guardians[0].sign(&message)),
+        (&guardians[0].public, &message, guardians[0].sign(&message)),
+        (&guardians[1].public, &tampered, guardians[1].sign(&tampered)),
+        (&outsider.public, &message, outsider.sign(&message)),
+        (&guardians[2].public, &message, guardians[2].sign(&message)),
+    ]);
+    let signers = parse_ed25519_signers(&data).unwrap();
+    assert_eq!(signers.len(), 5);
+    assert_eq!(signers[0].0, guardian_keys[0]);
+    assert_eq!(signers[0].1, message.as_slice());
+
+    // duplicate, wrong-message and non-guardian signatures do not count
+    assert_eq!(count_guardian_approvals(&signers, &guardian_keys, &message), 2);
+    assert_eq!(count_guardian_approvals(&signers, &guardian_keys, &tampered), 1);
+}
+
+#[test]
+fn message_binds_every_field() {
+    let mint = Pubkey::new_from_array([7; 32]);
+    let recipient = Pubkey::new_from_array([9; 32]);
+    let base = bridge_message(2, 1, 42, &mint, &recipient, 5);
+    assert_ne!(base, bridge_message(3, 1, 42, &mint, &recipient, 5));
+    assert_ne!(base, bridge_message(2, 4, 42, &mint, &recipient, 5));
+    assert_ne!(base, bridge_message(2, 1, 43, &mint, &recipient, 5));
+    assert_ne!(base, bridge_message(2, 1, 42, &recipient, &recipient, 5));
+    assert_ne!(base, bridge_message(2, 1, 42, &mint, &mint, 5));
+    assert_ne!(base, bridge_message(2, 1, 42, &mint, &recipient, 6));
+}
+
+#[test]
+fn rejects_offsets_into_other_instructions() {
+    let guardian = Guardian::new(1);
+    let message = sample_message(1);
+    let mut data = ed25519_instruction(&[(&guardian.public, &message, guardian.sign(&message))]);
+
+    // point the public key at instruction 0 instead of the precompile itself
+    data[8..10].copy_from_slice(&0u16.to_le_bytes());
+    assert!(parse_ed25519_signers(&data).is_err());
+}
+
+#[test]
+fn rejects_truncated_instruction_data() {
+    let guardian = Guardian::new(1);
+    let message = sample_message(1);
+    let data = ed25519_instruction(&[(&guardian.public, &message, guardian.sign(&message))]);
+
+    assert!(parse_ed25519_signers(&[]).is_err());
+    assert!(parse_ed25519_signers(&data[..10]).is_err());
+    assert!(parse_ed25519_signers(&data[..data.len() - 1]).is_err());
+}
+
+const LOCAL_CHAIN: u16 = 1;
+
+fn outbound_message_pda(nonce: u64) -> Pubkey {
+    pda(&[b"bridge_out", nonce.to_le_bytes().as_ref()])
+}
+
+fn configure_bridge(pusd: &mut Pusd) {
+    let owner = pusd.owner;
+    let guardians = (1..=3).map(|seed| Guardian::new(seed).pubkey()).collect();
+    pusd.send(ix(
+        pusd_spl::accounts::SetBridgeConfig {
+            owner_role: role_pda(&owner),
+            owner,
+            bridge_config: pda(&[b"bridge_config"]),
+            sunset_state: sunset_pda(),
+            system_program: anchor_lang::system_program::ID,
+        },
+        pusd_spl::instruction::SetBridgeConfig { local_chain_id: LOCAL_CHAIN, guardians, threshold: 2 },
+    ))
+    .unwrap();
+}
+
+fn burn_for_bridge(holder: Pubkey, mint: Pubkey, holder_token: Pubkey, nonce: u64, amount: u64) -> Instruction {
+    ix(
+        pusd_spl::accounts::BurnForBridge {
+            holder,
+            bridge_config: pda(&[b"bridge_config"]),
+            outbound_message: outbound_message_pda(nonce),
+            program_state: program_state_pda(),
+            mint,
+            holder_token,
+            sunset_state: sunset_pda(),
+            token_program: spl_token_2022::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        pusd_spl::instruction::BurnForBridge { destination_chain: 2, recipient: [9; 32], amount },
+    )
+}
+
+#[test]
+fn burn_for_bridge_records_the_mint_in_the_outbound_message() {
+    let mut pusd = Pusd::new();
+    configure_bridge(&mut pusd);
+    let (holder, mint) = (new_key(), pusd.mint);
+    pusd.svm.airdrop(&holder, 1_000_000_000);
+    let holder_token = pusd.create_ata(&holder, &mint);
+    let operator = pusd.operator;
+    pusd.send(pusd.mint_by_operator_ix(operator, holder_token, 5_000)).unwrap();
+
+    pusd.send(burn_for_bridge(holder, mint, holder_token, 0, 2_000)).unwrap();
+
+    assert_eq!(pusd.balance(&holder_token), 3_000);
+    assert_eq!(pusd.supply(), 3_000);
+    let message: OutboundBridgeMessage = pusd.anchor_account(&outbound_message_pda(0)).unwrap();
+    assert_eq!((message.nonce, message.mint, message.sender), (0, mint, holder));
+    assert_eq!((message.destination_chain, message.recipient, message.amount), (2, [9; 32], 2_000));
+}
+
+#[test]
+fn burn_for_bridge_is_pinned_to_the_pusd_mint() {
+    let mut pusd = Pusd::new();
+    configure_bridge(&mut pusd);
+    let holder = new_key();
+    pusd.svm.airdrop(&holder, 1_000_000_000);
+    let (other, authority) = (new_key(), pusd.upgrade_authority);
+    pusd.create_mint(other, authority, DECIMALS);
+    let holder_token = pusd.create_ata(&holder, &other);
+    pusd.mint_tokens(&other, &holder_token, 5_000);
+
+    let result = pusd.send(burn_for_bridge(holder, other, holder_token, 0, 2_000));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::InvalidMint));
+    assert_eq!(pusd.balance(&holder_token), 5_000);
+}
@@ -0,0 +1,68 @@
+#![allow(dead_code, unused_imports)]
+
+mod program;
+mod psm;
+mod sunset;
+pub use program::*;
+pub use psm::*;
+pub use pusd_native_tests::new_key;
+
+use anchor_lang::prelude::Pubkey;
+use ed25519_dalek::{Signature, Signer as _, SigningKey, VerifyingKey};
+
+// Locally generated ed25519 key for guardians and permit signers
+pub struct Signer {
+    key: SigningKey,
+    pub public: [u8; 32],
+}
+
+impl Signer {
+    pub fn new(seed: u8) -> Self {
+        let key = SigningKey::from_bytes(&[seed; 32]);
+        let public = key.verifying_key().to_bytes();
+        Signer { key, public }
+    }
+
+    pub fn pubkey(&self) -> Pubkey {
+        Pubkey::new_from_array(self.public)
+    }
+
+    pub fn sign(&self, message: &[u8]) -> [u8; 64] {
+        self.key.sign(message).to_bytes()
+    }
+}
+
+// Same check the ed25519 precompile performs
+pub fn verify(public: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> bool {
+    VerifyingKey::from_bytes(public)
+        .and_then(|key| key.verify_strict(message, &Signature::from_bytes(signature)))
+        .is_ok()
+}
+
+// Builds ed25519 precompile instruction data with all offsets in-instruction
+pub fn ed25519_instruction(entries: &[(&[u8; 32], &[u8], [u8; 64])]) -> Vec<u8> {
+    let header = 2 + entries.len() * 14;
+    let mut offsets = vec![entries.len() as u8, 0];
+    let mut payload = Vec::new();
+    for (public, message, signature) in entries {
+        let signature_offset = header + payload.len();
+        payload.extend_from_slice(signature);
+        let public_offset = header + payload.len();
+        payload.extend_from_slice(*public);
+        let message_offset = header + payload.len();
+        payload.extend_from_slice(message);
+        for value in [
+            signature_offset,
+            u16::MAX as usize,
+            public_offset,
+            u16::MAX as usize,
+            message_offset,
+            message.len(),
+            u16::MAX as usize,
+        ] {
+            offsets.extend_from_slice(&(value as u16).to_le_bytes());
+        }
+    }
+    offsets.extend_from_slice(&payload);
+    offsets
+}
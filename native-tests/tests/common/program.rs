@@ -0,0 +1,375 @@
+use anchor_lang::prelude::{AccountMeta, Pubkey, Rent};
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::{AccountDeserialize, AccountSerialize, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::associated_token::spl_associated_token_account;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::default_account_state::instruction as default_account_state_instruction;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::instruction as transfer_fee_instruction;
+use anchor_spl::token_2022::spl_token_2022::extension::{ExtensionType, StateWithExtensions};
+use anchor_spl::token_2022::spl_token_2022::state::AccountState;
+use pusd_spl::{ProgramState, PusdError, Role, RoleStatus, UserRole, ROLE_ACTIVATION_DELAY};
+use pusd_native_tests::{new_key, program_test, Account, ProgramTest, Svm};
+
+pub const DECIMALS: u8 = 6;
+pub const RESERVES: u64 = 1_000_000_000_000;
+
+/// The custom error code a failed PusdError check surfaces as
+pub fn pusd_error(error: PusdError) -> ProgramError {
+    ProgramError::Custom(error.into())
+}
+
+/// The custom error code a failed Anchor framework check surfaces as
+pub fn anchor_error(error: anchor_lang::error::ErrorCode) -> ProgramError {
+    ProgramError::Custom(error.into())
+}
+
+pub fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &pusd_spl::ID).0
+}
+
+pub fn program_state_pda() -> Pubkey {
+    pda(&[b"program_state"])
+}
+
+pub fn role_pda(user: &Pubkey) -> Pubkey {
+    pda(&[b"user_role", user.as_ref()])
+}
+
+pub fn mint_authority_pda() -> Pubkey {
+    pda(&[b"mint_authority"])
+}
+
+pub fn mint_window_pda(operator: &Pubkey) -> Pubkey {
+    pda(&[b"operator_mint_window", operator.as_ref()])
+}
+
+pub fn reserve_attestation_pda(mint: &Pubkey) -> Pubkey {
+    pda(&[b"reserve_attestation", mint.as_ref()])
+}
+
+pub fn sunset_pda() -> Pubkey {
+    pda(&[b"sunset"])
+}
+
+pub fn program_data_address() -> Pubkey {
+    Pubkey::find_program_address(&[pusd_spl::ID.as_ref()], &bpf_loader_upgradeable::ID).0
+}
+
+// UpgradeableLoaderState::ProgramData: u32 variant tag, slot, Option<Pubkey>
+pub fn program_data(upgrade_authority: Option<Pubkey>) -> Vec<u8> {
+    let mut data = 3u32.to_le_bytes().to_vec();
+    data.extend_from_slice(&42u64.to_le_bytes());
+    match upgrade_authority {
+        Some(authority) => {
+            data.push(1);
+            data.extend_from_slice(authority.as_ref());
+        }
+        None => data.push(0),
+    }
+    // program bytes follow the header
+    data.extend_from_slice(&[0xAB; 16]);
+    data
+}
+
+pub fn ix(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction { program_id: pusd_spl::ID, accounts: accounts.to_account_metas(None), data: data.data() }
+}
+
+/// Appends writable remaining accounts to an instruction
+pub fn with_remaining(mut instruction: Instruction, accounts: &[Pubkey]) -> Instruction {
+    instruction.accounts.extend(accounts.iter().map(|key| AccountMeta::new(*key, false)));
+    instruction
+}
+
+/// A deployed and initialized PUSD program with its mint under PDA control
+pub struct Pusd {
+    pub svm: Svm,
+    pub upgrade_authority: Pubkey,
+    pub owner: Pubkey,
+    pub operator: Pubkey,
+    pub guardian: Pubkey,
+    pub attestor: Pubkey,
+    pub mint: Pubkey,
+}
+
+impl Default for Pusd {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pusd {
+    pub fn new() -> Self {
+        Self::build(program_test(), false)
+    }
+
+    /// A KYC allowlist deployment whose mint has DefaultAccountState and the upgrade authority as freeze authority
+    pub fn new_kyc() -> Self {
+        Self::build(program_test(), true)
+    }
+
+    /// A deployment on a bank that also runs the programs registered on `program_test`
+    pub fn with_programs(program_test: ProgramTest) -> Self {
+        Self::build(program_test, false)
+    }
+
+    fn build(program_test: ProgramTest, kyc_required: bool) -> Self {
+        let mut svm = Svm::start(program_test);
+
+        let upgrade_authority = new_key();
+        svm.airdrop(&upgrade_authority, 100_000_000_000);
+        svm.set_account(
+            program_data_address(),
+            Account::new(1_000_000, program_data(Some(upgrade_authority)), bpf_loader_upgradeable::ID),
+        );
+
+        let mut pusd = Pusd {
+            svm,
+            upgrade_authority,
+            owner: new_key(),
+            operator: new_key(),
+            guardian: new_key(),
+            attestor: new_key(),
+            mint: new_key(),
+        };
+        for user in [pusd.owner, pusd.operator, pusd.guardian, pusd.attestor] {
+            pusd.svm.airdrop(&user, 10_000_000_000);
+        }
+
+        pusd.send(ix(
+            pusd_spl::accounts::Initialize {
+                program_state: program_state_pda(),
+                owner_role: role_pda(&pusd.owner),
+                operator_role: role_pda(&pusd.operator),
+                guardian_role: role_pda(&pusd.guardian),
+                sunset_state: sunset_pda(),
+                program_data: program_data_address(),
+                payer: upgrade_authority,
+                system_program: anchor_lang::system_program::ID,
+            },
+            pusd_spl::instruction::Initialize {
+                owner_address: pusd.owner,
+                operator_address: pusd.operator,
+                guardian_address: pusd.guardian,
+                kyc_required,
+            },
+        ))
+        .unwrap();
+
+        let mint = pusd.mint;
+        if kyc_required {
+            pusd.create_kyc_mint(mint, upgrade_authority);
+        } else {
+            pusd.create_mint(mint, upgrade_authority, DECIMALS);
+        }
+        pusd.send(ix(
+            pusd_spl::accounts::TransferAuthority {
+                program_state: program_state_pda(),
+                mint,
+                current_authority: upgrade_authority,
+                program_data: program_data_address(),
+                payer: upgrade_authority,
+                token_program: spl_token_2022::ID,
+            },
+            pusd_spl::instruction::TransferMintAuthorityToPda {},
+        ))
+        .unwrap();
+
+        // Roles granted at initialization activate after the usual delay
+        pusd.svm.warp(ROLE_ACTIVATION_DELAY, 1);
+        let attestor = pusd.attestor;
+        pusd.set_role(attestor, Role::Attestor);
+        pusd.attest(RESERVES);
+        pusd
+    }
+
+    pub fn send(&mut self, instruction: Instruction) -> Result<(), ProgramError> {
+        self.svm.send(&[instruction])
+    }
+
+    /// Writes an Anchor account with a rent-exempt balance
+    pub fn set_anchor_account<T: AccountSerialize>(&mut self, key: Pubkey, value: &T, space: usize) {
+        let mut data = Vec::with_capacity(space);
+        value.try_serialize(&mut data).unwrap();
+        data.resize(space, 0);
+        let lamports = Rent::default().minimum_balance(space);
+        self.svm.set_account(key, Account::new(lamports, data, pusd_spl::ID));
+    }
+
+    pub fn anchor_account<T: AccountDeserialize>(&self, key: &Pubkey) -> Option<T> {
+        let account = self.svm.account(key)?;
+        Some(T::try_deserialize(&mut account.data.as_slice()).unwrap())
+    }
+
+    /// Gives `user` an already active role without waiting out the delay
+    pub fn set_role(&mut self, user: Pubkey, role: Role) -> Pubkey {
+        let (key, bump) = Pubkey::find_program_address(&[b"user_role", user.as_ref()], &pusd_spl::ID);
+        let user_role = UserRole {
+            version: UserRole::VERSION,
+            user,
+            role,
+            bump,
+            role_active_time: 0,
+            status: RoleStatus::Active,
+        };
+        self.set_anchor_account(key, &user_role, UserRole::LEN);
+        self.svm.airdrop(&user, 10_000_000_000);
+        key
+    }
+
+    pub fn role(&self, user: &Pubkey) -> Option<UserRole> {
+        self.anchor_account(&role_pda(user))
+    }
+
+    pub fn state(&self) -> ProgramState {
+        self.anchor_account(&program_state_pda()).unwrap()
+    }
+
+    pub fn update_state(&mut self, update: impl FnOnce(&mut ProgramState)) {
+        let mut state = self.state();
+        update(&mut state);
+        self.set_anchor_account(program_state_pda(), &state, ProgramState::LEN);
+    }
+
+    pub fn attest(&mut self, reserve_amount: u64) {
+        let (attestor, mint) = (self.attestor, self.mint);
+        self.send(ix(
+            pusd_spl::accounts::AttestReserves {
+                attestor_role: role_pda(&attestor),
+                attestor,
+                program_state: program_state_pda(),
+                mint,
+                reserve_attestation: reserve_attestation_pda(&mint),
+                system_program: anchor_lang::system_program::ID,
+            },
+            pusd_spl::instruction::AttestReserves {
+                reserve_amount,
+                report_hash: [1; 32],
+                report_uri: "https://example.com/report".to_string(),
+            },
+        ))
+        .unwrap();
+    }
+
+    pub fn mint_by_operator_ix(&self, operator: Pubkey, recipient: Pubkey, amount: u64) -> Instruction {
+        ix(
+            pusd_spl::accounts::MintByOperator {
+                operator_role: role_pda(&operator),
+                program_state: program_state_pda(),
+                operator,
+                mint_window: mint_window_pda(&operator),
+                mint: self.mint,
+                reserve_attestation: reserve_attestation_pda(&self.mint),
+                recipient,
+                mint_authority: mint_authority_pda(),
+                token_program: spl_token_2022::ID,
+                system_program: anchor_lang::system_program::ID,
+            },
+            pusd_spl::instruction::MintByOperator { amount },
+        )
+    }
+
+    /// Creates a plain Token-2022 mint
+    pub fn create_mint(&mut self, mint: Pubkey, authority: Pubkey, decimals: u8) {
+        let space = spl_token_2022::state::Mint::LEN;
+        self.svm.set_account(
+            mint,
+            Account::new(Rent::default().minimum_balance(space), vec![0; space], spl_token_2022::ID),
+        );
+        let instruction =
+            spl_token_2022::instruction::initialize_mint2(&spl_token_2022::ID, &mint, &authority, None, decimals)
+                .unwrap();
+        self.svm.send(&[instruction]).unwrap();
+    }
+
+    /// Creates a Token-2022 mint with the DefaultAccountState extension, `authority` holding mint and freeze authority
+    pub fn create_kyc_mint(&mut self, mint: Pubkey, authority: Pubkey) {
+        let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+            ExtensionType::DefaultAccountState,
+        ])
+        .unwrap();
+        self.svm.set_account(
+            mint,
+            Account::new(Rent::default().minimum_balance(space), vec![0; space], spl_token_2022::ID),
+        );
+        let init_state = default_account_state_instruction::initialize_default_account_state(
+            &spl_token_2022::ID,
+            &mint,
+            &AccountState::Initialized,
+        )
+        .unwrap();
+        let init_mint = spl_token_2022::instruction::initialize_mint2(
+            &spl_token_2022::ID,
+            &mint,
+            &authority,
+            Some(&authority),
+            DECIMALS,
+        )
+        .unwrap();
+        self.svm.send(&[init_state, init_mint]).unwrap();
+    }
+
+    /// Creates a Token-2022 mint with the TransferFee extension, both fee authorities held by `authority`
+    pub fn create_fee_mint(&mut self, mint: Pubkey, authority: Pubkey, fee_bps: u16, maximum_fee: u64) {
+        let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+            ExtensionType::TransferFeeConfig,
+        ])
+        .unwrap();
+        self.svm.set_account(
+            mint,
+            Account::new(Rent::default().minimum_balance(space), vec![0; space], spl_token_2022::ID),
+        );
+        let init_fee = transfer_fee_instruction::initialize_transfer_fee_config(
+            &spl_token_2022::ID,
+            &mint,
+            Some(&authority),
+            Some(&authority),
+            fee_bps,
+            maximum_fee,
+        )
+        .unwrap();
+        let init_mint =
+            spl_token_2022::instruction::initialize_mint2(&spl_token_2022::ID, &mint, &authority, None, DECIMALS)
+                .unwrap();
+        self.svm.send(&[init_fee, init_mint]).unwrap();
+    }
+
+    /// Creates the Token-2022 associated token account of `wallet` for `mint`
+    pub fn create_ata(&mut self, wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+        let payer = self.upgrade_authority;
+        let instruction = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer,
+            wallet,
+            mint,
+            &spl_token_2022::ID,
+        );
+        self.svm.send(&[instruction]).unwrap();
+        get_associated_token_address_with_program_id(wallet, mint, &spl_token_2022::ID)
+    }
+
+    pub fn ata(&self, wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+        get_associated_token_address_with_program_id(wallet, mint, &spl_token_2022::ID)
+    }
+
+    pub fn token_account(&self, key: &Pubkey) -> spl_token_2022::state::Account {
+        let account = self.svm.account(key).expect("token account exists");
+        StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account.data).unwrap().base
+    }
+
+    pub fn balance(&self, key: &Pubkey) -> u64 {
+        self.token_account(key).amount
+    }
+
+    pub fn mint_state(&self, mint: &Pubkey) -> spl_token_2022::state::Mint {
+        let account = self.svm.account(mint).expect("mint exists");
+        StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&account.data).unwrap().base
+    }
+
+    pub fn supply(&self) -> u64 {
+        self.mint_state(&self.mint).supply
+    }
+}
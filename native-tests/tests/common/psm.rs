@@ -0,0 +1,138 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_spl::token_2022::spl_token_2022;
+
+use super::*;
+
+pub const PRICE_ONE: u64 = pusd_spl::PRICE_SCALE;
+
+pub fn collateral_config_pda(collateral_mint: &Pubkey) -> Pubkey {
+    pda(&[b"collateral", collateral_mint.as_ref()])
+}
+
+pub fn psm_vault_pda(collateral_mint: &Pubkey) -> Pubkey {
+    pda(&[b"psm_vault", collateral_mint.as_ref()])
+}
+
+pub fn treasury_pda() -> Pubkey {
+    pda(&[b"treasury"])
+}
+
+impl Pusd {
+    /// Mints tokens of a test mint created with the upgrade authority as mint authority
+    pub fn mint_tokens(&mut self, mint: &Pubkey, to: &Pubkey, amount: u64) {
+        let instruction = spl_token_2022::instruction::mint_to(
+            &spl_token_2022::ID,
+            mint,
+            to,
+            &self.upgrade_authority,
+            &[],
+            amount,
+        )
+        .unwrap();
+        self.svm.send(&[instruction]).unwrap();
+    }
+
+    /// Returns the treasury's PUSD account, creating it on first use
+    pub fn treasury_token(&mut self) -> Pubkey {
+        let (treasury, mint) = (treasury_pda(), self.mint);
+        let ata = self.ata(&treasury, &mint);
+        if self.svm.account(&ata).is_none() {
+            self.create_ata(&treasury, &mint);
+        }
+        ata
+    }
+
+    pub fn add_collateral_ix(&self, collateral_mint: Pubkey, price: u64, debt_ceiling: u64) -> Instruction {
+        ix(
+            pusd_spl::accounts::AddCollateral {
+                owner_role: role_pda(&self.owner),
+                owner: self.owner,
+                collateral_config: collateral_config_pda(&collateral_mint),
+                collateral_mint,
+                pusd_mint: self.mint,
+                vault: psm_vault_pda(&collateral_mint),
+                sunset_state: sunset_pda(),
+                collateral_token_program: spl_token_2022::ID,
+                token_program: spl_token_2022::ID,
+                system_program: anchor_lang::system_program::ID,
+            },
+            pusd_spl::instruction::AddCollateral { price, debt_ceiling },
+        )
+    }
+
+    /// Creates a Token-2022 collateral mint and registers it with the PSM
+    pub fn add_collateral(&mut self, decimals: u8, price: u64, debt_ceiling: u64) -> Pubkey {
+        let (collateral_mint, authority) = (new_key(), self.upgrade_authority);
+        self.create_mint(collateral_mint, authority, decimals);
+        self.send(self.add_collateral_ix(collateral_mint, price, debt_ceiling)).unwrap();
+        collateral_mint
+    }
+
+    /// Gives `user` a funded collateral account and an empty PUSD account
+    pub fn psm_user(&mut self, collateral_mint: &Pubkey, collateral: u64) -> (Pubkey, Pubkey, Pubkey) {
+        let user = new_key();
+        self.svm.airdrop(&user, 1_000_000_000);
+        let user_collateral = self.create_ata(&user, collateral_mint);
+        self.mint_tokens(collateral_mint, &user_collateral, collateral);
+        let user_pusd = self.create_ata(&user, &self.mint.clone());
+        (user, user_collateral, user_pusd)
+    }
+
+    pub fn swap_in_ix(
+        &mut self,
+        user: Pubkey,
+        collateral_mint: Pubkey,
+        user_collateral: Pubkey,
+        recipient: Pubkey,
+        amount: u64,
+    ) -> Instruction {
+        ix(
+            pusd_spl::accounts::SwapIn {
+                user,
+                program_state: program_state_pda(),
+                collateral_config: collateral_config_pda(&collateral_mint),
+                oracle: None,
+                collateral_mint,
+                vault: psm_vault_pda(&collateral_mint),
+                user_collateral,
+                mint: self.mint,
+                recipient,
+                mint_authority: mint_authority_pda(),
+                treasury: treasury_pda(),
+                treasury_token: self.treasury_token(),
+                collateral_token_program: spl_token_2022::ID,
+                token_program: spl_token_2022::ID,
+            },
+            pusd_spl::instruction::SwapIn { amount },
+        )
+    }
+
+    pub fn swap_out_ix(
+        &mut self,
+        user: Pubkey,
+        collateral_mint: Pubkey,
+        user_collateral: Pubkey,
+        user_pusd: Pubkey,
+        amount: u64,
+    ) -> Instruction {
+        ix(
+            pusd_spl::accounts::SwapOut {
+                user,
+                program_state: program_state_pda(),
+                collateral_config: collateral_config_pda(&collateral_mint),
+                oracle: None,
+                collateral_mint,
+                vault: psm_vault_pda(&collateral_mint),
+                user_collateral,
+                mint: self.mint,
+                user_pusd,
+                treasury: treasury_pda(),
+                treasury_token: self.treasury_token(),
+                collateral_token_program: spl_token_2022::ID,
+                token_program: spl_token_2022::ID,
+            },
+            pusd_spl::instruction::SwapOut { amount },
+        )
+    }
+}
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_spl::token_2022::spl_token_2022;
+use pusd_spl::SUNSET_TIMELOCK;
+
+use super::*;
+
+impl Pusd {
+    pub fn schedule_sunset_ix(&self, owner: Pubkey) -> Instruction {
+        ix(
+            pusd_spl::accounts::ScheduleSunset {
+                owner_role: role_pda(&owner),
+                owner,
+                sunset_state: sunset_pda(),
+                system_program: anchor_lang::system_program::ID,
+            },
+            pusd_spl::instruction::ScheduleSunset {},
+        )
+    }
+
+    pub fn cancel_sunset_ix(&self, owner: Pubkey) -> Instruction {
+        ix(
+            pusd_spl::accounts::CancelSunset { owner_role: role_pda(&owner), owner, sunset_state: sunset_pda() },
+            pusd_spl::instruction::CancelSunset {},
+        )
+    }
+
+    pub fn execute_sunset_ix(&self, owner: Pubkey, mint: Pubkey) -> Instruction {
+        ix(
+            pusd_spl::accounts::ExecuteSunset {
+                owner_role: role_pda(&owner),
+                owner,
+                program_state: program_state_pda(),
+                sunset_state: sunset_pda(),
+                mint,
+                mint_authority: mint_authority_pda(),
+                treasury: treasury_pda(),
+                treasury_token: self.ata(&treasury_pda(), &mint),
+                token_program: spl_token_2022::ID,
+            },
+            pusd_spl::instruction::ExecuteSunset {},
+        )
+    }
+
+    /// Schedules the sunset, waits out the timelock and executes it with an empty treasury
+    pub fn sunset(&mut self) {
+        let owner = self.owner;
+        self.send(self.schedule_sunset_ix(owner)).unwrap();
+        self.svm.warp(SUNSET_TIMELOCK, 1);
+        self.treasury_token();
+        self.send(self.execute_sunset_ix(owner, self.mint)).unwrap();
+    }
+
+    pub fn redeem_after_sunset_ix(&self, holder: Pubkey, mint: Pubkey, holder_token: Pubkey, amount: u64) -> Instruction {
+        ix(
+            pusd_spl::accounts::RedeemAfterSunset {
+                holder,
+                sunset_state: sunset_pda(),
+                mint,
+                holder_token,
+                token_program: spl_token_2022::ID,
+            },
+            pusd_spl::instruction::RedeemAfterSunset { amount },
+        )
+    }
+
+    pub fn swap_out_after_sunset_ix(
+        &self,
+        user: Pubkey,
+        collateral_mint: Pubkey,
+        user_collateral: Pubkey,
+        user_pusd: Pubkey,
+        amount: u64,
+    ) -> Instruction {
+        ix(
+            pusd_spl::accounts::SwapOutAfterSunset {
+                user,
+                sunset_state: sunset_pda(),
+                collateral_config: collateral_config_pda(&collateral_mint),
+                collateral_mint,
+                vault: psm_vault_pda(&collateral_mint),
+                user_collateral,
+                mint: self.mint,
+                user_pusd,
+                collateral_token_program: spl_token_2022::ID,
+                token_program: spl_token_2022::ID,
+            },
+            pusd_spl::instruction::SwapOutAfterSunset { amount },
+        )
+    }
+}
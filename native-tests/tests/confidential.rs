@@ -0,0 +1,253 @@
+mod common;
+
+use anchor_lang::prelude::{Pubkey, Rent};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::error::TokenError;
+use anchor_spl::token_2022::spl_token_2022::extension::confidential_transfer::{
+    instruction as confidential_instruction,
+    ConfidentialTransferAccount,
+    ConfidentialTransferMint,
+};
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions,
+    BaseStateWithExtensionsMut,
+    ExtensionType,
+    StateWithExtensions,
+    StateWithExtensionsMut,
+};
+use anchor_spl::token_2022::spl_token_2022::solana_zk_sdk::encryption::pod::elgamal::PodElGamalPubkey;
+use anchor_spl::token_2022::spl_token_2022::state::AccountState;
+use common::*;
+use pusd_spl::{PusdError, Role};
+use pusd_native_tests::Account;
+
+const AUDITOR: [u8; 32] = [7; 32];
+
+/// A Token-2022 mint with the ConfidentialTransferMint extension under `authority`, recorded as the PUSD mint
+fn create_confidential_mint(pusd: &mut Pusd, authority: Pubkey) -> Pubkey {
+    let mint = new_key();
+    let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+        ExtensionType::ConfidentialTransferMint,
+    ])
+    .unwrap();
+    pusd.svm.set_account(
+        mint,
+        Account::new(Rent::default().minimum_balance(space), vec![0; space], spl_token_2022::ID),
+    );
+    let init_confidential =
+        confidential_instruction::initialize_mint(&spl_token_2022::ID, &mint, Some(authority), true, None).unwrap();
+    let init_mint =
+        spl_token_2022::instruction::initialize_mint2(&spl_token_2022::ID, &mint, &authority, None, DECIMALS).unwrap();
+    pusd.svm.send(&[init_confidential, init_mint]).unwrap();
+    pusd.update_state(|state| state.mint = mint);
+    mint
+}
+
+/// A token account already configured for confidential transfers and awaiting approval
+/// Configuring requires a zero-knowledge proof, so the extension state is written directly
+fn configured_account(pusd: &mut Pusd, mint: Pubkey) -> Pubkey {
+    let key = new_key();
+    let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(&[
+        ExtensionType::ConfidentialTransferAccount,
+    ])
+    .unwrap();
+    let mut data = vec![0; space];
+    let mut state = StateWithExtensionsMut::<spl_token_2022::state::Account>::unpack_uninitialized(&mut data).unwrap();
+    state.base = spl_token_2022::state::Account {
+        mint,
+        owner: new_key(),
+        state: AccountState::Initialized,
+        ..Default::default()
+    };
+    state.pack_base();
+    state.init_account_type().unwrap();
+    state.init_extension::<ConfidentialTransferAccount>(true).unwrap();
+    pusd.svm.set_account(key, Account::new(Rent::default().minimum_balance(space), data, spl_token_2022::ID));
+    key
+}
+
+fn mint_config(pusd: &Pusd, mint: &Pubkey) -> ConfidentialTransferMint {
+    let data = &pusd.svm.account(mint).unwrap().data;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(data).unwrap();
+    *state.get_extension::<ConfidentialTransferMint>().unwrap()
+}
+
+fn approved(pusd: &Pusd, token_account: &Pubkey) -> bool {
+    let data = &pusd.svm.account(token_account).unwrap().data;
+    let state = StateWithExtensions::<spl_token_2022::state::Account>::unpack(data).unwrap();
+    state.get_extension::<ConfidentialTransferAccount>().unwrap().approved.into()
+}
+
+fn authority_to_pda(signer: Pubkey, current_authority: Pubkey, mint: Pubkey) -> Instruction {
+    ix(
+        pusd_spl::accounts::ConfidentialAuthorityToPda {
+            owner_role: role_pda(&signer),
+            owner: signer,
+            current_authority,
+            program_state: program_state_pda(),
+            mint,
+            mint_authority: mint_authority_pda(),
+            sunset_state: sunset_pda(),
+            token_program: spl_token_2022::ID,
+        },
+        pusd_spl::instruction::ConfidentialAuthorityToPda {},
+    )
+}
+
+fn configure(signer: Pubkey, mint: Pubkey, auditor_elgamal_pubkey: Option<[u8; 32]>) -> Instruction {
+    ix(
+        pusd_spl::accounts::ConfigureConfidentialTransfers {
+            owner_role: role_pda(&signer),
+            owner: signer,
+            program_state: program_state_pda(),
+            mint,
+            mint_authority: mint_authority_pda(),
+            sunset_state: sunset_pda(),
+            token_program: spl_token_2022::ID,
+        },
+        pusd_spl::instruction::ConfigureConfidentialTransfers { auditor_elgamal_pubkey },
+    )
+}
+
+fn approve(signer: Pubkey, token_account: Pubkey, mint: Pubkey) -> Instruction {
+    ix(
+        pusd_spl::accounts::ApproveConfidentialAccount {
+            compliance_role: role_pda(&signer),
+            compliance: signer,
+            token_account,
+            program_state: program_state_pda(),
+            mint,
+            mint_authority: mint_authority_pda(),
+            sunset_state: sunset_pda(),
+            token_program: spl_token_2022::ID,
+        },
+        pusd_spl::instruction::ApproveConfidentialAccount {},
+    )
+}
+
+/// A confidential mint whose authority is already the program PDA
+fn pda_confidential_mint(pusd: &mut Pusd) -> Pubkey {
+    let authority = pusd.upgrade_authority;
+    let mint = create_confidential_mint(pusd, authority);
+    let owner = pusd.owner;
+    pusd.send(authority_to_pda(owner, authority, mint)).unwrap();
+    mint
+}
+
+#[test]
+fn authority_moves_to_the_pda() {
+    let mut pusd = Pusd::new();
+    let mint = pda_confidential_mint(&mut pusd);
+
+    let config = mint_config(&pusd, &mint);
+    assert_eq!(Option::<Pubkey>::from(config.authority), Some(mint_authority_pda()));
+}
+
+#[test]
+fn authority_handover_requires_the_owner_and_the_current_authority() {
+    let mut pusd = Pusd::new();
+    let authority = pusd.upgrade_authority;
+    let mint = create_confidential_mint(&mut pusd, authority);
+
+    let operator = pusd.operator;
+    let result = pusd.send(authority_to_pda(operator, authority, mint));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::Unauthorized));
+
+    let owner = pusd.owner;
+    let result = pusd.send(authority_to_pda(owner, new_key(), mint));
+    assert_eq!(result.unwrap_err(), TokenError::OwnerMismatch.into());
+}
+
+#[test]
+fn configure_sets_the_auditor_and_disables_auto_approval() {
+    let mut pusd = Pusd::new();
+    let mint = pda_confidential_mint(&mut pusd);
+    assert!(bool::from(mint_config(&pusd, &mint).auto_approve_new_accounts));
+
+    let owner = pusd.owner;
+    pusd.send(configure(owner, mint, Some(AUDITOR))).unwrap();
+
+    let config = mint_config(&pusd, &mint);
+    assert!(!bool::from(config.auto_approve_new_accounts));
+    let auditor: Option<PodElGamalPubkey> = config.auditor_elgamal_pubkey.into();
+    assert_eq!(auditor, Some(PodElGamalPubkey::from(AUDITOR)));
+
+    pusd.send(configure(owner, mint, None)).unwrap();
+    let auditor: Option<PodElGamalPubkey> = mint_config(&pusd, &mint).auditor_elgamal_pubkey.into();
+    assert!(auditor.is_none());
+}
+
+#[test]
+fn configure_requires_the_owner_and_pda_authority() {
+    let mut pusd = Pusd::new();
+    let authority = pusd.upgrade_authority;
+    let mint = create_confidential_mint(&mut pusd, authority);
+    let owner = pusd.owner;
+
+    // The PDA cannot sign for a mint whose confidential authority it does not hold
+    let result = pusd.send(configure(owner, mint, Some(AUDITOR)));
+    assert_eq!(result.unwrap_err(), TokenError::OwnerMismatch.into());
+
+    pusd.send(authority_to_pda(owner, authority, mint)).unwrap();
+
+    let operator = pusd.operator;
+    let result = pusd.send(configure(operator, mint, Some(AUDITOR)));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::Unauthorized));
+}
+
+#[test]
+fn compliance_approves_configured_accounts() {
+    let mut pusd = Pusd::new();
+    let mint = pda_confidential_mint(&mut pusd);
+    let owner = pusd.owner;
+    pusd.send(configure(owner, mint, Some(AUDITOR))).unwrap();
+    let token_account = configured_account(&mut pusd, mint);
+    assert!(!approved(&pusd, &token_account));
+
+    let compliance = new_key();
+    pusd.set_role(compliance, Role::Compliance);
+    pusd.send(approve(compliance, token_account, mint)).unwrap();
+
+    assert!(approved(&pusd, &token_account));
+}
+
+#[test]
+fn approval_requires_compliance_and_the_accounts_mint() {
+    let mut pusd = Pusd::new();
+    let mint = pda_confidential_mint(&mut pusd);
+    let token_account = configured_account(&mut pusd, mint);
+
+    let owner = pusd.owner;
+    let result = pusd.send(approve(owner, token_account, mint));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::Unauthorized));
+
+    // An account of another confidential mint is refused by Token-2022
+    let compliance = new_key();
+    pusd.set_role(compliance, Role::Compliance);
+    let other_mint = pda_confidential_mint(&mut pusd);
+    let result = pusd.send(approve(compliance, token_account, other_mint));
+    assert_eq!(result.unwrap_err(), TokenError::MintMismatch.into());
+    assert!(!approved(&pusd, &token_account));
+}
+
+#[test]
+fn every_instruction_is_pinned_to_the_pusd_mint() {
+    let mut pusd = Pusd::new();
+    let authority = pusd.upgrade_authority;
+    let other = create_confidential_mint(&mut pusd, authority);
+    let token_account = configured_account(&mut pusd, other);
+    let mint = pda_confidential_mint(&mut pusd);
+    let owner = pusd.owner;
+    let compliance = new_key();
+    pusd.set_role(compliance, Role::Compliance);
+
+    let result = pusd.send(authority_to_pda(owner, authority, other));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::InvalidMint));
+    let result = pusd.send(configure(owner, other, Some(AUDITOR)));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::InvalidMint));
+    let result = pusd.send(approve(compliance, token_account, other));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::InvalidMint));
+
+    pusd.send(configure(owner, mint, Some(AUDITOR))).unwrap();
+}
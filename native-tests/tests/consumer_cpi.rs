@@ -0,0 +1,286 @@
+mod common;
+
+use anchor_lang::prelude::{AccountInfo, AccountMeta, Pubkey};
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token_2022::spl_token_2022;
+use common::{anchor_error, mint_authority_pda, new_key, program_state_pda, pusd_error, reserve_attestation_pda, role_pda, sunset_pda, Pusd};
+use pusd_consumer::MINTER_SEED;
+use pusd_native_tests::{anchor_processor, program_test};
+use pusd_spl::{derive_program_authority, PusdError, Role, ROLE_ACTIVATION_DELAY};
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::transaction::TransactionError;
+
+// A program never registered with PUSD that tries to mint with the same seeds
+const IMPOSTOR_ID: Pubkey = Pubkey::new_from_array([9; 32]);
+
+fn pda(seeds: &[&[u8]], program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(seeds, program_id).0
+}
+
+#[test]
+fn registration_matches_consumer_signer() {
+    let (registered, bump) = derive_program_authority(&pusd_consumer::ID, &[MINTER_SEED.to_vec()]).unwrap();
+    assert_eq!(registered, pda(&[MINTER_SEED], &pusd_consumer::ID));
+    assert_eq!((registered, bump), pusd_spl_cpi::program_authority(&pusd_consumer::ID, &[MINTER_SEED]));
+}
+
+#[test]
+fn unregistered_program_derives_unregistered_authority() {
+    // Same seeds under another program ID give a different PDA, so its role
+    // and registration accounts do not exist and the mint constraints fail
+    let registered = pda(&[MINTER_SEED], &pusd_consumer::ID);
+    let impostor = pda(&[MINTER_SEED], &new_key());
+    assert_ne!(registered, impostor);
+    assert_ne!(
+        pda(&[b"user_role", registered.as_ref()], &pusd_spl::ID),
+        pda(&[b"user_role", impostor.as_ref()], &pusd_spl::ID)
+    );
+    assert_ne!(
+        pda(&[b"contract_registration", registered.as_ref()], &pusd_spl::ID),
+        pda(&[b"contract_registration", impostor.as_ref()], &pusd_spl::ID)
+    );
+}
+
+#[test]
+fn registration_rejects_invalid_seed_sets() {
+    assert!(derive_program_authority(&pusd_consumer::ID, &[]).is_err());
+    assert!(derive_program_authority(&pusd_consumer::ID, &[vec![0; 33]]).is_err());
+    assert!(derive_program_authority(&pusd_consumer::ID, &vec![vec![1]; 16]).is_err());
+    assert!(derive_program_authority(&pusd_consumer::ID, &vec![vec![1]; 15]).is_ok());
+}
+
+#[test]
+fn cpi_requires_program_authority_signature() {
+    let minter = pda(&[MINTER_SEED], &pusd_consumer::ID);
+    let mint = new_key();
+    let accounts = pusd_spl::accounts::MintByProgram {
+        contract_role: pda(&[b"user_role", minter.as_ref()], &pusd_spl::ID),
+        program_authority: minter,
+        contract_registration: pda(&[b"contract_registration", minter.as_ref()], &pusd_spl::ID),
+        mint,
+        program_state: pda(&[b"program_state"], &pusd_spl::ID),
+        reserve_attestation: pda(&[b"reserve_attestation", mint.as_ref()], &pusd_spl::ID),
+        recipient: new_key(),
+        mint_authority: pda(&[b"mint_authority"], &pusd_spl::ID),
+        token_program: new_key(),
+    }
+    .to_account_metas(None);
+
+    let signers: Vec<Pubkey> = accounts.iter().filter(|meta| meta.is_signer).map(|meta| meta.pubkey).collect();
+    assert_eq!(signers, vec![minter]);
+
+    // The consumer passes the minter PDA unsigned; it signs inside the CPI
+    let consumer_accounts = pusd_consumer::accounts::MintReward {
+        minter,
+        contract_role: accounts[0].pubkey,
+        contract_registration: accounts[2].pubkey,
+        mint,
+        program_state: accounts[4].pubkey,
+        reserve_attestation: accounts[5].pubkey,
+        recipient: accounts[6].pubkey,
+        mint_authority: accounts[7].pubkey,
+        token_program: accounts[8].pubkey,
+        pusd_program: pusd_spl::ID,
+    }
+    .to_account_metas(None);
+    assert!(consumer_accounts.iter().all(|meta| !meta.is_signer));
+
+    let data = pusd_consumer::instruction::MintReward { amount: 5 }.data();
+    assert_eq!(&data[8..], &5u64.to_le_bytes());
+}
+
+/// Forwards the MintByProgram accounts and data to PUSD, signing with its own MINTER_SEED PDA
+fn impostor<'a>(program_id: &Pubkey, accounts: &'a [AccountInfo<'a>], data: &[u8]) -> ProgramResult {
+    let (authority, bump) = Pubkey::find_program_address(&[MINTER_SEED], program_id);
+    let metas = accounts
+        .iter()
+        .map(|info| AccountMeta {
+            pubkey: *info.key,
+            is_signer: *info.key == authority || info.is_signer,
+            is_writable: info.is_writable,
+        })
+        .collect();
+    // The program authority is passed unsigned and must be signed for here
+    let mut instruction = Instruction { program_id: pusd_spl::ID, accounts: metas, data: data.to_vec() };
+    instruction.accounts[1].is_signer = true;
+    invoke_signed(&instruction, accounts, &[&[MINTER_SEED, &[bump]]])
+}
+
+/// A PUSD deployment with the consumer and the impostor loaded and a recipient account
+fn deployment() -> (Pusd, Pubkey) {
+    let mut program_test = program_test();
+    program_test.add_program("pusd_consumer", pusd_consumer::ID, anchor_processor!(pusd_consumer::entry));
+    program_test.add_program("impostor", IMPOSTOR_ID, anchor_processor!(impostor));
+    let mut pusd = Pusd::with_programs(program_test);
+    let mint = pusd.mint;
+    let recipient = pusd.create_ata(&new_key(), &mint);
+    (pusd, recipient)
+}
+
+fn register(pusd: &mut Pusd, program_id: Pubkey) {
+    let owner = pusd.owner;
+    let authority = pda(&[MINTER_SEED], &program_id);
+    pusd.send(common::ix(
+        pusd_spl::accounts::RegisterAuthorizedProgram {
+            owner_role: role_pda(&owner),
+            owner,
+            program_authority: authority,
+            user_role: role_pda(&authority),
+            contract_registration: pda(&[b"contract_registration", authority.as_ref()], &pusd_spl::ID),
+            sunset_state: sunset_pda(),
+            system_program: anchor_lang::system_program::ID,
+        },
+        pusd_spl::instruction::RegisterAuthorizedProgram { program_id, seeds: vec![MINTER_SEED.to_vec()] },
+    ))
+    .unwrap();
+}
+
+fn remove_role(pusd: &Pusd, user: Pubkey) -> Instruction {
+    let owner = pusd.owner;
+    common::ix(
+        pusd_spl::accounts::RemoveRole {
+            owner_role: role_pda(&owner),
+            owner,
+            user_role: role_pda(&user),
+            sunset_state: sunset_pda(),
+        },
+        pusd_spl::instruction::RemoveRole {},
+    )
+}
+
+fn mint_by_program_accounts(pusd: &Pusd, authority: Pubkey, recipient: Pubkey) -> pusd_spl::accounts::MintByProgram {
+    pusd_spl::accounts::MintByProgram {
+        contract_role: role_pda(&authority),
+        program_authority: authority,
+        contract_registration: pda(&[b"contract_registration", authority.as_ref()], &pusd_spl::ID),
+        mint: pusd.mint,
+        program_state: program_state_pda(),
+        reserve_attestation: reserve_attestation_pda(&pusd.mint),
+        recipient,
+        mint_authority: mint_authority_pda(),
+        token_program: spl_token_2022::ID,
+    }
+}
+
+fn mint_reward(pusd: &Pusd, recipient: Pubkey, amount: u64) -> Instruction {
+    let minter = pda(&[MINTER_SEED], &pusd_consumer::ID);
+    let accounts = mint_by_program_accounts(pusd, minter, recipient);
+    Instruction {
+        program_id: pusd_consumer::ID,
+        accounts: pusd_consumer::accounts::MintReward {
+            minter,
+            contract_role: accounts.contract_role,
+            contract_registration: accounts.contract_registration,
+            mint: accounts.mint,
+            program_state: accounts.program_state,
+            reserve_attestation: accounts.reserve_attestation,
+            recipient,
+            mint_authority: accounts.mint_authority,
+            token_program: accounts.token_program,
+            pusd_program: pusd_spl::ID,
+        }
+        .to_account_metas(None),
+        data: pusd_consumer::instruction::MintReward { amount }.data(),
+    }
+}
+
+/// The impostor's call, with `authority` as the program authority it claims
+fn impostor_mint(pusd: &Pusd, authority: Pubkey, recipient: Pubkey, amount: u64) -> Instruction {
+    let mut accounts = mint_by_program_accounts(pusd, authority, recipient).to_account_metas(None);
+    accounts[1].is_signer = false;
+    accounts.push(AccountMeta::new_readonly(pusd_spl::ID, false));
+    Instruction { program_id: IMPOSTOR_ID, accounts, data: pusd_spl::instruction::MintByProgram { amount }.data() }
+}
+
+#[test]
+fn registered_consumer_mints_through_cpi() {
+    let (mut pusd, recipient) = deployment();
+    register(&mut pusd, pusd_consumer::ID);
+    pusd.svm.warp(ROLE_ACTIVATION_DELAY, 1);
+
+    pusd.send(mint_reward(&pusd, recipient, 500)).unwrap();
+
+    assert_eq!(pusd.balance(&recipient), 500);
+    assert_eq!(pusd.supply(), 500);
+}
+
+#[test]
+fn registered_consumer_waits_out_the_activation_delay() {
+    let (mut pusd, recipient) = deployment();
+    register(&mut pusd, pusd_consumer::ID);
+
+    let result = pusd.send(mint_reward(&pusd, recipient, 500));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::RoleNotActivated));
+}
+
+#[test]
+fn registered_consumer_needs_the_authorized_contract_role() {
+    let (mut pusd, recipient) = deployment();
+    register(&mut pusd, pusd_consumer::ID);
+
+    let minter = pda(&[MINTER_SEED], &pusd_consumer::ID);
+    pusd.set_role(minter, Role::Operator);
+    let result = pusd.send(mint_reward(&pusd, recipient, 500));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::Unauthorized));
+}
+
+#[test]
+fn top_level_calls_are_rejected() {
+    let (mut pusd, recipient) = deployment();
+    register(&mut pusd, pusd_consumer::ID);
+    pusd.svm.warp(ROLE_ACTIVATION_DELAY, 1);
+
+    // The registered authority is a PDA, so a transaction calling PUSD directly cannot sign for it
+    let minter = pda(&[MINTER_SEED], &pusd_consumer::ID);
+    let mut direct = common::ix(
+        mint_by_program_accounts(&pusd, minter, recipient),
+        pusd_spl::instruction::MintByProgram { amount: 500 },
+    );
+    direct.accounts[1].is_signer = false;
+    assert_eq!(pusd.send(direct).unwrap_err(), anchor_error(anchor_lang::error::ErrorCode::AccountNotSigner));
+    assert_eq!(pusd.balance(&recipient), 0);
+}
+
+#[test]
+fn unregistered_programs_cannot_mint() {
+    let (mut pusd, recipient) = deployment();
+    register(&mut pusd, pusd_consumer::ID);
+    pusd.svm.warp(ROLE_ACTIVATION_DELAY, 1);
+
+    // Its own PDA has no role or registration account
+    let impostor_authority = pda(&[MINTER_SEED], &IMPOSTOR_ID);
+    let result = pusd.send(impostor_mint(&pusd, impostor_authority, recipient, 500));
+    assert_eq!(result.unwrap_err(), anchor_error(anchor_lang::error::ErrorCode::AccountNotInitialized));
+
+    // It cannot sign for the registered consumer's PDA; the runtime refuses the
+    // escalated CPI, which program-test surfaces as the native call failing
+    let minter = pda(&[MINTER_SEED], &pusd_consumer::ID);
+    let result = pusd.svm.send_transaction(&[impostor_mint(&pusd, minter, recipient, 500)]);
+    assert_eq!(result.unwrap_err(), TransactionError::InstructionError(0, InstructionError::ProgramFailedToComplete));
+    assert_eq!(pusd.balance(&recipient), 0);
+}
+
+#[test]
+fn a_removed_program_can_be_registered_again() {
+    let (mut pusd, recipient) = deployment();
+    register(&mut pusd, pusd_consumer::ID);
+    pusd.svm.warp(ROLE_ACTIVATION_DELAY, 1);
+    pusd.send(mint_reward(&pusd, recipient, 500)).unwrap();
+
+    let minter = pda(&[MINTER_SEED], &pusd_consumer::ID);
+    pusd.send(remove_role(&pusd, minter)).unwrap();
+    let result = pusd.send(mint_reward(&pusd, recipient, 500));
+    assert_eq!(result.unwrap_err(), anchor_error(anchor_lang::error::ErrorCode::AccountNotInitialized));
+
+    // The registration survives the removal and is re-used, with a fresh activation delay
+    register(&mut pusd, pusd_consumer::ID);
+    let result = pusd.send(mint_reward(&pusd, recipient, 500));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::RoleNotActivated));
+
+    pusd.svm.warp(ROLE_ACTIVATION_DELAY, 1);
+    pusd.send(mint_reward(&pusd, recipient, 500)).unwrap();
+    assert_eq!(pusd.balance(&recipient), 1_000);
+}
@@ -0,0 +1,139 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use common::*;
+use pusd_spl::PusdError;
+
+fn governance(signer: Pubkey) -> pusd_spl::accounts::UpdateGovernance {
+    pusd_spl::accounts::UpdateGovernance {
+        signer,
+        program_state: program_state_pda(),
+        program_data: program_data_address(),
+    }
+}
+
+fn propose_admin(signer: Pubkey, new_admin: Pubkey) -> Instruction {
+    ix(governance(signer), pusd_spl::instruction::ProposeAdmin { new_admin })
+}
+
+fn accept_admin(new_admin: Pubkey) -> Instruction {
+    ix(
+        pusd_spl::accounts::AcceptAdmin { new_admin, program_state: program_state_pda() },
+        pusd_spl::instruction::AcceptAdmin {},
+    )
+}
+
+fn renounce(signer: Pubkey) -> Instruction {
+    ix(governance(signer), pusd_spl::instruction::RenounceUpgradeAuthorityPrivileges {})
+}
+
+#[test]
+fn admin_passes_governance() {
+    let mut pusd = Pusd::new();
+    let owner = pusd.owner;
+    assert_eq!(pusd.state().admin, owner);
+
+    let proposed = new_key();
+    pusd.send(propose_admin(owner, proposed)).unwrap();
+    assert_eq!(pusd.state().pending_admin, proposed);
+
+    // The admin keeps governance after renouncing the upgrade authority
+    pusd.send(renounce(owner)).unwrap();
+    pusd.send(propose_admin(owner, proposed)).unwrap();
+}
+
+#[test]
+fn upgrade_authority_passes_governance_until_renounced() {
+    let mut pusd = Pusd::new();
+    let (owner, authority) = (pusd.owner, pusd.upgrade_authority);
+    pusd.send(propose_admin(authority, new_key())).unwrap();
+
+    pusd.send(renounce(owner)).unwrap();
+    assert!(pusd.state().upgrade_authority_renounced);
+
+    let result = pusd.send(propose_admin(authority, new_key()));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::OnlyAdmin));
+}
+
+#[test]
+fn others_fail_governance() {
+    let mut pusd = Pusd::new();
+    let operator = pusd.operator;
+    let result = pusd.send(propose_admin(operator, new_key()));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::OnlyUpgradeAuthority));
+
+    let owner = pusd.owner;
+    pusd.send(renounce(owner)).unwrap();
+    let result = pusd.send(propose_admin(operator, new_key()));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::OnlyAdmin));
+}
+
+#[test]
+fn renounce_requires_an_admin() {
+    let mut pusd = Pusd::new();
+    let (owner, authority) = (pusd.owner, pusd.upgrade_authority);
+
+    let result = pusd.send(renounce(authority));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::OnlyAdmin));
+
+    pusd.send(renounce(owner)).unwrap();
+    let result = pusd.send(renounce(owner));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::UpgradeAuthorityRenounced));
+
+    // Migrated deployments start without an admin
+    let mut pusd = Pusd::new();
+    pusd.update_state(|state| state.admin = Pubkey::default());
+    let authority = pusd.upgrade_authority;
+    let result = pusd.send(renounce(authority));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::AdminNotSet));
+
+    // Governance then falls back to the upgrade authority alone
+    pusd.send(propose_admin(authority, new_key())).unwrap();
+}
+
+#[test]
+fn admin_handover_takes_effect_on_acceptance() {
+    let mut pusd = Pusd::new();
+    let (owner, authority) = (pusd.owner, pusd.upgrade_authority);
+    let multisig = new_key();
+
+    pusd.send(propose_admin(owner, multisig)).unwrap();
+    assert_eq!(pusd.state().admin, owner);
+
+    pusd.send(accept_admin(multisig)).unwrap();
+    let state = pusd.state();
+    assert_eq!((state.admin, state.pending_admin), (multisig, Pubkey::default()));
+
+    // The previous admin has no governance rights left once the upgrade authority is renounced
+    pusd.send(renounce(multisig)).unwrap();
+    let result = pusd.send(propose_admin(owner, new_key()));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::OnlyAdmin));
+    let result = pusd.send(propose_admin(authority, new_key()));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::OnlyAdmin));
+}
+
+#[test]
+fn only_the_proposed_admin_accepts() {
+    let mut pusd = Pusd::new();
+    let owner = pusd.owner;
+    let multisig = new_key();
+
+    let result = pusd.send(accept_admin(multisig));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::NoPendingAdmin));
+
+    pusd.send(propose_admin(owner, multisig)).unwrap();
+    let result = pusd.send(accept_admin(new_key()));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::OnlyPendingAdmin));
+
+    // A new proposal replaces the pending one
+    let replacement = new_key();
+    pusd.send(propose_admin(owner, replacement)).unwrap();
+    let result = pusd.send(accept_admin(multisig));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::OnlyPendingAdmin));
+    pusd.send(accept_admin(replacement)).unwrap();
+    assert_eq!(pusd.state().admin, replacement);
+
+    let result = pusd.send(propose_admin(replacement, Pubkey::default()));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::InvalidAddress));
+}
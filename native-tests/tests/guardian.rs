@@ -0,0 +1,162 @@
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::AccountDeserialize;
+use common::*;
+use pusd_spl::{ProgramState, PusdError, Role, RoleStatus, UserRole};
+use pusd_native_tests::{Account, Svm};
+
+fn pause(guardian: Pubkey) -> Instruction {
+    ix(
+        pusd_spl::accounts::GuardianAction {
+            guardian_role: role_pda(&guardian),
+            guardian,
+            program_state: program_state_pda(),
+        },
+        pusd_spl::instruction::Pause {},
+    )
+}
+
+fn unpause(owner: Pubkey) -> Instruction {
+    ix(
+        pusd_spl::accounts::UpdateConfig { owner_role: role_pda(&owner), owner, program_state: program_state_pda() },
+        pusd_spl::instruction::Unpause {},
+    )
+}
+
+fn revoke_minter(guardian: Pubkey, minter: Pubkey, owner: Pubkey) -> Instruction {
+    ix(
+        pusd_spl::accounts::GuardianRevokeMinter {
+            guardian_role: role_pda(&guardian),
+            guardian,
+            minter_role: role_pda(&minter),
+            owner_role: role_pda(&owner),
+            owner,
+            sunset_state: sunset_pda(),
+        },
+        pusd_spl::instruction::GuardianRevokeMinter {},
+    )
+}
+
+/// Mints to a fresh holder account through the operator
+fn operator_mint(pusd: &mut Pusd) -> Result<(), ProgramError> {
+    let (operator, mint) = (pusd.operator, pusd.mint);
+    let recipient = pusd.create_ata(&new_key(), &mint);
+    pusd.send(pusd.mint_by_operator_ix(operator, recipient, 1_000))
+}
+
+#[test]
+fn guardian_is_active_immediately_after_initialize() {
+    let mut svm = Svm::new();
+    let authority = new_key();
+    svm.airdrop(&authority, 10_000_000_000);
+    svm.set_account(
+        program_data_address(),
+        Account::new(1_000_000, program_data(Some(authority)), bpf_loader_upgradeable::ID),
+    );
+    let (owner, operator, guardian) = (new_key(), new_key(), new_key());
+    let initialize = ix(
+        pusd_spl::accounts::Initialize {
+            program_state: program_state_pda(),
+            owner_role: role_pda(&owner),
+            operator_role: role_pda(&operator),
+            guardian_role: role_pda(&guardian),
+            sunset_state: sunset_pda(),
+            program_data: program_data_address(),
+            payer: authority,
+            system_program: anchor_lang::system_program::ID,
+        },
+        pusd_spl::instruction::Initialize {
+            owner_address: owner,
+            operator_address: operator,
+            guardian_address: guardian,
+            kyc_required: false,
+        },
+    );
+    svm.send(&[initialize]).unwrap();
+
+    let guardian_role = svm.account(&role_pda(&guardian)).unwrap().data;
+    let guardian_role = UserRole::try_deserialize(&mut &guardian_role[..]).unwrap();
+    assert_eq!(guardian_role.status, RoleStatus::Active);
+    assert_eq!(guardian_role.role_active_time, svm.clock().unix_timestamp);
+
+    // No warp: the guardian can pause in the same slot as initialization
+    svm.send(&[pause(guardian)]).unwrap();
+    let state = svm.account(&program_state_pda()).unwrap().data;
+    let state = ProgramState::try_deserialize(&mut &state[..]).unwrap();
+    assert!(state.paused);
+}
+
+#[test]
+fn guardian_pauses_and_only_an_owner_unpauses() {
+    let mut pusd = Pusd::new();
+    let (owner, guardian) = (pusd.owner, pusd.guardian);
+
+    pusd.send(pause(guardian)).unwrap();
+    assert!(pusd.state().paused);
+    assert_eq!(operator_mint(&mut pusd).unwrap_err(), pusd_error(PusdError::ProgramPaused));
+
+    let result = pusd.send(unpause(guardian));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::Unauthorized));
+
+    pusd.send(unpause(owner)).unwrap();
+    assert!(!pusd.state().paused);
+    operator_mint(&mut pusd).unwrap();
+}
+
+#[test]
+fn pause_is_guardian_only() {
+    let mut pusd = Pusd::new();
+    let (owner, operator) = (pusd.owner, pusd.operator);
+
+    let result = pusd.send(pause(owner));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::Unauthorized));
+    let result = pusd.send(pause(operator));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::Unauthorized));
+    assert!(!pusd.state().paused);
+}
+
+#[test]
+fn revoked_minter_rent_goes_to_an_owner() {
+    let mut pusd = Pusd::new();
+    let (owner, guardian, operator) = (pusd.owner, pusd.guardian, pusd.operator);
+    let role_rent = pusd.svm.account(&role_pda(&operator)).unwrap().lamports;
+    let lamports = |pusd: &Pusd| [owner, guardian, operator].map(|key| pusd.svm.account(&key).unwrap().lamports);
+    let [owner_before, guardian_before, operator_before] = lamports(&pusd);
+
+    pusd.send(revoke_minter(guardian, operator, owner)).unwrap();
+
+    assert!(pusd.role(&operator).is_none());
+    assert_eq!(lamports(&pusd), [owner_before + role_rent, guardian_before, operator_before]);
+    assert_eq!(operator_mint(&mut pusd).unwrap_err(), anchor_error(ErrorCode::AccountNotInitialized));
+}
+
+#[test]
+fn revoke_is_guardian_only_and_limited_to_minters() {
+    let mut pusd = Pusd::new();
+    let (owner, guardian, operator) = (pusd.owner, pusd.guardian, pusd.operator);
+
+    let result = pusd.send(revoke_minter(owner, operator, owner));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::Unauthorized));
+
+    // The rent goes to an Owner, not to the revoked key or the guardian
+    let result = pusd.send(revoke_minter(guardian, operator, operator));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::Unauthorized));
+    let result = pusd.send(revoke_minter(guardian, operator, guardian));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::Unauthorized));
+
+    let result = pusd.send(revoke_minter(guardian, owner, owner));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::NotAMinter));
+
+    for role in [Role::AuthorizedContract, Role::AuthorizedSigner] {
+        let minter = new_key();
+        pusd.set_role(minter, role);
+        pusd.send(revoke_minter(guardian, minter, owner)).unwrap();
+        assert!(pusd.role(&minter).is_none());
+    }
+    assert!(pusd.role(&operator).is_some());
+}
@@ -0,0 +1,232 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::default_account_state::DefaultAccountState;
+use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+use anchor_spl::token_2022::spl_token_2022::state::AccountState;
+use common::*;
+use pusd_spl::{KycRecord, PusdError, Role, SunsetState};
+
+fn freeze_authority_pda() -> Pubkey {
+    pda(&[b"freeze_authority"])
+}
+
+fn kyc_record_pda(token_account: &Pubkey) -> Pubkey {
+    pda(&[b"kyc_record", token_account.as_ref()])
+}
+
+fn freeze_authority_to_pda(signer: Pubkey, current_authority: Pubkey, mint: Pubkey) -> Instruction {
+    ix(
+        pusd_spl::accounts::FreezeAuthorityToPda {
+            owner_role: role_pda(&signer),
+            owner: signer,
+            program_state: program_state_pda(),
+            current_authority,
+            mint,
+            freeze_authority: freeze_authority_pda(),
+            token_program: spl_token_2022::ID,
+        },
+        pusd_spl::instruction::FreezeAuthorityToPda {},
+    )
+}
+
+fn kyc_approve(signer: Pubkey, token_account: Pubkey, mint: Pubkey) -> Instruction {
+    ix(
+        pusd_spl::accounts::KycApprove {
+            kyc_officer_role: role_pda(&signer),
+            kyc_officer: signer,
+            program_state: program_state_pda(),
+            kyc_record: kyc_record_pda(&token_account),
+            token_account,
+            mint,
+            freeze_authority: freeze_authority_pda(),
+            sunset_state: sunset_pda(),
+            token_program: spl_token_2022::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        pusd_spl::instruction::KycApprove {},
+    )
+}
+
+fn kyc_revoke(signer: Pubkey, token_account: Pubkey, mint: Pubkey) -> Instruction {
+    ix(
+        pusd_spl::accounts::KycRevoke {
+            kyc_officer_role: role_pda(&signer),
+            kyc_officer: signer,
+            program_state: program_state_pda(),
+            kyc_record: kyc_record_pda(&token_account),
+            token_account,
+            mint,
+            freeze_authority: freeze_authority_pda(),
+            sunset_state: sunset_pda(),
+            token_program: spl_token_2022::ID,
+        },
+        pusd_spl::instruction::KycRevoke {},
+    )
+}
+
+fn default_state(pusd: &Pusd, mint: &Pubkey) -> u8 {
+    let data = &pusd.svm.account(mint).unwrap().data;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(data).unwrap();
+    state.get_extension::<DefaultAccountState>().unwrap().state
+}
+
+/// A KYC deployment with the freeze authority handed to the PDA and a KycOfficer
+fn kyc_deployment() -> (Pusd, Pubkey) {
+    let mut pusd = Pusd::new_kyc();
+    let (owner, authority, mint) = (pusd.owner, pusd.upgrade_authority, pusd.mint);
+    pusd.send(freeze_authority_to_pda(owner, authority, mint)).unwrap();
+    let officer = new_key();
+    pusd.set_role(officer, Role::KycOfficer);
+    (pusd, officer)
+}
+
+fn holder_account(pusd: &mut Pusd) -> Pubkey {
+    let mint = pusd.mint;
+    pusd.create_ata(&new_key(), &mint)
+}
+
+#[test]
+fn freeze_authority_moves_to_the_pda_and_new_accounts_start_frozen() {
+    let mut pusd = Pusd::new_kyc();
+    let mint = pusd.mint;
+    assert_eq!(default_state(&pusd, &mint), AccountState::Initialized as u8);
+
+    let (owner, authority) = (pusd.owner, pusd.upgrade_authority);
+    pusd.send(freeze_authority_to_pda(owner, authority, mint)).unwrap();
+
+    assert_eq!(pusd.mint_state(&mint).freeze_authority, COption::Some(freeze_authority_pda()));
+    assert_eq!(default_state(&pusd, &mint), AccountState::Frozen as u8);
+    let token_account = holder_account(&mut pusd);
+    assert!(pusd.token_account(&token_account).is_frozen());
+}
+
+#[test]
+fn freeze_authority_handover_requires_the_owner_and_kyc_mode() {
+    let mut pusd = Pusd::new_kyc();
+    let (operator, authority, mint) = (pusd.operator, pusd.upgrade_authority, pusd.mint);
+    let result = pusd.send(freeze_authority_to_pda(operator, authority, mint));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::Unauthorized));
+
+    pusd.update_state(|state| state.kyc_required = false);
+    let owner = pusd.owner;
+    let result = pusd.send(freeze_authority_to_pda(owner, authority, mint));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::KycModeDisabled));
+}
+
+#[test]
+fn freeze_authority_handover_is_pinned_to_a_frozen_capable_pusd_mint() {
+    let mut pusd = Pusd::new_kyc();
+    let (owner, authority) = (pusd.owner, pusd.upgrade_authority);
+
+    let other = new_key();
+    pusd.create_kyc_mint(other, authority);
+    let result = pusd.send(freeze_authority_to_pda(owner, authority, other));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::InvalidMint));
+
+    // A mint created without DefaultAccountState cannot enforce the allowlist
+    let plain = new_key();
+    pusd.create_mint(plain, authority, DECIMALS);
+    pusd.update_state(|state| state.mint = plain);
+    let result = pusd.send(freeze_authority_to_pda(owner, authority, plain));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::MissingDefaultAccountState));
+}
+
+#[test]
+fn approve_thaws_and_revoke_refreezes() {
+    let (mut pusd, officer) = kyc_deployment();
+    let mint = pusd.mint;
+    let token_account = holder_account(&mut pusd);
+
+    pusd.send(kyc_approve(officer, token_account, mint)).unwrap();
+    assert!(!pusd.token_account(&token_account).is_frozen());
+    let record: KycRecord = pusd.anchor_account(&kyc_record_pda(&token_account)).unwrap();
+    assert!(record.approved);
+    assert_eq!((record.token_account, record.mint, record.officer), (token_account, mint, officer));
+    assert_eq!(record.holder, pusd.token_account(&token_account).owner);
+
+    // Approved holders can receive PUSD
+    let operator = pusd.operator;
+    pusd.send(pusd.mint_by_operator_ix(operator, token_account, 1_000)).unwrap();
+    assert_eq!(pusd.balance(&token_account), 1_000);
+
+    pusd.send(kyc_revoke(officer, token_account, mint)).unwrap();
+    assert!(pusd.token_account(&token_account).is_frozen());
+    let record: KycRecord = pusd.anchor_account(&kyc_record_pda(&token_account)).unwrap();
+    assert!(!record.approved);
+
+    // The record is re-used for a later approval
+    pusd.send(kyc_approve(officer, token_account, mint)).unwrap();
+    assert!(!pusd.token_account(&token_account).is_frozen());
+}
+
+#[test]
+fn kyc_decisions_require_a_kyc_officer() {
+    let (mut pusd, officer) = kyc_deployment();
+    let (owner, mint) = (pusd.owner, pusd.mint);
+    let token_account = holder_account(&mut pusd);
+
+    let result = pusd.send(kyc_approve(owner, token_account, mint));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::Unauthorized));
+
+    pusd.send(kyc_approve(officer, token_account, mint)).unwrap();
+    let result = pusd.send(kyc_revoke(owner, token_account, mint));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::Unauthorized));
+}
+
+#[test]
+fn approve_and_revoke_follow_the_record() {
+    let (mut pusd, officer) = kyc_deployment();
+    let mint = pusd.mint;
+    let token_account = holder_account(&mut pusd);
+
+    pusd.send(kyc_approve(officer, token_account, mint)).unwrap();
+    let result = pusd.send(kyc_approve(officer, token_account, mint));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::AlreadyKycApproved));
+
+    pusd.send(kyc_revoke(officer, token_account, mint)).unwrap();
+    let result = pusd.send(kyc_revoke(officer, token_account, mint));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::NotKycApproved));
+}
+
+#[test]
+fn approve_and_revoke_require_kyc_mode() {
+    let (mut pusd, officer) = kyc_deployment();
+    let mint = pusd.mint;
+    let token_account = holder_account(&mut pusd);
+    pusd.send(kyc_approve(officer, token_account, mint)).unwrap();
+
+    pusd.update_state(|state| state.kyc_required = false);
+    let result = pusd.send(kyc_revoke(officer, token_account, mint));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::KycModeDisabled));
+    assert!(!pusd.token_account(&token_account).is_frozen());
+
+    let other_account = holder_account(&mut pusd);
+    let result = pusd.send(kyc_approve(officer, other_account, mint));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::KycModeDisabled));
+}
+
+#[test]
+fn approve_and_revoke_are_rejected_after_sunset() {
+    let (mut pusd, officer) = kyc_deployment();
+    let (owner, mint) = (pusd.owner, pusd.mint);
+    let approved = holder_account(&mut pusd);
+    pusd.send(kyc_approve(officer, approved, mint)).unwrap();
+    let pending = holder_account(&mut pusd);
+
+    // An executed tombstone alongside the live state isolates the sunset check
+    let now = pusd.svm.clock().unix_timestamp;
+    let tombstone = SunsetState { owner, eta: now, executed: true, executed_at: now, bump: 0, mint };
+    pusd.set_anchor_account(sunset_pda(), &tombstone, SunsetState::LEN);
+
+    let result = pusd.send(kyc_approve(officer, pending, mint));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::ProgramSunset));
+    assert!(pusd.token_account(&pending).is_frozen());
+
+    let result = pusd.send(kyc_revoke(officer, approved, mint));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::ProgramSunset));
+    assert!(!pusd.token_account(&approved).is_frozen());
+}
@@ -0,0 +1,113 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_spl::token_2022::spl_token_2022;
+use common::*;
+use pusd_spl::{OperatorMintWindow, PusdError, Role, LARGE_MINT_WINDOW};
+
+const THRESHOLD: u64 = 10_000;
+
+fn batch_mint(pusd: &Pusd, operator: Pubkey, recipient: Pubkey, amounts: Vec<u64>) -> Instruction {
+    let recipients = vec![recipient; amounts.len()];
+    with_remaining(
+        ix(
+            pusd_spl::accounts::BatchMintByOperator {
+                operator_role: role_pda(&operator),
+                program_state: program_state_pda(),
+                operator,
+                mint_window: mint_window_pda(&operator),
+                mint: pusd.mint,
+                reserve_attestation: reserve_attestation_pda(&pusd.mint),
+                mint_authority: mint_authority_pda(),
+                token_program: spl_token_2022::ID,
+                system_program: anchor_lang::system_program::ID,
+            },
+            pusd_spl::instruction::BatchMintByOperator { amounts },
+        ),
+        &recipients,
+    )
+}
+
+fn setup() -> (Pusd, Pubkey) {
+    let mut pusd = Pusd::new();
+    pusd.update_state(|state| state.large_mint_threshold = THRESHOLD);
+    let recipient = pusd.create_ata(&new_key(), &pusd.mint.clone());
+    (pusd, recipient)
+}
+
+#[test]
+fn split_mints_within_the_window_require_approval() {
+    let (mut pusd, recipient) = setup();
+    let operator = pusd.operator;
+
+    pusd.send(pusd.mint_by_operator_ix(operator, recipient, 6_000)).unwrap();
+    pusd.send(pusd.mint_by_operator_ix(operator, recipient, 4_000)).unwrap();
+    assert_eq!(
+        pusd.send(pusd.mint_by_operator_ix(operator, recipient, 1)).unwrap_err(),
+        pusd_error(PusdError::RequiresApproval)
+    );
+    assert_eq!(pusd.balance(&recipient), THRESHOLD);
+
+    let window: OperatorMintWindow = pusd.anchor_account(&mint_window_pda(&operator)).unwrap();
+    assert_eq!(window.operator, operator);
+    assert_eq!(window.minted, THRESHOLD);
+}
+
+#[test]
+fn a_new_window_starts_after_the_window_length() {
+    let (mut pusd, recipient) = setup();
+    let operator = pusd.operator;
+    pusd.send(pusd.mint_by_operator_ix(operator, recipient, THRESHOLD)).unwrap();
+
+    pusd.svm.warp(LARGE_MINT_WINDOW - 1, 1);
+    assert_eq!(
+        pusd.send(pusd.mint_by_operator_ix(operator, recipient, 1)).unwrap_err(),
+        pusd_error(PusdError::RequiresApproval)
+    );
+
+    pusd.svm.warp(1, 1);
+    pusd.send(pusd.mint_by_operator_ix(operator, recipient, THRESHOLD)).unwrap();
+    assert_eq!(pusd.balance(&recipient), 2 * THRESHOLD);
+}
+
+#[test]
+fn windows_are_tracked_per_operator() {
+    let (mut pusd, recipient) = setup();
+    let (operator, other) = (pusd.operator, new_key());
+    pusd.set_role(other, Role::Operator);
+
+    pusd.send(pusd.mint_by_operator_ix(operator, recipient, THRESHOLD)).unwrap();
+    pusd.send(pusd.mint_by_operator_ix(other, recipient, THRESHOLD)).unwrap();
+    assert_eq!(pusd.balance(&recipient), 2 * THRESHOLD);
+}
+
+#[test]
+fn batches_count_towards_the_window() {
+    let (mut pusd, recipient) = setup();
+    let operator = pusd.operator;
+
+    pusd.send(batch_mint(&pusd, operator, recipient, vec![3_000, 3_000])).unwrap();
+    assert_eq!(
+        pusd.send(batch_mint(&pusd, operator, recipient, vec![3_000, 1_001])).unwrap_err(),
+        pusd_error(PusdError::RequiresApproval)
+    );
+    assert_eq!(
+        pusd.send(pusd.mint_by_operator_ix(operator, recipient, 4_001)).unwrap_err(),
+        pusd_error(PusdError::RequiresApproval)
+    );
+    pusd.send(pusd.mint_by_operator_ix(operator, recipient, 4_000)).unwrap();
+    assert_eq!(pusd.balance(&recipient), THRESHOLD);
+}
+
+#[test]
+fn a_zero_threshold_disables_the_limit() {
+    let (mut pusd, recipient) = setup();
+    pusd.update_state(|state| state.large_mint_threshold = 0);
+    let operator = pusd.operator;
+
+    for _ in 0..3 {
+        pusd.send(pusd.mint_by_operator_ix(operator, recipient, 100 * THRESHOLD)).unwrap();
+    }
+    assert_eq!(pusd.balance(&recipient), 300 * THRESHOLD);
+}
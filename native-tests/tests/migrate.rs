@@ -0,0 +1,122 @@
+mod common;
+
+use anchor_lang::prelude::{Pubkey, Rent};
+use anchor_lang::{AccountDeserialize, Discriminator};
+use common::*;
+use pusd_spl::migrate::migrate_account_data;
+use pusd_spl::{ProgramState, PusdError, Role, RoleStatus, UserRole};
+use pusd_native_tests::Account;
+
+// UserRole before versioning: discriminator, user, role, bump, role_active_time
+fn user_role_v1(user: Pubkey, role: u8, bump: u8, role_active_time: i64) -> Vec<u8> {
+    let mut data = UserRole::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(user.as_ref());
+    data.push(role);
+    data.push(bump);
+    data.extend_from_slice(&role_active_time.to_le_bytes());
+    data
+}
+
+// ProgramState before versioning: discriminator, is_initialized, bump
+fn program_state_v1(is_initialized: bool, bump: u8) -> Vec<u8> {
+    let mut data = ProgramState::DISCRIMINATOR.to_vec();
+    data.push(is_initialized as u8);
+    data.push(bump);
+    data
+}
+
+#[test]
+fn v1_layouts_match_legacy_sizes() {
+    assert_eq!(user_role_v1(new_key(), 0, 255, 0).len(), 50);
+    assert_eq!(user_role_v1(new_key(), 0, 255, 0).len(), UserRole::V1_LEN);
+    assert_eq!(program_state_v1(true, 254).len(), 10);
+    assert_eq!(program_state_v1(true, 254).len(), ProgramState::V1_LEN);
+}
+
+#[test]
+fn migrates_v1_user_role() {
+    let user = new_key();
+    let migrated = migrate_account_data(&user_role_v1(user, 2, 253, 1_700_086_400)).unwrap();
+    assert_eq!(migrated.len(), UserRole::LEN);
+
+    let user_role = UserRole::try_deserialize(&mut migrated.as_slice()).unwrap();
+    assert_eq!(user_role.version, UserRole::VERSION);
+    assert_eq!(user_role.user, user);
+    assert_eq!(user_role.role, Role::Operator);
+    assert_eq!(user_role.bump, 253);
+    assert_eq!(user_role.role_active_time, 1_700_086_400);
+    assert_eq!(user_role.status, RoleStatus::Pending);
+}
+
+#[test]
+fn migrates_v1_program_state_with_defaults() {
+    let migrated = migrate_account_data(&program_state_v1(true, 254)).unwrap();
+    assert_eq!(migrated.len(), ProgramState::LEN);
+
+    let program_state = ProgramState::try_deserialize(&mut migrated.as_slice()).unwrap();
+    assert_eq!(program_state.version, ProgramState::VERSION);
+    assert!(program_state.is_initialized);
+    assert_eq!(program_state.bump, 254);
+    assert!(!program_state.direct_mint_disabled);
+    assert_eq!(program_state.large_mint_threshold, 0);
+    assert_eq!(program_state.redemption_fee_bps, 0);
+    assert!(!program_state.kyc_required);
+    assert_eq!(program_state.admin, Pubkey::default());
+    assert!(!program_state.upgrade_authority_renounced);
+    assert!(!program_state.paused);
+}
+
+#[test]
+fn migrates_a_deployed_v1_program_state() {
+    let mut pusd = Pusd::new();
+    let legacy = program_state_v1(true, 253);
+    let lamports = Rent::default().minimum_balance(legacy.len());
+    pusd.svm.set_account(program_state_pda(), Account::new(lamports, legacy, pusd_spl::ID));
+
+    let migrate = ix(
+        pusd_spl::accounts::MigrateAccount {
+            payer: pusd.owner,
+            account: program_state_pda(),
+            sunset_state: sunset_pda(),
+            system_program: anchor_lang::system_program::ID,
+        },
+        pusd_spl::instruction::MigrateAccount {},
+    );
+    pusd.send(migrate.clone()).unwrap();
+
+    let account = pusd.svm.account(&program_state_pda()).unwrap();
+    assert_eq!(account.data.len(), ProgramState::LEN);
+    assert_eq!(account.lamports, Rent::default().minimum_balance(ProgramState::LEN));
+    let program_state = pusd.state();
+    assert_eq!(program_state.version, ProgramState::VERSION);
+    assert!(program_state.is_initialized);
+    assert_eq!(program_state.bump, 253);
+
+    assert_eq!(pusd.send(migrate).unwrap_err(), pusd_error(PusdError::AccountAlreadyMigrated));
+}
+
+#[test]
+fn migrated_accounts_cannot_be_migrated_again() {
+    let user_role = migrate_account_data(&user_role_v1(new_key(), 0, 255, 0)).unwrap();
+    assert_eq!(migrate_account_data(&user_role).unwrap_err(), PusdError::AccountAlreadyMigrated.into());
+
+    let program_state = migrate_account_data(&program_state_v1(true, 255)).unwrap();
+    assert_eq!(migrate_account_data(&program_state).unwrap_err(), PusdError::AccountAlreadyMigrated.into());
+}
+
+#[test]
+fn rejects_unknown_accounts_and_sizes() {
+    let mut truncated = user_role_v1(new_key(), 0, 255, 0);
+    truncated.pop();
+    assert_eq!(migrate_account_data(&truncated).unwrap_err(), PusdError::UnsupportedAccountLayout.into());
+
+    let mut other = program_state_v1(true, 255);
+    other[..8].copy_from_slice(&[1; 8]);
+    assert_eq!(migrate_account_data(&other).unwrap_err(), PusdError::UnsupportedAccountLayout.into());
+
+    let mut between_layouts = program_state_v1(true, 255);
+    between_layouts.push(0);
+    assert_eq!(migrate_account_data(&between_layouts).unwrap_err(), PusdError::UnsupportedAccountLayout.into());
+
+    assert!(migrate_account_data(&[0; 4]).is_err());
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token_2022::spl_token_2022;
+use common::*;
+use pusd_spl::PusdError;
+
+fn transfer_authority(pusd: &Pusd, mint: Pubkey, current_authority: Pubkey) -> Instruction {
+    ix(
+        pusd_spl::accounts::TransferAuthority {
+            program_state: program_state_pda(),
+            mint,
+            current_authority,
+            program_data: program_data_address(),
+            payer: pusd.upgrade_authority,
+            token_program: spl_token_2022::ID,
+        },
+        pusd_spl::instruction::TransferMintAuthorityToPda {},
+    )
+}
+
+#[test]
+fn records_the_canonical_mint() {
+    let pusd = Pusd::new();
+    assert_eq!(pusd.state().mint, pusd.mint);
+    assert_eq!(pusd.mint_state(&pusd.mint).mint_authority, COption::Some(mint_authority_pda()));
+}
+
+#[test]
+fn the_canonical_mint_cannot_be_replaced() {
+    let mut pusd = Pusd::new();
+    let (other_mint, authority) = (new_key(), pusd.upgrade_authority);
+    pusd.create_mint(other_mint, authority, DECIMALS);
+
+    assert_eq!(
+        pusd.send(transfer_authority(&pusd, other_mint, authority)).unwrap_err(),
+        pusd_error(PusdError::MintAlreadyRecorded)
+    );
+    assert_eq!(pusd.state().mint, pusd.mint);
+    assert_eq!(pusd.mint_state(&other_mint).mint_authority, COption::Some(authority));
+}
+
+#[test]
+fn records_a_mint_already_under_pda_control() {
+    // ProgramState migrated from a layout without the canonical mint
+    let mut pusd = Pusd::new();
+    pusd.update_state(|state| state.mint = Pubkey::default());
+    let (mint, authority) = (pusd.mint, pusd.upgrade_authority);
+
+    pusd.send(transfer_authority(&pusd, mint, authority)).unwrap();
+    assert_eq!(pusd.state().mint, mint);
+}
+
+#[test]
+fn requires_governance() {
+    let mut pusd = Pusd::new();
+    pusd.update_state(|state| state.mint = Pubkey::default());
+    let (mint, operator) = (pusd.mint, pusd.operator);
+
+    let mut instruction = transfer_authority(&pusd, mint, operator);
+    instruction.accounts[4].pubkey = operator;
+    assert_eq!(pusd.send(instruction).unwrap_err(), pusd_error(PusdError::OnlyUpgradeAuthority));
+}
@@ -0,0 +1,156 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_spl::associated_token::spl_associated_token_account;
+use anchor_spl::token_2022::spl_token_2022;
+use common::*;
+use pusd_spl::{PusdError, Role};
+
+fn by_operator(pusd: &Pusd, operator: Pubkey, wallet: Pubkey, recipient: Pubkey, amount: u64) -> Instruction {
+    ix(
+        pusd_spl::accounts::MintToWalletByOperator {
+            operator_role: role_pda(&operator),
+            program_state: program_state_pda(),
+            operator,
+            mint_window: mint_window_pda(&operator),
+            mint: pusd.mint,
+            reserve_attestation: reserve_attestation_pda(&pusd.mint),
+            wallet,
+            recipient,
+            mint_authority: mint_authority_pda(),
+            token_program: spl_token_2022::ID,
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        pusd_spl::instruction::MintToWalletByOperator { amount },
+    )
+}
+
+fn by_contract(pusd: &Pusd, contract: Pubkey, wallet: Pubkey, amount: u64) -> Instruction {
+    ix(
+        pusd_spl::accounts::MintToWalletByContract {
+            contract_role: role_pda(&contract),
+            authorized_contract: contract,
+            mint: pusd.mint,
+            program_state: program_state_pda(),
+            reserve_attestation: reserve_attestation_pda(&pusd.mint),
+            wallet,
+            recipient: pusd.ata(&wallet, &pusd.mint),
+            mint_authority: mint_authority_pda(),
+            token_program: spl_token_2022::ID,
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        pusd_spl::instruction::MintToWallet { amount },
+    )
+}
+
+#[test]
+fn operator_mint_creates_missing_ata_paid_by_operator() {
+    let mut pusd = Pusd::new();
+    let (operator, wallet) = (pusd.operator, new_key());
+    let ata = pusd.ata(&wallet, &pusd.mint);
+    assert!(pusd.svm.account(&ata).is_none());
+    let operator_lamports = pusd.svm.account(&operator).unwrap().lamports;
+
+    pusd.send(by_operator(&pusd, operator, wallet, ata, 1_000)).unwrap();
+
+    let token_account = pusd.token_account(&ata);
+    assert_eq!(token_account.owner, wallet);
+    assert_eq!(token_account.mint, pusd.mint);
+    assert_eq!(token_account.amount, 1_000);
+    assert!(pusd.svm.account(&operator).unwrap().lamports < operator_lamports);
+    assert_eq!(pusd.supply(), 1_000);
+
+    // the second mint reuses the existing account
+    pusd.send(by_operator(&pusd, operator, wallet, ata, 500)).unwrap();
+    assert_eq!(pusd.balance(&ata), 1_500);
+}
+
+#[test]
+fn contract_mint_to_wallet_with_existing_ata() {
+    let mut pusd = Pusd::new();
+    let contract = new_key();
+    pusd.set_role(contract, Role::AuthorizedContract);
+    let wallet = new_key();
+    let ata = pusd.create_ata(&wallet, &pusd.mint.clone());
+
+    pusd.send(by_contract(&pusd, contract, wallet, 2_000)).unwrap();
+    assert_eq!(pusd.balance(&ata), 2_000);
+}
+
+#[test]
+fn contract_mint_creates_missing_ata() {
+    let mut pusd = Pusd::new();
+    let contract = new_key();
+    pusd.set_role(contract, Role::AuthorizedContract);
+    let wallet = new_key();
+
+    pusd.send(by_contract(&pusd, contract, wallet, 2_000)).unwrap();
+    assert_eq!(pusd.balance(&pusd.ata(&wallet, &pusd.mint)), 2_000);
+}
+
+#[test]
+fn rejects_callers_without_the_role() {
+    let mut pusd = Pusd::new();
+    let wallet = new_key();
+    let ata = pusd.ata(&wallet, &pusd.mint);
+
+    // an Operator is not an AuthorizedContract and vice versa
+    let operator = pusd.operator;
+    assert_eq!(
+        pusd.send(by_contract(&pusd, operator, wallet, 1)).unwrap_err(),
+        pusd_error(PusdError::Unauthorized)
+    );
+    let contract = new_key();
+    pusd.set_role(contract, Role::AuthorizedContract);
+    assert_eq!(
+        pusd.send(by_operator(&pusd, contract, wallet, ata, 1)).unwrap_err(),
+        pusd_error(PusdError::Unauthorized)
+    );
+
+    // no role account at all
+    let stranger = new_key();
+    pusd.svm.airdrop(&stranger, 1_000_000_000);
+    assert_eq!(
+        pusd.send(by_operator(&pusd, stranger, wallet, ata, 1)).unwrap_err(),
+        anchor_error(anchor_lang::error::ErrorCode::AccountNotInitialized)
+    );
+    assert!(pusd.svm.account(&ata).is_none());
+    assert_eq!(pusd.supply(), 0);
+}
+
+#[test]
+fn rejects_a_recipient_that_is_not_the_wallet_ata() {
+    let mut pusd = Pusd::new();
+    let operator = pusd.operator;
+    let (wallet, other) = (new_key(), new_key());
+    let other_ata = pusd.create_ata(&other, &pusd.mint.clone());
+
+    assert_eq!(
+        pusd.send(by_operator(&pusd, operator, wallet, other_ata, 1)).unwrap_err(),
+        anchor_error(anchor_lang::error::ErrorCode::ConstraintTokenOwner)
+    );
+    assert_eq!(pusd.balance(&other_ata), 0);
+}
+
+#[test]
+fn operator_path_honours_mint_controls() {
+    let mut pusd = Pusd::new();
+    let operator = pusd.operator;
+    let wallet = new_key();
+    let ata = pusd.ata(&wallet, &pusd.mint);
+
+    pusd.update_state(|state| state.large_mint_threshold = 1_000);
+    assert_eq!(
+        pusd.send(by_operator(&pusd, operator, wallet, ata, 1_001)).unwrap_err(),
+        pusd_error(PusdError::RequiresApproval)
+    );
+
+    pusd.update_state(|state| state.direct_mint_disabled = true);
+    assert_eq!(
+        pusd.send(by_operator(&pusd, operator, wallet, ata, 1)).unwrap_err(),
+        pusd_error(PusdError::DirectMintDisabled)
+    );
+}
@@ -0,0 +1,179 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_spl::token_2022::spl_token_2022;
+use common::*;
+use pusd_spl::{MintReceipt, PusdError, Role, MINT_RECEIPT_MIN_AGE_SLOTS};
+
+fn receipt_pda(contract: &Pubkey, reference: &[u8; 32]) -> Pubkey {
+    pda(&[b"mint_receipt", contract.as_ref(), reference])
+}
+
+/// A mint of a reference issued at the current slot
+fn mint_with_reference(pusd: &Pusd, contract: Pubkey, recipient: Pubkey, reference: [u8; 32], amount: u64) -> Instruction {
+    mint_issued_at(pusd, contract, recipient, reference, pusd.svm.clock().slot, amount)
+}
+
+fn mint_issued_at(
+    pusd: &Pusd,
+    contract: Pubkey,
+    recipient: Pubkey,
+    reference: [u8; 32],
+    reference_slot: u64,
+    amount: u64,
+) -> Instruction {
+    ix(
+        pusd_spl::accounts::MintWithReference {
+            contract_role: role_pda(&contract),
+            authorized_contract: contract,
+            mint_receipt: receipt_pda(&contract, &reference),
+            mint: pusd.mint,
+            program_state: program_state_pda(),
+            reserve_attestation: reserve_attestation_pda(&pusd.mint),
+            recipient,
+            mint_authority: mint_authority_pda(),
+            token_program: spl_token_2022::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        pusd_spl::instruction::MintWithReference { reference, reference_slot, amount },
+    )
+}
+
+fn close_receipt(owner: Pubkey, receipt: Pubkey) -> Instruction {
+    ix(
+        pusd_spl::accounts::CloseMintReceipt { owner_role: role_pda(&owner), owner, mint_receipt: receipt },
+        pusd_spl::instruction::CloseMintReceipt {},
+    )
+}
+
+fn setup() -> (Pusd, Pubkey, Pubkey) {
+    let mut pusd = Pusd::new();
+    let contract = new_key();
+    pusd.set_role(contract, Role::AuthorizedContract);
+    let recipient = pusd.create_ata(&new_key(), &pusd.mint.clone());
+    (pusd, contract, recipient)
+}
+
+#[test]
+fn records_a_receipt_per_caller_and_reference() {
+    let (mut pusd, contract, recipient) = setup();
+
+    pusd.send(mint_with_reference(&pusd, contract, recipient, [7; 32], 1_000)).unwrap();
+    assert_eq!(pusd.balance(&recipient), 1_000);
+
+    let receipt: MintReceipt = pusd.anchor_account(&receipt_pda(&contract, &[7; 32])).unwrap();
+    assert_eq!(receipt.reference, [7; 32]);
+    assert_eq!(receipt.minter, contract);
+    assert_eq!(receipt.recipient, recipient);
+    assert_eq!(receipt.amount, 1_000);
+}
+
+#[test]
+fn a_retried_reference_mints_once() {
+    let (mut pusd, contract, recipient) = setup();
+    let issued = pusd.svm.clock().slot;
+    pusd.send(mint_issued_at(&pusd, contract, recipient, [7; 32], issued, 1_000)).unwrap();
+
+    // a retry late in the reference's lifetime still finds the receipt
+    pusd.svm.warp(7 * 24 * 60 * 60, MINT_RECEIPT_MIN_AGE_SLOTS - 1);
+    pusd.attest(RESERVES);
+    assert_eq!(
+        pusd.send(mint_issued_at(&pusd, contract, recipient, [7; 32], issued, 1_000)).unwrap_err(),
+        pusd_error(PusdError::DuplicateMintReference)
+    );
+    assert_eq!(pusd.supply(), 1_000);
+}
+
+#[test]
+fn callers_cannot_consume_each_others_references() {
+    let (mut pusd, contract, recipient) = setup();
+    let other = new_key();
+    pusd.set_role(other, Role::AuthorizedContract);
+
+    // another contract minting first does not block this contract's reference
+    pusd.send(mint_with_reference(&pusd, other, recipient, [7; 32], 1)).unwrap();
+    pusd.send(mint_with_reference(&pusd, contract, recipient, [7; 32], 1_000)).unwrap();
+    assert_eq!(pusd.balance(&recipient), 1_001);
+}
+
+#[test]
+fn refuses_stale_and_future_references() {
+    let (mut pusd, contract, recipient) = setup();
+    pusd.svm.warp(0, MINT_RECEIPT_MIN_AGE_SLOTS);
+    let slot = pusd.svm.clock().slot;
+
+    for reference_slot in [slot - MINT_RECEIPT_MIN_AGE_SLOTS, slot + 1] {
+        assert_eq!(
+            pusd.send(mint_issued_at(&pusd, contract, recipient, [7; 32], reference_slot, 1)).unwrap_err(),
+            pusd_error(PusdError::ReferenceExpired)
+        );
+    }
+    pusd.send(mint_issued_at(&pusd, contract, recipient, [7; 32], slot - MINT_RECEIPT_MIN_AGE_SLOTS + 1, 1)).unwrap();
+}
+
+#[test]
+fn owner_closes_old_receipts() {
+    let (mut pusd, contract, recipient) = setup();
+    let owner = pusd.owner;
+    let receipt = receipt_pda(&contract, &[7; 32]);
+    pusd.send(mint_with_reference(&pusd, contract, recipient, [7; 32], 1_000)).unwrap();
+
+    pusd.svm.warp(0, MINT_RECEIPT_MIN_AGE_SLOTS - 1);
+    assert_eq!(
+        pusd.send(close_receipt(owner, receipt)).unwrap_err(),
+        pusd_error(PusdError::ReceiptTooRecent)
+    );
+
+    pusd.svm.warp(0, 1);
+    let rent = pusd.svm.account(&receipt).unwrap().lamports;
+    let owner_before = pusd.svm.account(&owner).unwrap().lamports;
+    pusd.send(close_receipt(owner, receipt)).unwrap();
+    assert!(pusd.svm.account(&receipt).is_none());
+    assert_eq!(pusd.svm.account(&owner).unwrap().lamports, owner_before + rent);
+}
+
+#[test]
+fn only_an_owner_closes_receipts() {
+    let (mut pusd, contract, recipient) = setup();
+    let operator = pusd.operator;
+    let receipt = receipt_pda(&contract, &[7; 32]);
+    pusd.send(mint_with_reference(&pusd, contract, recipient, [7; 32], 1_000)).unwrap();
+    pusd.svm.warp(0, MINT_RECEIPT_MIN_AGE_SLOTS);
+
+    assert_eq!(
+        pusd.send(close_receipt(operator, receipt)).unwrap_err(),
+        pusd_error(PusdError::Unauthorized)
+    );
+    assert!(pusd.svm.account(&receipt).is_some());
+}
+
+#[test]
+fn a_closed_receipts_reference_cannot_mint_again() {
+    let (mut pusd, contract, recipient) = setup();
+    let owner = pusd.owner;
+    let issued = pusd.svm.clock().slot;
+    pusd.send(mint_issued_at(&pusd, contract, recipient, [7; 32], issued, 1_000)).unwrap();
+
+    pusd.svm.warp(7 * 24 * 60 * 60, MINT_RECEIPT_MIN_AGE_SLOTS);
+    pusd.attest(RESERVES);
+    pusd.send(close_receipt(owner, receipt_pda(&contract, &[7; 32]))).unwrap();
+
+    assert_eq!(
+        pusd.send(mint_issued_at(&pusd, contract, recipient, [7; 32], issued, 1_000)).unwrap_err(),
+        pusd_error(PusdError::ReferenceExpired)
+    );
+    assert_eq!(pusd.supply(), 1_000);
+}
+
+#[test]
+fn requires_the_authorized_contract_role() {
+    let (mut pusd, _, recipient) = setup();
+    let operator = pusd.operator;
+
+    assert_eq!(
+        pusd.send(mint_with_reference(&pusd, operator, recipient, [7; 32], 1)).unwrap_err(),
+        pusd_error(PusdError::Unauthorized)
+    );
+    assert!(pusd.svm.account(&receipt_pda(&operator, &[7; 32])).is_none());
+}
@@ -0,0 +1,172 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_spl::token_2022::spl_token_2022;
+use common::{ed25519_instruction, verify, Signer, *};
+use pusd_spl::bridge::parse_ed25519_signers;
+use pusd_spl::permit::permit_message;
+use pusd_spl::{PermitNonce, PusdError, Role, PERMIT_MESSAGE_DOMAIN};
+use solana_sdk_ids::{ed25519_program, sysvar};
+
+const MINT: Pubkey = Pubkey::new_from_array([7; 32]);
+const RECIPIENT: Pubkey = Pubkey::new_from_array([9; 32]);
+
+#[test]
+fn permit_message_layout() {
+    let message = permit_message(&MINT, &RECIPIENT, 1_000, 5, 1_700_000_000);
+    let domain = PERMIT_MESSAGE_DOMAIN.len();
+    assert_eq!(message.len(), domain + 32 + 32 + 32 + 8 + 8 + 8);
+    assert_eq!(&message[..domain], PERMIT_MESSAGE_DOMAIN);
+    assert_eq!(&message[domain..domain + 32], pusd_spl::ID.as_ref());
+    let fields = domain + 32;
+    assert_eq!(&message[fields..fields + 32], MINT.as_ref());
+    assert_eq!(&message[fields + 32..fields + 64], RECIPIENT.as_ref());
+    assert_eq!(&message[fields + 64..fields + 72], &1_000u64.to_le_bytes());
+    assert_eq!(&message[fields + 72..fields + 80], &5u64.to_le_bytes());
+    assert_eq!(&message[fields + 80..], &1_700_000_000i64.to_le_bytes());
+}
+
+#[test]
+fn hsm_signed_permit_round_trips_through_precompile_data() {
+    let hsm = Signer::new(42);
+    let message = permit_message(&MINT, &RECIPIENT, 1_000, 5, 1_700_000_000);
+    let signature = hsm.sign(&message);
+    assert!(verify(&hsm.public, &message, &signature));
+
+    let data = ed25519_instruction(&[(&hsm.public, &message, signature)]);
+    let signers = parse_ed25519_signers(&data).unwrap();
+    assert_eq!(signers, vec![(hsm.pubkey(), message.as_slice())]);
+}
+
+#[test]
+fn signature_does_not_cover_altered_permits() {
+    let hsm = Signer::new(42);
+    let message = permit_message(&MINT, &RECIPIENT, 1_000, 5, 1_700_000_000);
+    let signature = hsm.sign(&message);
+
+    for altered in [
+        permit_message(&MINT, &MINT, 1_000, 5, 1_700_000_000),
+        permit_message(&MINT, &RECIPIENT, 1_001, 5, 1_700_000_000),
+        permit_message(&MINT, &RECIPIENT, 1_000, 6, 1_700_000_000),
+        permit_message(&MINT, &RECIPIENT, 1_000, 5, 1_700_000_001),
+    ] {
+        assert_ne!(altered, message);
+        assert!(!verify(&hsm.public, &altered, &signature));
+    }
+}
+
+fn permit_nonce_pda(signer: &Pubkey, nonce: u64) -> Pubkey {
+    pda(&[b"permit_nonce", signer.as_ref(), nonce.to_le_bytes().as_ref()])
+}
+
+fn signed(signer: &Signer, message: &[u8]) -> Instruction {
+    let data = ed25519_instruction(&[(&signer.public, message, signer.sign(message))]);
+    Instruction { program_id: ed25519_program::ID, accounts: vec![], data }
+}
+
+fn mint_with_permit(pusd: &Pusd, signer: Pubkey, recipient: Pubkey, amount: u64, nonce: u64, expiry: i64) -> Instruction {
+    ix(
+        pusd_spl::accounts::MintWithPermit {
+            signer_role: role_pda(&signer),
+            signer,
+            payer: pusd.upgrade_authority,
+            permit_nonce: permit_nonce_pda(&signer, nonce),
+            mint: pusd.mint,
+            program_state: program_state_pda(),
+            reserve_attestation: reserve_attestation_pda(&pusd.mint),
+            recipient,
+            mint_authority: mint_authority_pda(),
+            instructions: sysvar::instructions::ID,
+            token_program: spl_token_2022::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        pusd_spl::instruction::MintWithPermit { amount, nonce, expiry },
+    )
+}
+
+/// A deployment with an HSM key holding AuthorizedSigner and a recipient account
+fn permit_deployment() -> (Pusd, Signer, Pubkey, i64) {
+    let mut pusd = Pusd::new();
+    let hsm = Signer::new(42);
+    pusd.set_role(hsm.pubkey(), Role::AuthorizedSigner);
+    let mint = pusd.mint;
+    let recipient = pusd.create_ata(&new_key(), &mint);
+    let expiry = pusd.svm.clock().unix_timestamp + 600;
+    (pusd, hsm, recipient, expiry)
+}
+
+#[test]
+fn relayed_permit_mints_and_records_the_nonce() {
+    let (mut pusd, hsm, recipient, expiry) = permit_deployment();
+    let message = permit_message(&pusd.mint, &recipient, 1_000, 5, expiry);
+
+    let permit = mint_with_permit(&pusd, hsm.pubkey(), recipient, 1_000, 5, expiry);
+    pusd.svm.send(&[signed(&hsm, &message), permit]).unwrap();
+
+    assert_eq!(pusd.balance(&recipient), 1_000);
+    let nonce: PermitNonce = pusd.anchor_account(&permit_nonce_pda(&hsm.pubkey(), 5)).unwrap();
+    assert_eq!((nonce.signer, nonce.nonce, nonce.recipient, nonce.amount), (hsm.pubkey(), 5, recipient, 1_000));
+}
+
+#[test]
+fn permits_cannot_be_replayed() {
+    let (mut pusd, hsm, recipient, expiry) = permit_deployment();
+    let message = permit_message(&pusd.mint, &recipient, 1_000, 5, expiry);
+    let permit = mint_with_permit(&pusd, hsm.pubkey(), recipient, 1_000, 5, expiry);
+    pusd.svm.send(&[signed(&hsm, &message), permit.clone()]).unwrap();
+
+    assert!(pusd.svm.send(&[signed(&hsm, &message), permit]).is_err());
+    assert_eq!(pusd.balance(&recipient), 1_000);
+}
+
+#[test]
+fn permits_signed_for_another_program_are_rejected() {
+    let (mut pusd, hsm, recipient, expiry) = permit_deployment();
+    let mut message = permit_message(&pusd.mint, &recipient, 1_000, 5, expiry);
+    let domain = PERMIT_MESSAGE_DOMAIN.len();
+    message[domain..domain + 32].copy_from_slice(new_key().as_ref());
+
+    let permit = mint_with_permit(&pusd, hsm.pubkey(), recipient, 1_000, 5, expiry);
+    let result = pusd.svm.send(&[signed(&hsm, &message), permit]);
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::InvalidPermitSignature));
+}
+
+#[test]
+fn permits_must_match_every_field_and_be_signed_by_the_signer() {
+    let (mut pusd, hsm, recipient, expiry) = permit_deployment();
+    let message = permit_message(&pusd.mint, &recipient, 1_000, 5, expiry);
+
+    // Amount differs from the signed permit
+    let permit = mint_with_permit(&pusd, hsm.pubkey(), recipient, 1_001, 5, expiry);
+    let result = pusd.svm.send(&[signed(&hsm, &message), permit]);
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::InvalidPermitSignature));
+
+    // Signed by a key other than the named AuthorizedSigner
+    let permit = mint_with_permit(&pusd, hsm.pubkey(), recipient, 1_000, 5, expiry);
+    let result = pusd.svm.send(&[signed(&Signer::new(43), &message), permit]);
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::InvalidPermitSignature));
+
+    // No signature instruction at all
+    let permit = mint_with_permit(&pusd, hsm.pubkey(), recipient, 1_000, 5, expiry);
+    assert_eq!(pusd.send(permit).unwrap_err(), pusd_error(PusdError::InvalidPermitSignature));
+}
+
+#[test]
+fn expired_permits_and_unauthorized_signers_are_rejected() {
+    let (mut pusd, hsm, recipient, expiry) = permit_deployment();
+
+    pusd.svm.warp(601, 1);
+    let message = permit_message(&pusd.mint, &recipient, 1_000, 5, expiry);
+    let permit = mint_with_permit(&pusd, hsm.pubkey(), recipient, 1_000, 5, expiry);
+    let result = pusd.svm.send(&[signed(&hsm, &message), permit]);
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::PermitExpired));
+
+    let outsider = Signer::new(43);
+    pusd.set_role(outsider.pubkey(), Role::Operator);
+    let expiry = pusd.svm.clock().unix_timestamp + 600;
+    let message = permit_message(&pusd.mint, &recipient, 1_000, 6, expiry);
+    let permit = mint_with_permit(&pusd, outsider.pubkey(), recipient, 1_000, 6, expiry);
+    let result = pusd.svm.send(&[signed(&outsider, &message), permit]);
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::Unauthorized));
+}
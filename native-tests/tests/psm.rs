@@ -0,0 +1,202 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use common::*;
+use pusd_spl::{CollateralConfig, PusdError};
+
+fn update_collateral(signer: Pubkey, collateral_mint: Pubkey, price: u64, debt_ceiling: u64, enabled: bool) -> Instruction {
+    ix(
+        pusd_spl::accounts::UpdateCollateral {
+            owner_role: role_pda(&signer),
+            owner: signer,
+            collateral_config: collateral_config_pda(&collateral_mint),
+            sunset_state: sunset_pda(),
+        },
+        pusd_spl::instruction::UpdateCollateral { price, debt_ceiling, enabled },
+    )
+}
+
+fn config(pusd: &Pusd, collateral_mint: &Pubkey) -> CollateralConfig {
+    pusd.anchor_account(&collateral_config_pda(collateral_mint)).unwrap()
+}
+
+#[test]
+fn add_collateral_creates_the_config_and_vault() {
+    let mut pusd = Pusd::new();
+    let collateral = pusd.add_collateral(8, PRICE_ONE, 1_000_000);
+
+    let config = config(&pusd, &collateral);
+    assert_eq!(config.collateral_mint, collateral);
+    assert_eq!(config.pusd_mint, pusd.mint);
+    assert_eq!(config.vault, psm_vault_pda(&collateral));
+    assert_eq!((config.collateral_decimals, config.pusd_decimals), (8, DECIMALS));
+    assert_eq!(config.debt_ceiling, 1_000_000);
+    assert!(config.enabled);
+
+    let vault = pusd.token_account(&psm_vault_pda(&collateral));
+    assert_eq!(vault.mint, collateral);
+    assert_eq!(vault.owner, collateral_config_pda(&collateral));
+}
+
+#[test]
+fn swap_in_mints_pusd_and_fees_against_collateral() {
+    let mut pusd = Pusd::new();
+    pusd.update_state(|state| state.psm_fee_in_bps = 100);
+    let collateral = pusd.add_collateral(DECIMALS, PRICE_ONE, u64::MAX);
+    let (user, user_collateral, user_pusd) = pusd.psm_user(&collateral, 10_000);
+
+    let swap_in = pusd.swap_in_ix(user, collateral, user_collateral, user_pusd, 10_000);
+    pusd.send(swap_in).unwrap();
+
+    assert_eq!(pusd.balance(&user_collateral), 0);
+    assert_eq!(pusd.balance(&psm_vault_pda(&collateral)), 10_000);
+    assert_eq!(pusd.balance(&user_pusd), 9_900);
+    let treasury_token = pusd.treasury_token();
+    assert_eq!(pusd.balance(&treasury_token), 100);
+    assert_eq!(config(&pusd, &collateral).debt, 10_000);
+}
+
+#[test]
+fn swap_in_normalizes_decimals_in_the_protocols_favour() {
+    let mut pusd = Pusd::new();
+    // 8 decimal collateral worth 0.5 USD; 99 base units are worth 0.495 PUSD base units
+    let collateral = pusd.add_collateral(8, PRICE_ONE / 2, u64::MAX);
+    let (user, user_collateral, user_pusd) = pusd.psm_user(&collateral, 100_000_099);
+
+    let swap_in = pusd.swap_in_ix(user, collateral, user_collateral, user_pusd, 100_000_099);
+    pusd.send(swap_in).unwrap();
+    assert_eq!(pusd.balance(&user_pusd), 500_000);
+
+    let swap_in = pusd.swap_in_ix(user, collateral, user_collateral, user_pusd, 0);
+    assert_eq!(pusd.send(swap_in).unwrap_err(), pusd_error(PusdError::InvalidAmount));
+}
+
+#[test]
+fn swap_out_burns_pusd_and_releases_collateral() {
+    let mut pusd = Pusd::new();
+    pusd.update_state(|state| state.psm_fee_out_bps = 50);
+    let collateral = pusd.add_collateral(DECIMALS, PRICE_ONE, u64::MAX);
+    let (user, user_collateral, user_pusd) = pusd.psm_user(&collateral, 10_000);
+    let swap_in = pusd.swap_in_ix(user, collateral, user_collateral, user_pusd, 10_000);
+    pusd.send(swap_in).unwrap();
+
+    let swap_out = pusd.swap_out_ix(user, collateral, user_collateral, user_pusd, 4_000);
+    pusd.send(swap_out).unwrap();
+
+    assert_eq!(pusd.balance(&user_pusd), 6_000);
+    assert_eq!(pusd.balance(&user_collateral), 3_980);
+    assert_eq!(pusd.balance(&psm_vault_pda(&collateral)), 6_020);
+    let treasury_token = pusd.treasury_token();
+    assert_eq!(pusd.balance(&treasury_token), 20);
+    assert_eq!(config(&pusd, &collateral).debt, 6_020);
+    assert_eq!(pusd.supply(), 6_020);
+}
+
+#[test]
+fn swap_out_is_limited_by_vault_liquidity() {
+    let mut pusd = Pusd::new();
+    let collateral = pusd.add_collateral(DECIMALS, PRICE_ONE, u64::MAX);
+    let (user, user_collateral, user_pusd) = pusd.psm_user(&collateral, 1_000);
+    let swap_in = pusd.swap_in_ix(user, collateral, user_collateral, user_pusd, 1_000);
+    pusd.send(swap_in).unwrap();
+
+    // PUSD from elsewhere cannot drain more than this collateral's debt
+    let operator = pusd.operator;
+    pusd.send(pusd.mint_by_operator_ix(operator, user_pusd, 1_000)).unwrap();
+    let swap_out = pusd.swap_out_ix(user, collateral, user_collateral, user_pusd, 1_001);
+    assert_eq!(pusd.send(swap_out).unwrap_err(), pusd_error(PusdError::InsufficientPsmLiquidity));
+}
+
+#[test]
+fn swap_in_respects_the_debt_ceiling() {
+    let mut pusd = Pusd::new();
+    let collateral = pusd.add_collateral(DECIMALS, PRICE_ONE, 1_000);
+    let (user, user_collateral, user_pusd) = pusd.psm_user(&collateral, 2_000);
+
+    let swap_in = pusd.swap_in_ix(user, collateral, user_collateral, user_pusd, 1_001);
+    assert_eq!(pusd.send(swap_in).unwrap_err(), pusd_error(PusdError::DebtCeilingExceeded));
+    let swap_in = pusd.swap_in_ix(user, collateral, user_collateral, user_pusd, 1_000);
+    pusd.send(swap_in).unwrap();
+}
+
+#[test]
+fn disabled_collateral_rejects_swaps() {
+    let mut pusd = Pusd::new();
+    let collateral = pusd.add_collateral(DECIMALS, PRICE_ONE, u64::MAX);
+    let (user, user_collateral, user_pusd) = pusd.psm_user(&collateral, 1_000);
+    let owner = pusd.owner;
+    pusd.send(update_collateral(owner, collateral, PRICE_ONE, u64::MAX, false)).unwrap();
+
+    let swap_in = pusd.swap_in_ix(user, collateral, user_collateral, user_pusd, 1_000);
+    assert_eq!(pusd.send(swap_in).unwrap_err(), pusd_error(PusdError::CollateralDisabled));
+    let swap_out = pusd.swap_out_ix(user, collateral, user_collateral, user_pusd, 1_000);
+    assert_eq!(pusd.send(swap_out).unwrap_err(), pusd_error(PusdError::CollateralDisabled));
+}
+
+#[test]
+fn collateral_management_requires_the_owner() {
+    let mut pusd = Pusd::new();
+    let collateral = pusd.add_collateral(DECIMALS, PRICE_ONE, u64::MAX);
+    let operator = pusd.operator;
+
+    assert_eq!(
+        pusd.send(update_collateral(operator, collateral, 2 * PRICE_ONE, u64::MAX, true)).unwrap_err(),
+        pusd_error(PusdError::Unauthorized)
+    );
+
+    let (other, authority) = (new_key(), pusd.upgrade_authority);
+    pusd.create_mint(other, authority, DECIMALS);
+    let mut add = pusd.add_collateral_ix(other, PRICE_ONE, u64::MAX);
+    add.accounts[0].pubkey = role_pda(&operator);
+    add.accounts[1].pubkey = operator;
+    assert_eq!(pusd.send(add).unwrap_err(), pusd_error(PusdError::Unauthorized));
+    assert!(pusd.svm.account(&collateral_config_pda(&other)).is_none());
+
+    assert_eq!(
+        pusd.send(pusd.add_collateral_ix(other, 0, u64::MAX)).unwrap_err(),
+        pusd_error(PusdError::InvalidPrice)
+    );
+}
+
+#[test]
+fn swaps_are_bound_to_the_configured_pusd_mint() {
+    let mut pusd = Pusd::new();
+    let collateral = pusd.add_collateral(DECIMALS, PRICE_ONE, u64::MAX);
+    let (user, user_collateral, _) = pusd.psm_user(&collateral, 1_000);
+
+    // a second mint under the same mint authority PDA must not be issued through the PSM
+    let (other, authority) = (new_key(), pusd.upgrade_authority);
+    pusd.create_mint(other, authority, DECIMALS);
+    let other_pusd = pusd.create_ata(&user, &other);
+    let mut swap_in = pusd.swap_in_ix(user, collateral, user_collateral, other_pusd, 1_000);
+    swap_in.accounts[7].pubkey = other;
+    assert_eq!(pusd.send(swap_in).unwrap_err(), pusd_error(PusdError::InvalidConfig));
+
+    // collateral accounts of another mint are rejected by the account constraints
+    let wrong_mint = new_key();
+    pusd.create_mint(wrong_mint, authority, DECIMALS);
+    let wrong_collateral = pusd.create_ata(&user, &wrong_mint);
+    let user_pusd = pusd.ata(&user, &pusd.mint);
+    let swap_in = pusd.swap_in_ix(user, collateral, wrong_collateral, user_pusd, 1_000);
+    assert_eq!(
+        pusd.send(swap_in).unwrap_err(),
+        anchor_error(anchor_lang::error::ErrorCode::ConstraintTokenMint)
+    );
+}
+
+#[test]
+fn swap_in_only_issues_the_recorded_pusd_mint() {
+    let mut pusd = Pusd::new();
+    // a second mint the PDA can sign for, configured as a collateral's PUSD mint
+    let other = new_key();
+    pusd.create_mint(other, mint_authority_pda(), DECIMALS);
+    let pusd_mint = std::mem::replace(&mut pusd.mint, other);
+    let collateral = pusd.add_collateral(DECIMALS, PRICE_ONE, u64::MAX);
+    let (user, user_collateral, user_other) = pusd.psm_user(&collateral, 1_000);
+
+    let swap_in = pusd.swap_in_ix(user, collateral, user_collateral, user_other, 1_000);
+    assert_eq!(pusd.send(swap_in).unwrap_err(), pusd_error(PusdError::InvalidMint));
+    assert_eq!(pusd.balance(&user_other), 0);
+    pusd.mint = pusd_mint;
+}
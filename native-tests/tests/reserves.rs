@@ -0,0 +1,90 @@
+mod common;
+
+use common::*;
+use pusd_spl::{PusdError, DEFAULT_RESERVE_STALENESS_WINDOW};
+
+#[test]
+fn fiat_mints_are_capped_by_attested_reserves() {
+    let mut pusd = Pusd::new();
+    let (operator, recipient) = (pusd.operator, pusd.create_ata(&new_key(), &pusd.mint.clone()));
+    pusd.attest(1_000);
+
+    pusd.send(pusd.mint_by_operator_ix(operator, recipient, 600)).unwrap();
+    assert_eq!(
+        pusd.send(pusd.mint_by_operator_ix(operator, recipient, 401)).unwrap_err(),
+        pusd_error(PusdError::InsufficientReserves)
+    );
+    pusd.send(pusd.mint_by_operator_ix(operator, recipient, 400)).unwrap();
+    assert_eq!(pusd.supply(), 1_000);
+}
+
+#[test]
+fn fiat_mints_require_a_fresh_attestation() {
+    let mut pusd = Pusd::new();
+    let (operator, recipient) = (pusd.operator, pusd.create_ata(&new_key(), &pusd.mint.clone()));
+
+    pusd.svm.warp(DEFAULT_RESERVE_STALENESS_WINDOW + 1, 1);
+    assert_eq!(
+        pusd.send(pusd.mint_by_operator_ix(operator, recipient, 1)).unwrap_err(),
+        pusd_error(PusdError::ReserveAttestationStale)
+    );
+
+    pusd.attest(RESERVES);
+    pusd.send(pusd.mint_by_operator_ix(operator, recipient, 1)).unwrap();
+}
+
+#[test]
+fn psm_issuance_is_not_gated_on_the_attestation() {
+    let mut pusd = Pusd::new();
+    let collateral = pusd.add_collateral(DECIMALS, PRICE_ONE, u64::MAX);
+    let (user, user_collateral, user_pusd) = pusd.psm_user(&collateral, 5_000);
+
+    // reserves below the supply and a stale attestation do not block collateral-backed mints
+    pusd.attest(1_000);
+    pusd.svm.warp(DEFAULT_RESERVE_STALENESS_WINDOW + 1, 1);
+    let swap_in = pusd.swap_in_ix(user, collateral, user_collateral, user_pusd, 5_000);
+    pusd.send(swap_in).unwrap();
+    assert_eq!(pusd.balance(&user_pusd), 5_000);
+}
+
+#[test]
+fn psm_supply_still_counts_against_fiat_mints() {
+    let mut pusd = Pusd::new();
+    let collateral = pusd.add_collateral(DECIMALS, PRICE_ONE, u64::MAX);
+    let (user, user_collateral, user_pusd) = pusd.psm_user(&collateral, 600);
+    let swap_in = pusd.swap_in_ix(user, collateral, user_collateral, user_pusd, 600);
+    pusd.send(swap_in).unwrap();
+
+    pusd.attest(1_000);
+    let operator = pusd.operator;
+    assert_eq!(
+        pusd.send(pusd.mint_by_operator_ix(operator, user_pusd, 401)).unwrap_err(),
+        pusd_error(PusdError::InsufficientReserves)
+    );
+    pusd.send(pusd.mint_by_operator_ix(operator, user_pusd, 400)).unwrap();
+}
+
+#[test]
+fn attestations_are_pinned_to_the_pusd_mint() {
+    let mut pusd = Pusd::new();
+    let (other_mint, authority, attestor) = (new_key(), pusd.upgrade_authority, pusd.attestor);
+    pusd.create_mint(other_mint, authority, DECIMALS);
+
+    let attest = ix(
+        pusd_spl::accounts::AttestReserves {
+            attestor_role: role_pda(&attestor),
+            attestor,
+            program_state: program_state_pda(),
+            mint: other_mint,
+            reserve_attestation: reserve_attestation_pda(&other_mint),
+            system_program: anchor_lang::system_program::ID,
+        },
+        pusd_spl::instruction::AttestReserves {
+            reserve_amount: RESERVES,
+            report_hash: [1; 32],
+            report_uri: String::new(),
+        },
+    );
+    assert_eq!(pusd.send(attest).unwrap_err(), pusd_error(PusdError::InvalidMint));
+    assert!(pusd.svm.account(&reserve_attestation_pda(&other_mint)).is_none());
+}
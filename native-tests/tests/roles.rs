@@ -0,0 +1,279 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use common::*;
+use pusd_spl::{ProgramState, PusdError, Role, RoleStatus, UserRole, ROLE_ACTIVATION_DELAY};
+
+fn user_role(status: RoleStatus, role_active_time: i64) -> UserRole {
+    UserRole {
+        version: UserRole::VERSION,
+        user: new_key(),
+        role: Role::Operator,
+        bump: 255,
+        role_active_time,
+        status,
+    }
+}
+
+fn add_role(owner: Pubkey, user: Pubkey, role: Role) -> Instruction {
+    ix(
+        pusd_spl::accounts::AddRole {
+            owner_role: role_pda(&owner),
+            owner,
+            user_role: role_pda(&user),
+            sunset_state: sunset_pda(),
+            system_program: anchor_lang::system_program::ID,
+        },
+        pusd_spl::instruction::AddRole { user, role },
+    )
+}
+
+fn cancel_pending_role(owner: Pubkey, user: Pubkey) -> Instruction {
+    ix(
+        pusd_spl::accounts::RemoveRole {
+            owner_role: role_pda(&owner),
+            owner,
+            user_role: role_pda(&user),
+            sunset_state: sunset_pda(),
+        },
+        pusd_spl::instruction::CancelPendingRole {},
+    )
+}
+
+fn update_role_status(owner: Pubkey, user: Pubkey) -> pusd_spl::accounts::UpdateRoleStatus {
+    pusd_spl::accounts::UpdateRoleStatus {
+        owner_role: role_pda(&owner),
+        owner,
+        user_role: role_pda(&user),
+        sunset_state: sunset_pda(),
+    }
+}
+
+fn suspend_role(owner: Pubkey, user: Pubkey) -> Instruction {
+    ix(update_role_status(owner, user), pusd_spl::instruction::SuspendRole {})
+}
+
+fn resume_role(owner: Pubkey, user: Pubkey) -> Instruction {
+    ix(update_role_status(owner, user), pusd_spl::instruction::ResumeRole {})
+}
+
+fn has_role(user: Pubkey, role: Role) -> Instruction {
+    ix(pusd_spl::accounts::HasRole { user_role: role_pda(&user) }, pusd_spl::instruction::HasRole {
+        _user: user,
+        _role: role,
+    })
+}
+
+fn renounce_role(holder: Pubkey, rent_recipient: Pubkey, other_owner: Option<Pubkey>) -> Instruction {
+    ix(
+        pusd_spl::accounts::RenounceRole {
+            holder,
+            user_role: role_pda(&holder),
+            program_state: program_state_pda(),
+            rent_recipient,
+            other_owner_role: other_owner.map(|owner| role_pda(&owner)),
+        },
+        pusd_spl::instruction::RenounceRole {},
+    )
+}
+
+fn status(pusd: &Pusd, user: &Pubkey) -> RoleStatus {
+    pusd.role(user).unwrap().status
+}
+
+#[test]
+fn pending_roles_activate_at_role_active_time() {
+    let pending = user_role(RoleStatus::Pending, 1_000);
+    assert_eq!(pending.effective_status(999), RoleStatus::Pending);
+    assert_eq!(pending.effective_status(1_000), RoleStatus::Active);
+    assert_eq!(pending.effective_status(i64::MAX), RoleStatus::Active);
+}
+
+#[test]
+fn suspended_roles_stay_suspended() {
+    let suspended = user_role(RoleStatus::Suspended, 1_000);
+    assert_eq!(suspended.effective_status(999), RoleStatus::Suspended);
+    assert_eq!(suspended.effective_status(i64::MAX), RoleStatus::Suspended);
+
+    let active = user_role(RoleStatus::Active, 0);
+    assert_eq!(active.effective_status(0), RoleStatus::Active);
+}
+
+#[test]
+fn renounced_rent_defaults_to_the_holder() {
+    let mut program_state = ProgramState {
+        version: ProgramState::VERSION,
+        is_initialized: true,
+        bump: 255,
+        direct_mint_disabled: false,
+        large_mint_threshold: 0,
+        psm_fee_in_bps: 0,
+        psm_fee_out_bps: 0,
+        redemption_fee_bps: 0,
+        kyc_required: false,
+        admin: new_key(),
+        upgrade_authority_renounced: false,
+        paused: false,
+        renounce_rent_recipient: Pubkey::default(),
+        mint: new_key(),
+        pending_admin: Pubkey::default(),
+    };
+    let holder = new_key();
+    assert_eq!(program_state.renounce_rent_recipient_for(holder), holder);
+
+    let owner = new_key();
+    program_state.renounce_rent_recipient = owner;
+    assert_eq!(program_state.renounce_rent_recipient_for(holder), owner);
+}
+
+#[test]
+fn only_active_roles_count_as_held() {
+    let pending = user_role(RoleStatus::Pending, 1_000);
+    assert!(!pending.has_active_role(Role::Operator, 999));
+    assert!(pending.has_active_role(Role::Operator, 1_000));
+    assert!(!pending.has_active_role(Role::Owner, 1_000));
+
+    let suspended = user_role(RoleStatus::Suspended, 0);
+    assert!(!suspended.has_active_role(Role::Operator, i64::MAX));
+}
+
+#[test]
+fn has_role_queries_existing_roles() {
+    let mut pusd = Pusd::new();
+    let (owner, operator) = (pusd.owner, pusd.operator);
+    pusd.send(has_role(operator, Role::Operator)).unwrap();
+    pusd.send(suspend_role(owner, operator)).unwrap();
+    pusd.send(has_role(operator, Role::Operator)).unwrap();
+
+    let result = pusd.send(has_role(new_key(), Role::Operator));
+    assert_eq!(result.unwrap_err(), anchor_error(anchor_lang::error::ErrorCode::AccountNotInitialized));
+}
+
+#[test]
+fn suspended_roles_are_rejected_by_require_role() {
+    let mut pusd = Pusd::new();
+    let (owner, operator, mint) = (pusd.owner, pusd.operator, pusd.mint);
+    let recipient = pusd.create_ata(&new_key(), &mint);
+
+    pusd.send(suspend_role(owner, operator)).unwrap();
+    assert_eq!(status(&pusd, &operator), RoleStatus::Suspended);
+    let result = pusd.send(pusd.mint_by_operator_ix(operator, recipient, 1_000));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::RoleSuspended));
+
+    pusd.send(resume_role(owner, operator)).unwrap();
+    assert_eq!(status(&pusd, &operator), RoleStatus::Active);
+    pusd.send(pusd.mint_by_operator_ix(operator, recipient, 1_000)).unwrap();
+    assert_eq!(pusd.balance(&recipient), 1_000);
+}
+
+#[test]
+fn suspend_and_resume_are_owner_only_and_follow_the_status() {
+    let mut pusd = Pusd::new();
+    let (owner, operator, guardian) = (pusd.owner, pusd.operator, pusd.guardian);
+
+    let result = pusd.send(suspend_role(guardian, operator));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::Unauthorized));
+    // An Owner cannot suspend itself
+    let result = pusd.send(suspend_role(owner, owner));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::Unauthorized));
+    let result = pusd.send(resume_role(owner, operator));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::RoleNotSuspended));
+
+    pusd.send(suspend_role(owner, operator)).unwrap();
+    let result = pusd.send(suspend_role(owner, operator));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::RoleSuspended));
+    let result = pusd.send(resume_role(guardian, operator));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::Unauthorized));
+}
+
+#[test]
+fn resumed_roles_keep_their_activation_time() {
+    let mut pusd = Pusd::new();
+    let (owner, user) = (pusd.owner, new_key());
+    pusd.send(add_role(owner, user, Role::Operator)).unwrap();
+    let role_active_time = pusd.role(&user).unwrap().role_active_time;
+
+    // Suspending a pending role cannot be used to skip the activation delay
+    pusd.send(suspend_role(owner, user)).unwrap();
+    pusd.send(resume_role(owner, user)).unwrap();
+    assert_eq!(status(&pusd, &user), RoleStatus::Pending);
+    assert_eq!(pusd.role(&user).unwrap().role_active_time, role_active_time);
+
+    pusd.send(suspend_role(owner, user)).unwrap();
+    pusd.svm.warp(ROLE_ACTIVATION_DELAY, 1);
+    pusd.send(resume_role(owner, user)).unwrap();
+    assert_eq!(status(&pusd, &user), RoleStatus::Active);
+}
+
+#[test]
+fn cancel_pending_role_closes_only_pending_roles() {
+    let mut pusd = Pusd::new();
+    let (owner, operator, user) = (pusd.owner, pusd.operator, new_key());
+    pusd.send(add_role(owner, user, Role::Operator)).unwrap();
+    let role_rent = pusd.svm.account(&role_pda(&user)).unwrap().lamports;
+    let owner_before = pusd.svm.account(&owner).unwrap().lamports;
+
+    let result = pusd.send(cancel_pending_role(operator, user));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::Unauthorized));
+
+    pusd.send(cancel_pending_role(owner, user)).unwrap();
+    assert!(pusd.role(&user).is_none());
+    assert_eq!(pusd.svm.account(&owner).unwrap().lamports, owner_before + role_rent);
+
+    // Active roles go through remove_role instead
+    let result = pusd.send(cancel_pending_role(owner, operator));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::RoleNotPending));
+    assert!(pusd.role(&operator).is_some());
+}
+
+#[test]
+fn the_last_owner_cannot_renounce() {
+    let mut pusd = Pusd::new();
+    let (owner, operator) = (pusd.owner, pusd.operator);
+
+    let result = pusd.send(renounce_role(owner, owner, None));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::LastOwner));
+    // The Owner's own role does not count as another Owner
+    let result = pusd.send(renounce_role(owner, owner, Some(owner)));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::LastOwner));
+    let result = pusd.send(renounce_role(owner, owner, Some(operator)));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::LastOwner));
+    assert!(pusd.role(&owner).is_some());
+}
+
+#[test]
+fn only_an_active_owner_lets_an_owner_renounce() {
+    let mut pusd = Pusd::new();
+    let (owner, other_owner) = (pusd.owner, new_key());
+    pusd.send(add_role(owner, other_owner, Role::Owner)).unwrap();
+
+    let result = pusd.send(renounce_role(owner, owner, Some(other_owner)));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::LastOwner));
+
+    pusd.svm.warp(ROLE_ACTIVATION_DELAY, 1);
+    pusd.send(suspend_role(owner, other_owner)).unwrap();
+    let result = pusd.send(renounce_role(owner, owner, Some(other_owner)));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::LastOwner));
+
+    pusd.send(resume_role(owner, other_owner)).unwrap();
+    let role_rent = pusd.svm.account(&role_pda(&owner)).unwrap().lamports;
+    let owner_before = pusd.svm.account(&owner).unwrap().lamports;
+    pusd.send(renounce_role(owner, owner, Some(other_owner))).unwrap();
+    assert!(pusd.role(&owner).is_none());
+    assert_eq!(pusd.svm.account(&owner).unwrap().lamports, owner_before + role_rent);
+
+    // The remaining Owner is now the last one
+    let result = pusd.send(renounce_role(other_owner, other_owner, Some(owner)));
+    assert_eq!(result.unwrap_err(), anchor_error(anchor_lang::error::ErrorCode::AccountNotInitialized));
+    let result = pusd.send(renounce_role(other_owner, other_owner, None));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::LastOwner));
+}
+
+#[test]
+fn other_roles_renounce_without_an_owner() {
+    let mut pusd = Pusd::new();
+    let operator = pusd.operator;
+    pusd.send(renounce_role(operator, operator, None)).unwrap();
+    assert!(pusd.role(&operator).is_none());
+}
@@ -0,0 +1,294 @@
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::token_2022::spl_token_2022;
+use common::*;
+use pusd_spl::{CollateralConfig, PusdError, RateBounds, Role, SunsetState, SUNSET_TIMELOCK};
+
+fn add_role(owner: Pubkey, user: Pubkey, role: Role) -> Instruction {
+    ix(
+        pusd_spl::accounts::AddRole {
+            owner_role: role_pda(&owner),
+            owner,
+            user_role: role_pda(&user),
+            sunset_state: sunset_pda(),
+            system_program: anchor_lang::system_program::ID,
+        },
+        pusd_spl::instruction::AddRole { user, role },
+    )
+}
+
+fn set_bridge_config(owner: Pubkey) -> Instruction {
+    ix(
+        pusd_spl::accounts::SetBridgeConfig {
+            owner_role: role_pda(&owner),
+            owner,
+            bridge_config: pda(&[b"bridge_config"]),
+            sunset_state: sunset_pda(),
+            system_program: anchor_lang::system_program::ID,
+        },
+        pusd_spl::instruction::SetBridgeConfig { local_chain_id: 1, guardians: vec![new_key()], threshold: 1 },
+    )
+}
+
+fn initialize_yield_mint(owner: Pubkey, mint: Pubkey, pusd_mint: Pubkey) -> Instruction {
+    let bounds = RateBounds { min_rate: 0, max_rate: 1_000, max_rate_change: 50, min_update_interval: 86_400 };
+    ix(
+        pusd_spl::accounts::InitializeYieldMint {
+            owner_role: role_pda(&owner),
+            owner,
+            mint,
+            pusd_mint,
+            rate_config: pda(&[b"rate_config", mint.as_ref()]),
+            rate_authority: pda(&[b"rate_authority"]),
+            mint_authority: pda(&[b"yield_mint_authority"]),
+            reserve: pda(&[b"yield_reserve", mint.as_ref()]),
+            sunset_state: sunset_pda(),
+            token_program: spl_token_2022::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        pusd_spl::instruction::InitializeYieldMint { initial_rate: 500, bounds },
+    )
+}
+
+fn tombstone(pusd: &Pusd) -> SunsetState {
+    pusd.anchor_account(&sunset_pda()).unwrap()
+}
+
+/// A holder of `amount` PUSD minted by the operator
+fn holder(pusd: &mut Pusd, amount: u64) -> (Pubkey, Pubkey) {
+    let (holder, mint, operator) = (new_key(), pusd.mint, pusd.operator);
+    let holder_token = pusd.create_ata(&holder, &mint);
+    pusd.send(pusd.mint_by_operator_ix(operator, holder_token, amount)).unwrap();
+    (holder, holder_token)
+}
+
+#[test]
+fn execution_waits_out_the_timelock() {
+    let mut pusd = Pusd::new();
+    let (owner, mint) = (pusd.owner, pusd.mint);
+    pusd.send(pusd.schedule_sunset_ix(owner)).unwrap();
+    let scheduled_at = pusd.svm.clock().unix_timestamp;
+    assert_eq!(tombstone(&pusd).eta, scheduled_at + SUNSET_TIMELOCK);
+    pusd.treasury_token();
+
+    pusd.svm.warp(SUNSET_TIMELOCK - 1, 1);
+    let result = pusd.send(pusd.execute_sunset_ix(owner, mint));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::SunsetNotReady));
+
+    pusd.svm.warp(1, 1);
+    pusd.send(pusd.execute_sunset_ix(owner, mint)).unwrap();
+
+    assert_eq!(pusd.mint_state(&mint).mint_authority, COption::None);
+    assert!(pusd.svm.account(&program_state_pda()).is_none());
+    assert!(pusd.role(&owner).is_none());
+    let tombstone = tombstone(&pusd);
+    assert!(tombstone.executed);
+    assert_eq!((tombstone.owner, tombstone.mint), (owner, mint));
+    assert_eq!(tombstone.executed_at, pusd.svm.clock().unix_timestamp);
+}
+
+#[test]
+fn sunset_is_owner_only() {
+    let mut pusd = Pusd::new();
+    let (owner, operator, mint) = (pusd.owner, pusd.operator, pusd.mint);
+
+    let result = pusd.send(pusd.schedule_sunset_ix(operator));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::Unauthorized));
+
+    pusd.send(pusd.schedule_sunset_ix(owner)).unwrap();
+    pusd.svm.warp(SUNSET_TIMELOCK, 1);
+    pusd.treasury_token();
+    let result = pusd.send(pusd.execute_sunset_ix(operator, mint));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::Unauthorized));
+    let result = pusd.send(pusd.cancel_sunset_ix(operator));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::Unauthorized));
+}
+
+#[test]
+fn cancel_only_before_execution() {
+    let mut pusd = Pusd::new();
+    let owner = pusd.owner;
+    pusd.send(pusd.schedule_sunset_ix(owner)).unwrap();
+    pusd.send(pusd.cancel_sunset_ix(owner)).unwrap();
+    assert!(pusd.svm.account(&sunset_pda()).is_none());
+
+    // A cancelled sunset can be scheduled again with a fresh timelock
+    pusd.svm.warp(SUNSET_TIMELOCK, 1);
+    pusd.send(pusd.schedule_sunset_ix(owner)).unwrap();
+    assert_eq!(tombstone(&pusd).eta, pusd.svm.clock().unix_timestamp + SUNSET_TIMELOCK);
+
+    let second_owner = new_key();
+    pusd.set_role(second_owner, Role::Owner);
+    pusd.svm.warp(SUNSET_TIMELOCK, 1);
+    pusd.treasury_token();
+    pusd.send(pusd.execute_sunset_ix(owner, pusd.mint)).unwrap();
+
+    let result = pusd.send(pusd.cancel_sunset_ix(second_owner));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::ProgramSunset));
+    assert!(tombstone(&pusd).executed);
+    // The tombstone cannot be replaced by a new schedule either
+    assert!(pusd.send(pusd.schedule_sunset_ix(second_owner)).is_err());
+}
+
+#[test]
+fn execution_is_pinned_to_the_pusd_mint() {
+    let mut pusd = Pusd::new();
+    let owner = pusd.owner;
+    pusd.send(pusd.schedule_sunset_ix(owner)).unwrap();
+    pusd.svm.warp(SUNSET_TIMELOCK, 1);
+
+    let (other, authority) = (new_key(), pusd.upgrade_authority);
+    pusd.create_mint(other, authority, DECIMALS);
+    pusd.create_ata(&treasury_pda(), &other);
+    let result = pusd.send(pusd.execute_sunset_ix(owner, other));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::InvalidMint));
+    assert!(!tombstone(&pusd).executed);
+}
+
+#[test]
+fn treasury_must_be_emptied_first() {
+    let mut pusd = Pusd::new();
+    let (owner, operator, mint) = (pusd.owner, pusd.operator, pusd.mint);
+    let treasury_token = pusd.treasury_token();
+    pusd.send(pusd.mint_by_operator_ix(operator, treasury_token, 1_000)).unwrap();
+    pusd.send(pusd.schedule_sunset_ix(owner)).unwrap();
+    pusd.svm.warp(SUNSET_TIMELOCK, 1);
+
+    let result = pusd.send(pusd.execute_sunset_ix(owner, mint));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::TreasuryNotEmpty));
+    assert_eq!(pusd.mint_state(&mint).mint_authority, COption::Some(mint_authority_pda()));
+}
+
+#[test]
+fn initialize_is_refused_after_sunset() {
+    let mut pusd = Pusd::new();
+    pusd.sunset();
+
+    let (payer, owner, operator, guardian) =
+        (pusd.upgrade_authority, new_key(), new_key(), new_key());
+    let result = pusd.send(ix(
+        pusd_spl::accounts::Initialize {
+            program_state: program_state_pda(),
+            owner_role: role_pda(&owner),
+            operator_role: role_pda(&operator),
+            guardian_role: role_pda(&guardian),
+            sunset_state: sunset_pda(),
+            program_data: program_data_address(),
+            payer,
+            system_program: anchor_lang::system_program::ID,
+        },
+        pusd_spl::instruction::Initialize {
+            owner_address: owner,
+            operator_address: operator,
+            guardian_address: guardian,
+            kyc_required: false,
+        },
+    ));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::ProgramSunset));
+    assert!(pusd.svm.account(&program_state_pda()).is_none());
+}
+
+#[test]
+fn a_pending_sunset_blocks_nothing() {
+    let mut pusd = Pusd::new();
+    let owner = pusd.owner;
+    pusd.send(pusd.schedule_sunset_ix(owner)).unwrap();
+
+    pusd.send(add_role(owner, new_key(), Role::Operator)).unwrap();
+    pusd.send(set_bridge_config(owner)).unwrap();
+}
+
+#[test]
+fn other_instructions_are_rejected_after_sunset() {
+    let mut pusd = Pusd::new();
+    let collateral_mint = pusd.add_collateral(DECIMALS, PRICE_ONE, 1_000_000);
+    let (user, user_collateral, _) = pusd.psm_user(&collateral_mint, 1_000);
+    let second_owner = new_key();
+    pusd.set_role(second_owner, Role::Owner);
+    pusd.sunset();
+
+    // Instructions checking the tombstone
+    let result = pusd.send(add_role(second_owner, new_key(), Role::Operator));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::ProgramSunset));
+    let result = pusd.send(set_bridge_config(second_owner));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::ProgramSunset));
+    let result = pusd.send(initialize_yield_mint(second_owner, new_key(), pusd.mint));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::ProgramSunset));
+    pusd.owner = second_owner;
+    let (other_collateral, authority) = (new_key(), pusd.upgrade_authority);
+    pusd.create_mint(other_collateral, authority, DECIMALS);
+    let result = pusd.send(pusd.add_collateral_ix(other_collateral, PRICE_ONE, 1_000_000));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::ProgramSunset));
+
+    // Instructions reading ProgramState fail on the closed account
+    let (operator, recipient) = (pusd.operator, pusd.treasury_token());
+    let result = pusd.send(pusd.mint_by_operator_ix(operator, recipient, 1_000));
+    assert_eq!(result.unwrap_err(), anchor_error(ErrorCode::AccountNotInitialized));
+    let swap_in = pusd.swap_in_ix(user, collateral_mint, user_collateral, recipient, 1_000);
+    assert_eq!(pusd.send(swap_in).unwrap_err(), anchor_error(ErrorCode::AccountNotInitialized));
+}
+
+#[test]
+fn holders_redeem_the_retired_mint_after_sunset() {
+    let mut pusd = Pusd::new();
+    let (holder, holder_token) = holder(&mut pusd, 1_000);
+    let mint = pusd.mint;
+
+    // Redemption opens only once the sunset has executed
+    let result = pusd.send(pusd.redeem_after_sunset_ix(holder, mint, holder_token, 400));
+    assert_eq!(result.unwrap_err(), anchor_error(ErrorCode::AccountNotInitialized));
+    let owner = pusd.owner;
+    pusd.send(pusd.schedule_sunset_ix(owner)).unwrap();
+    let result = pusd.send(pusd.redeem_after_sunset_ix(holder, mint, holder_token, 400));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::SunsetNotExecuted));
+    pusd.send(pusd.cancel_sunset_ix(owner)).unwrap();
+    pusd.sunset();
+
+    pusd.send(pusd.redeem_after_sunset_ix(holder, mint, holder_token, 400)).unwrap();
+    assert_eq!(pusd.balance(&holder_token), 600);
+    assert_eq!(pusd.supply(), 600);
+
+    let (other, authority) = (new_key(), pusd.upgrade_authority);
+    pusd.create_mint(other, authority, DECIMALS);
+    let other_token = pusd.create_ata(&holder, &other);
+    pusd.mint_tokens(&other, &other_token, 1_000);
+    let result = pusd.send(pusd.redeem_after_sunset_ix(holder, other, other_token, 400));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::InvalidMint));
+}
+
+#[test]
+fn psm_collateral_is_shared_pro_rata_after_sunset() {
+    let mut pusd = Pusd::new();
+    let collateral_mint = pusd.add_collateral(DECIMALS, PRICE_ONE, 1_000_000);
+    let (user, user_collateral, user_pusd) = pusd.psm_user(&collateral_mint, 1_000);
+    let swap_in = pusd.swap_in_ix(user, collateral_mint, user_collateral, user_pusd, 1_000);
+    pusd.send(swap_in).unwrap();
+    assert_eq!(pusd.balance(&user_pusd), 1_000);
+    pusd.sunset();
+
+    // Half of the vault is lost, so each PUSD now claims half a unit of collateral
+    let vault = psm_vault_pda(&collateral_mint);
+    let mut vault_account = pusd.svm.account(&vault).unwrap();
+    let mut token = pusd.token_account(&vault);
+    token.amount = 500;
+    spl_token_2022::state::Account::pack(token, &mut vault_account.data[..spl_token_2022::state::Account::LEN]).unwrap();
+    pusd.svm.set_account(vault, vault_account);
+
+    pusd.send(pusd.swap_out_after_sunset_ix(user, collateral_mint, user_collateral, user_pusd, 400)).unwrap();
+    assert_eq!(pusd.balance(&user_collateral), 200);
+    assert_eq!(pusd.balance(&user_pusd), 600);
+    let config: CollateralConfig = pusd.anchor_account(&collateral_config_pda(&collateral_mint)).unwrap();
+    assert_eq!(config.debt, 600);
+
+    // Exits are capped by the PUSD outstanding against the vault
+    let result = pusd.send(pusd.swap_out_after_sunset_ix(user, collateral_mint, user_collateral, user_pusd, 601));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::InsufficientPsmLiquidity));
+    pusd.send(pusd.swap_out_after_sunset_ix(user, collateral_mint, user_collateral, user_pusd, 600)).unwrap();
+    assert_eq!(pusd.balance(&user_collateral), 500);
+    assert_eq!(pusd.balance(&vault), 0);
+}
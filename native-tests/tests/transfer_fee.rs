@@ -0,0 +1,228 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::error::TokenError;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::{instruction as fee_instruction, TransferFeeConfig};
+use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+use common::*;
+use pusd_spl::{PusdError, Role};
+
+const FEE_BPS: u16 = 100;
+const MAX_FEE: u64 = 1_000;
+
+fn create_fee_mint(pusd: &mut Pusd, authority: Pubkey) -> Pubkey {
+    let mint = new_key();
+    pusd.create_fee_mint(mint, authority, FEE_BPS, MAX_FEE);
+    mint
+}
+
+fn fee_config(pusd: &Pusd, mint: &Pubkey) -> TransferFeeConfig {
+    let data = &pusd.svm.account(mint).unwrap().data;
+    *StateWithExtensions::<spl_token_2022::state::Mint>::unpack(data).unwrap().get_extension::<TransferFeeConfig>().unwrap()
+}
+
+fn authorities_to_pda(signer: Pubkey, current_authority: Pubkey, mint: Pubkey) -> Instruction {
+    ix(
+        pusd_spl::accounts::TransferFeeAuthoritiesToPda {
+            owner_role: role_pda(&signer),
+            owner: signer,
+            current_authority,
+            mint,
+            mint_authority: mint_authority_pda(),
+            sunset_state: sunset_pda(),
+            token_program: spl_token_2022::ID,
+        },
+        pusd_spl::instruction::TransferFeeAuthoritiesToPda {},
+    )
+}
+
+fn manage(signer: Pubkey, mint: Pubkey) -> pusd_spl::accounts::ManageTransferFee {
+    pusd_spl::accounts::ManageTransferFee {
+        owner_role: role_pda(&signer),
+        owner: signer,
+        mint,
+        mint_authority: mint_authority_pda(),
+        sunset_state: sunset_pda(),
+        token_program: spl_token_2022::ID,
+    }
+}
+
+fn set_transfer_fee(signer: Pubkey, mint: Pubkey, transfer_fee_basis_points: u16, maximum_fee: u64) -> Instruction {
+    ix(manage(signer, mint), pusd_spl::instruction::SetTransferFee { transfer_fee_basis_points, maximum_fee })
+}
+
+fn harvest(signer: Pubkey, mint: Pubkey, sources: &[Pubkey]) -> Instruction {
+    with_remaining(ix(manage(signer, mint), pusd_spl::instruction::HarvestWithheldToMint {}), sources)
+}
+
+fn withdraw_to_treasury(signer: Pubkey, mint: Pubkey, treasury_token: Pubkey) -> Instruction {
+    ix(
+        pusd_spl::accounts::WithdrawWithheldToTreasury {
+            owner_role: role_pda(&signer),
+            owner: signer,
+            mint,
+            mint_authority: mint_authority_pda(),
+            treasury: treasury_pda(),
+            treasury_token,
+            sunset_state: sunset_pda(),
+            token_program: spl_token_2022::ID,
+        },
+        pusd_spl::instruction::WithdrawWithheldToTreasury {},
+    )
+}
+
+/// Fee mint under PDA control plus a recipient account holding `fee` withheld from one transfer
+fn fee_mint_with_withheld(pusd: &mut Pusd, amount: u64) -> (Pubkey, Pubkey, u64) {
+    let issuer = pusd.upgrade_authority;
+    let mint = create_fee_mint(pusd, issuer);
+    let owner = pusd.owner;
+    pusd.send(authorities_to_pda(owner, issuer, mint)).unwrap();
+
+    let (sender, recipient) = (new_key(), new_key());
+    let sender_token = pusd.create_ata(&sender, &mint);
+    let recipient_token = pusd.create_ata(&recipient, &mint);
+    let mint_to =
+        spl_token_2022::instruction::mint_to(&spl_token_2022::ID, &mint, &sender_token, &issuer, &[], amount).unwrap();
+    let fee = (amount * FEE_BPS as u64 / 10_000).min(MAX_FEE);
+    let transfer = fee_instruction::transfer_checked_with_fee(
+        &spl_token_2022::ID,
+        &sender_token,
+        &mint,
+        &recipient_token,
+        &sender,
+        &[],
+        amount,
+        DECIMALS,
+        fee,
+    )
+    .unwrap();
+    pusd.svm.send(&[mint_to, transfer]).unwrap();
+    assert_eq!(pusd.balance(&recipient_token), amount - fee);
+    (mint, recipient_token, fee)
+}
+
+#[test]
+fn authorities_move_to_the_pda() {
+    let mut pusd = Pusd::new();
+    let authority = pusd.upgrade_authority;
+    let mint = create_fee_mint(&mut pusd, authority);
+
+    let owner = pusd.owner;
+    pusd.send(authorities_to_pda(owner, authority, mint)).unwrap();
+
+    let config = fee_config(&pusd, &mint);
+    assert_eq!(Option::<Pubkey>::from(config.transfer_fee_config_authority), Some(mint_authority_pda()));
+    assert_eq!(Option::<Pubkey>::from(config.withdraw_withheld_authority), Some(mint_authority_pda()));
+    // The mint authority itself is untouched
+    assert_eq!(pusd.mint_state(&mint).mint_authority, COption::Some(authority));
+}
+
+#[test]
+fn authority_handover_requires_the_owner_and_the_current_authority() {
+    let mut pusd = Pusd::new();
+    let authority = pusd.upgrade_authority;
+    let mint = create_fee_mint(&mut pusd, authority);
+
+    let operator = pusd.operator;
+    let result = pusd.send(authorities_to_pda(operator, authority, mint));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::Unauthorized));
+
+    let owner = pusd.owner;
+    let result = pusd.send(authorities_to_pda(owner, new_key(), mint));
+    assert_eq!(result.unwrap_err(), TokenError::OwnerMismatch.into());
+}
+
+#[test]
+fn set_transfer_fee_schedules_the_new_fee() {
+    let mut pusd = Pusd::new();
+    let authority = pusd.upgrade_authority;
+    let mint = create_fee_mint(&mut pusd, authority);
+    let owner = pusd.owner;
+    pusd.send(authorities_to_pda(owner, authority, mint)).unwrap();
+
+    pusd.send(set_transfer_fee(owner, mint, 250, 5_000)).unwrap();
+
+    let config = fee_config(&pusd, &mint);
+    assert_eq!(u16::from(config.newer_transfer_fee.transfer_fee_basis_points), 250);
+    assert_eq!(u64::from(config.newer_transfer_fee.maximum_fee), 5_000);
+    assert_eq!(u64::from(config.newer_transfer_fee.epoch), pusd.svm.clock().epoch + 2);
+    assert_eq!(u16::from(config.older_transfer_fee.transfer_fee_basis_points), FEE_BPS);
+}
+
+#[test]
+fn set_transfer_fee_is_owner_only_and_bounded() {
+    let mut pusd = Pusd::new();
+    let authority = pusd.upgrade_authority;
+    let mint = create_fee_mint(&mut pusd, authority);
+    let owner = pusd.owner;
+
+    // The PDA cannot sign for a mint whose fee authority it does not hold
+    let result = pusd.send(set_transfer_fee(owner, mint, 250, 5_000));
+    assert_eq!(result.unwrap_err(), TokenError::OwnerMismatch.into());
+
+    pusd.send(authorities_to_pda(owner, authority, mint)).unwrap();
+
+    let operator = pusd.operator;
+    let result = pusd.send(set_transfer_fee(operator, mint, 250, 5_000));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::Unauthorized));
+
+    let result = pusd.send(set_transfer_fee(owner, mint, pusd_spl::MAX_FEE_BPS + 1, 5_000));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::FeeTooHigh));
+
+    pusd.send(set_transfer_fee(owner, mint, pusd_spl::MAX_FEE_BPS, 5_000)).unwrap();
+}
+
+#[test]
+fn withheld_fees_are_harvested_and_withdrawn_to_the_treasury() {
+    let mut pusd = Pusd::new();
+    let (mint, recipient_token, fee) = fee_mint_with_withheld(&mut pusd, 50_000);
+    assert_eq!(fee, 500);
+
+    let owner = pusd.owner;
+    pusd.send(harvest(owner, mint, &[recipient_token])).unwrap();
+    assert_eq!(u64::from(fee_config(&pusd, &mint).withheld_amount), fee);
+
+    let treasury_token = pusd.create_ata(&treasury_pda(), &mint);
+    pusd.send(withdraw_to_treasury(owner, mint, treasury_token)).unwrap();
+
+    assert_eq!(pusd.balance(&treasury_token), fee);
+    assert_eq!(u64::from(fee_config(&pusd, &mint).withheld_amount), 0);
+}
+
+#[test]
+fn harvest_is_owner_only_and_needs_accounts() {
+    let mut pusd = Pusd::new();
+    let (mint, recipient_token, _) = fee_mint_with_withheld(&mut pusd, 50_000);
+
+    let operator = pusd.operator;
+    let result = pusd.send(harvest(operator, mint, &[recipient_token]));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::Unauthorized));
+
+    let owner = pusd.owner;
+    let result = pusd.send(harvest(owner, mint, &[]));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::EmptyBatch));
+}
+
+#[test]
+fn withdraw_is_owner_only_and_pays_only_the_treasury() {
+    let mut pusd = Pusd::new();
+    let (mint, recipient_token, _) = fee_mint_with_withheld(&mut pusd, 50_000);
+    let owner = pusd.owner;
+    pusd.send(harvest(owner, mint, &[recipient_token])).unwrap();
+    let treasury_token = pusd.create_ata(&treasury_pda(), &mint);
+
+    let operator = pusd.operator;
+    let result = pusd.send(withdraw_to_treasury(operator, mint, treasury_token));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::Unauthorized));
+
+    // Any account other than the treasury's associated token account is refused
+    let result = pusd.send(withdraw_to_treasury(owner, mint, recipient_token));
+    assert_eq!(result.unwrap_err(), anchor_error(anchor_lang::error::ErrorCode::ConstraintTokenOwner));
+
+    pusd.set_role(operator, Role::Owner);
+    pusd.send(withdraw_to_treasury(operator, mint, treasury_token)).unwrap();
+    assert!(pusd.balance(&treasury_token) > 0);
+}
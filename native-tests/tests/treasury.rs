@@ -0,0 +1,116 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_spl::token_2022::spl_token_2022;
+use common::*;
+use pusd_spl::PusdError;
+
+fn redeem(pusd: &Pusd, holder: Pubkey, mint: Pubkey, holder_token: Pubkey, amount: u64) -> Instruction {
+    let treasury_token = pusd.ata(&treasury_pda(), &mint);
+    ix(
+        pusd_spl::accounts::Redeem {
+            holder,
+            program_state: program_state_pda(),
+            mint,
+            holder_token,
+            treasury: treasury_pda(),
+            treasury_token,
+            token_program: spl_token_2022::ID,
+        },
+        pusd_spl::instruction::Redeem { amount },
+    )
+}
+
+fn withdraw_fees(pusd: &mut Pusd, signer: Pubkey, destination: Pubkey, amount: u64) -> Instruction {
+    let treasury_token = pusd.treasury_token();
+    ix(
+        pusd_spl::accounts::WithdrawFees {
+            owner_role: role_pda(&signer),
+            owner: signer,
+            mint: pusd.mint,
+            treasury: treasury_pda(),
+            treasury_token,
+            destination,
+            sunset_state: sunset_pda(),
+            token_program: spl_token_2022::ID,
+        },
+        pusd_spl::instruction::WithdrawFees { amount },
+    )
+}
+
+fn funded_holder(pusd: &mut Pusd, amount: u64) -> (Pubkey, Pubkey) {
+    let holder = new_key();
+    pusd.svm.airdrop(&holder, 1_000_000_000);
+    let holder_token = pusd.create_ata(&holder, &pusd.mint.clone());
+    let operator = pusd.operator;
+    pusd.send(pusd.mint_by_operator_ix(operator, holder_token, amount)).unwrap();
+    (holder, holder_token)
+}
+
+#[test]
+fn redeem_burns_and_moves_the_fee_to_the_treasury() {
+    let mut pusd = Pusd::new();
+    pusd.update_state(|state| state.redemption_fee_bps = 25);
+    let (holder, holder_token) = funded_holder(&mut pusd, 10_000);
+    let treasury_token = pusd.treasury_token();
+    let mint = pusd.mint;
+
+    let instruction = redeem(&pusd, holder, mint, holder_token, 8_000);
+    pusd.send(instruction).unwrap();
+
+    assert_eq!(pusd.balance(&holder_token), 2_000);
+    assert_eq!(pusd.balance(&treasury_token), 20);
+    assert_eq!(pusd.supply(), 10_000 - 7_980);
+}
+
+#[test]
+fn redeem_is_pinned_to_the_pusd_mint() {
+    let mut pusd = Pusd::new();
+    let (other, authority) = (new_key(), pusd.upgrade_authority);
+    pusd.create_mint(other, authority, DECIMALS);
+    let holder = new_key();
+    let holder_token = pusd.create_ata(&holder, &other);
+    pusd.mint_tokens(&other, &holder_token, 1_000);
+    pusd.create_ata(&treasury_pda(), &other);
+
+    let instruction = redeem(&pusd, holder, other, holder_token, 1_000);
+    assert_eq!(pusd.send(instruction).unwrap_err(), pusd_error(PusdError::InvalidMint));
+    assert_eq!(pusd.balance(&holder_token), 1_000);
+}
+
+#[test]
+fn redeem_rejects_amounts_that_burn_nothing() {
+    let mut pusd = Pusd::new();
+    pusd.update_state(|state| state.redemption_fee_bps = 100);
+    let (holder, holder_token) = funded_holder(&mut pusd, 10);
+    pusd.treasury_token();
+    let mint = pusd.mint;
+
+    for amount in [0, 1] {
+        let instruction = redeem(&pusd, holder, mint, holder_token, amount);
+        assert_eq!(pusd.send(instruction).unwrap_err(), pusd_error(PusdError::InvalidAmount));
+    }
+}
+
+#[test]
+fn only_the_owner_withdraws_fees() {
+    let mut pusd = Pusd::new();
+    pusd.update_state(|state| state.redemption_fee_bps = 100);
+    let (holder, holder_token) = funded_holder(&mut pusd, 10_000);
+    pusd.treasury_token();
+    let mint = pusd.mint;
+    let instruction = redeem(&pusd, holder, mint, holder_token, 10_000);
+    pusd.send(instruction).unwrap();
+
+    let (owner, operator) = (pusd.owner, pusd.operator);
+    let destination = pusd.create_ata(&owner, &mint);
+    let instruction = withdraw_fees(&mut pusd, operator, destination, 100);
+    assert_eq!(pusd.send(instruction).unwrap_err(), pusd_error(PusdError::Unauthorized));
+    let instruction = withdraw_fees(&mut pusd, owner, destination, 101);
+    assert_eq!(pusd.send(instruction).unwrap_err(), pusd_error(PusdError::InsufficientTreasuryBalance));
+
+    let instruction = withdraw_fees(&mut pusd, owner, destination, 100);
+    pusd.send(instruction).unwrap();
+    assert_eq!(pusd.balance(&destination), 100);
+}
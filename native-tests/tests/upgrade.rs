@@ -0,0 +1,103 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::solana_program::program_error::ProgramError;
+use common::*;
+use pusd_spl::upgrade::check_upgrade_authority;
+use pusd_spl::PusdError;
+use pusd_native_tests::{Account, Svm};
+
+/// A deployed but uninitialized program whose ProgramData account is `program_data`
+fn deployment(program_data: Account) -> Svm {
+    let mut svm = Svm::new();
+    svm.set_account(program_data_address(), program_data);
+    svm
+}
+
+fn initialize(svm: &mut Svm, payer: Pubkey) -> Result<(), ProgramError> {
+    svm.airdrop(&payer, 10_000_000_000);
+    let (owner, operator, guardian) = (new_key(), new_key(), new_key());
+    let instruction = ix(
+        pusd_spl::accounts::Initialize {
+            program_state: program_state_pda(),
+            owner_role: role_pda(&owner),
+            operator_role: role_pda(&operator),
+            guardian_role: role_pda(&guardian),
+            sunset_state: sunset_pda(),
+            program_data: program_data_address(),
+            payer,
+            system_program: anchor_lang::system_program::ID,
+        },
+        pusd_spl::instruction::Initialize {
+            owner_address: owner,
+            operator_address: operator,
+            guardian_address: guardian,
+            kyc_required: false,
+        },
+    );
+    svm.send(&[instruction])
+}
+
+fn loader_account(data: Vec<u8>) -> Account {
+    Account::new(1_000_000, data, bpf_loader_upgradeable::ID)
+}
+
+#[test]
+fn checks_upgrade_authority() {
+    let authority = new_key();
+    assert!(check_upgrade_authority(Some(authority), authority).is_ok());
+    assert_eq!(
+        check_upgrade_authority(Some(authority), new_key()).unwrap_err(),
+        PusdError::OnlyUpgradeAuthority.into()
+    );
+    assert_eq!(
+        check_upgrade_authority(None, new_key()).unwrap_err(),
+        PusdError::ProgramImmutable.into()
+    );
+}
+
+#[test]
+fn only_the_upgrade_authority_initializes() {
+    let authority = new_key();
+    let mut svm = deployment(loader_account(program_data(Some(authority))));
+
+    let result = initialize(&mut svm, new_key());
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::OnlyUpgradeAuthority));
+
+    initialize(&mut svm, authority).unwrap();
+    assert!(svm.account(&program_state_pda()).is_some());
+}
+
+#[test]
+fn immutable_program_cannot_be_initialized() {
+    let mut svm = deployment(loader_account(program_data(None)));
+
+    let result = initialize(&mut svm, new_key());
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::ProgramImmutable));
+}
+
+#[test]
+fn rejects_malformed_program_data() {
+    // Buffer variant instead of ProgramData
+    let authority = new_key();
+    let mut buffer = program_data(Some(authority));
+    buffer[..4].copy_from_slice(&1u32.to_le_bytes());
+    let mut svm = deployment(loader_account(buffer));
+    let result = initialize(&mut svm, authority);
+    assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
+
+    let truncated = program_data(Some(authority))[..20].to_vec();
+    let mut svm = deployment(loader_account(truncated));
+    let result = initialize(&mut svm, authority);
+    assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
+}
+
+#[test]
+fn rejects_program_data_not_owned_by_the_loader() {
+    let authority = new_key();
+    let mut svm = deployment(Account::new(1_000_000, program_data(Some(authority)), pusd_spl::ID));
+
+    let result = initialize(&mut svm, authority);
+    assert_eq!(result.unwrap_err(), anchor_error(anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram));
+}
@@ -0,0 +1,399 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_spl::token_2022::spl_token_2022;
+use common::*;
+use pusd_spl::math::{shares_to_assets, Rounding};
+use pusd_spl::{assets_for_unstake, shares_for_stake, OperatorMintWindow, PusdError, StakingVault};
+
+// Deterministic xorshift generator so fuzz failures are reproducible
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+
+    // Amounts spread across magnitudes from dust to ~10^15
+    fn amount(&mut self) -> u64 {
+        let exp = self.below(16) as u32;
+        1 + self.below(10u64.pow(exp))
+    }
+}
+
+// Vault totals driven through the same share math as the stake and unstake instructions
+#[derive(Default)]
+struct Vault {
+    total_assets: u64,
+    total_shares: u64,
+}
+
+impl Vault {
+    fn stake(&mut self, assets: u64) -> Option<u64> {
+        let (shares, total_assets) = shares_for_stake(assets, self.total_assets, self.total_shares).ok()?;
+        self.total_shares = self.total_shares.checked_add(shares)?;
+        self.total_assets = total_assets;
+        Some(shares)
+    }
+
+    fn unstake(&mut self, shares: u64) -> Option<u64> {
+        let (assets, total_assets) = assets_for_unstake(shares, self.total_assets, self.total_shares).ok()?;
+        self.total_shares -= shares;
+        self.total_assets = total_assets;
+        Some(assets)
+    }
+
+    // Assets per share scaled by 10^12 for monotonicity checks
+    fn price(&self) -> u128 {
+        (self.total_assets as u128 + 1) * 1_000_000_000_000 / (self.total_shares as u128 + 1_000_000)
+    }
+}
+
+#[test]
+fn first_stake_is_priced_at_virtual_rate() {
+    let mut vault = Vault::default();
+    assert_eq!(vault.stake(1), Some(1_000_000));
+    assert_eq!(vault.unstake(1_000_000), Some(1));
+}
+
+#[test]
+fn dust_is_rejected_instead_of_rounding_to_zero() {
+    let mut vault = Vault { total_assets: 1_000_000_000, total_shares: 1 };
+    assert_eq!(vault.stake(1), None);
+    assert_eq!(vault.unstake(0), None);
+    assert_eq!((vault.total_assets, vault.total_shares), (1_000_000_000, 1));
+
+    let mut vault = Vault { total_assets: 0, total_shares: 1_000_000_000 };
+    assert_eq!(vault.unstake(1), None);
+    assert_eq!((vault.total_assets, vault.total_shares), (0, 1_000_000_000));
+}
+
+#[test]
+fn stake_then_unstake_never_returns_more() {
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+    for _ in 0..20_000 {
+        let mut vault = Vault {
+            total_assets: rng.amount(),
+            total_shares: rng.amount(),
+        };
+        let assets = rng.amount();
+        if let Some(shares) = vault.stake(assets) {
+            assert!(vault.unstake(shares).unwrap_or(0) <= assets);
+        }
+    }
+}
+
+#[test]
+fn donation_attack_is_unprofitable() {
+    let mut rng = Rng(0xdead_beef_cafe_f00d);
+    for _ in 0..20_000 {
+        let mut vault = Vault::default();
+        let seed = 1 + rng.below(1_000);
+        let attacker_shares = vault.stake(seed).unwrap();
+
+        // Worst case: the donation is counted as vault assets
+        let donation = rng.amount();
+        vault.total_assets += donation;
+
+        let victim_assets = rng.amount();
+        let victim_shares = vault.stake(victim_assets);
+
+        let attacker_out = vault.unstake(attacker_shares).unwrap_or(0);
+        assert!(attacker_out <= seed + donation);
+
+        // The victim either gets rejected (keeping their funds) or loses
+        // at most a millionth of the attacker's outlay to rounding
+        if let Some(shares) = victim_shares {
+            let victim_out = vault.unstake(shares).unwrap_or(0);
+            let loss = victim_assets.saturating_sub(victim_out);
+            assert!(loss <= (seed + donation) / 1_000_000 + 1, "loss {loss}");
+        }
+    }
+}
+
+#[test]
+fn random_operations_keep_vault_solvent() {
+    let mut rng = Rng(0x0123_4567_89ab_cdef);
+    for _ in 0..200 {
+        let mut vault = Vault::default();
+        let mut holders = [0u64; 4];
+        for _ in 0..200 {
+            let holder = rng.below(holders.len() as u64) as usize;
+            let price_before = vault.price();
+            match rng.below(3) {
+                0 => {
+                    if let Some(shares) = vault.stake(rng.amount()) {
+                        holders[holder] += shares;
+                    }
+                    assert!(vault.price() >= price_before);
+                }
+                1 if holders[holder] > 0 => {
+                    let shares = 1 + rng.below(holders[holder]);
+                    if vault.unstake(shares).is_some() {
+                        holders[holder] -= shares;
+                    }
+                    assert!(vault.price() >= price_before);
+                }
+                _ => {
+                    // distribute_yield
+                    vault.total_assets += rng.amount();
+                    assert!(vault.price() >= price_before);
+                }
+            }
+
+            assert_eq!(holders.iter().sum::<u64>(), vault.total_shares);
+            let claims: u64 = holders
+                .iter()
+                .map(|&shares| {
+                    shares_to_assets(shares, vault.total_assets, vault.total_shares, Rounding::Down).unwrap()
+                })
+                .sum();
+            assert!(claims <= vault.total_assets);
+        }
+    }
+}
+
+fn staking_vault_pda(mint: &Pubkey) -> Pubkey {
+    pda(&[b"staking_vault", mint.as_ref()])
+}
+
+fn share_mint_pda(mint: &Pubkey) -> Pubkey {
+    pda(&[b"share_mint", mint.as_ref()])
+}
+
+fn vault_token_pda(mint: &Pubkey) -> Pubkey {
+    pda(&[b"staking_vault_token", mint.as_ref()])
+}
+
+fn initialize_staking_vault(pusd: &mut Pusd, mint: Pubkey) {
+    let owner = pusd.owner;
+    pusd.send(ix(
+        pusd_spl::accounts::InitializeStakingVault {
+            owner_role: role_pda(&owner),
+            owner,
+            staking_vault: staking_vault_pda(&mint),
+            pusd_mint: mint,
+            share_mint: share_mint_pda(&mint),
+            vault_token: vault_token_pda(&mint),
+            sunset_state: sunset_pda(),
+            token_program: spl_token_2022::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        pusd_spl::instruction::InitializeStakingVault {},
+    ))
+    .unwrap();
+}
+
+fn vault_user(user: Pubkey, mint: Pubkey, user_pusd: Pubkey, user_shares: Pubkey) -> pusd_spl::accounts::StakingVaultUser {
+    pusd_spl::accounts::StakingVaultUser {
+        user,
+        staking_vault: staking_vault_pda(&mint),
+        pusd_mint: mint,
+        share_mint: share_mint_pda(&mint),
+        vault_token: vault_token_pda(&mint),
+        user_pusd,
+        user_shares,
+        sunset_state: sunset_pda(),
+        token_program: spl_token_2022::ID,
+    }
+}
+
+fn distribute_yield(pusd: &Pusd, amount: u64) -> Instruction {
+    ix(
+        pusd_spl::accounts::DistributeYield {
+            operator_role: role_pda(&pusd.operator),
+            operator: pusd.operator,
+            mint_window: mint_window_pda(&pusd.operator),
+            staking_vault: staking_vault_pda(&pusd.mint),
+            vault_token: vault_token_pda(&pusd.mint),
+            mint: pusd.mint,
+            program_state: program_state_pda(),
+            reserve_attestation: reserve_attestation_pda(&pusd.mint),
+            mint_authority: mint_authority_pda(),
+            token_program: spl_token_2022::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        pusd_spl::instruction::DistributeYield { amount },
+    )
+}
+
+/// A staker holding `amount` of `mint` and an empty share account
+fn staker(pusd: &mut Pusd, mint: Pubkey, amount: u64) -> (Pubkey, Pubkey, Pubkey) {
+    let user = new_key();
+    let user_pusd = pusd.create_ata(&user, &mint);
+    pusd.mint_tokens(&mint, &user_pusd, amount);
+    let user_shares = pusd.create_ata(&user, &share_mint_pda(&mint));
+    (user, user_pusd, user_shares)
+}
+
+fn total_assets(pusd: &Pusd, mint: &Pubkey) -> u64 {
+    pusd.anchor_account::<StakingVault>(&staking_vault_pda(mint)).unwrap().total_assets
+}
+
+#[test]
+fn stake_and_unstake_round_trip() {
+    let mut pusd = Pusd::new();
+    let mint = new_key();
+    let authority = pusd.upgrade_authority;
+    pusd.create_mint(mint, authority, DECIMALS);
+    initialize_staking_vault(&mut pusd, mint);
+    let (user, user_pusd, user_shares) = staker(&mut pusd, mint, 10_000);
+
+    pusd.send(ix(vault_user(user, mint, user_pusd, user_shares), pusd_spl::instruction::Stake { assets: 10_000 }))
+        .unwrap();
+    assert_eq!(pusd.balance(&user_shares), 10_000_000_000);
+    assert_eq!(pusd.balance(&vault_token_pda(&mint)), 10_000);
+    assert_eq!(total_assets(&pusd, &mint), 10_000);
+
+    let unstake = pusd_spl::instruction::Unstake { shares: 10_000_000_000 };
+    pusd.send(ix(vault_user(user, mint, user_pusd, user_shares), unstake)).unwrap();
+    assert_eq!(pusd.balance(&user_shares), 0);
+    assert_eq!(pusd.balance(&user_pusd), 10_000);
+    assert_eq!(total_assets(&pusd, &mint), 0);
+}
+
+#[test]
+fn stake_credits_only_what_the_vault_receives() {
+    let mut pusd = Pusd::new();
+    let mint = new_key();
+    let authority = pusd.upgrade_authority;
+    // 1% transfer fee
+    pusd.create_fee_mint(mint, authority, 100, u64::MAX);
+    initialize_staking_vault(&mut pusd, mint);
+    let (user, user_pusd, user_shares) = staker(&mut pusd, mint, 10_000);
+
+    pusd.send(ix(vault_user(user, mint, user_pusd, user_shares), pusd_spl::instruction::Stake { assets: 10_000 }))
+        .unwrap();
+    assert_eq!(pusd.balance(&vault_token_pda(&mint)), 9_900);
+    assert_eq!(total_assets(&pusd, &mint), 9_900);
+    assert_eq!(pusd.balance(&user_shares), 9_900_000_000);
+
+    // Unstaking pays the fee again on the way out, but the vault stays fully backed
+    let unstake = pusd_spl::instruction::Unstake { shares: 9_900_000_000 };
+    pusd.send(ix(vault_user(user, mint, user_pusd, user_shares), unstake)).unwrap();
+    assert_eq!(pusd.balance(&user_pusd), 9_801);
+    assert_eq!(pusd.balance(&vault_token_pda(&mint)), 0);
+    assert_eq!(total_assets(&pusd, &mint), 0);
+}
+
+#[test]
+fn stake_and_unstake_reject_empty_amounts() {
+    let mut pusd = Pusd::new();
+    let mint = new_key();
+    let authority = pusd.upgrade_authority;
+    pusd.create_mint(mint, authority, DECIMALS);
+    initialize_staking_vault(&mut pusd, mint);
+    let (user, user_pusd, user_shares) = staker(&mut pusd, mint, 10_000);
+
+    let stake = ix(vault_user(user, mint, user_pusd, user_shares), pusd_spl::instruction::Stake { assets: 0 });
+    assert_eq!(pusd.send(stake).unwrap_err(), pusd_error(PusdError::InvalidAmount));
+    let unstake = ix(vault_user(user, mint, user_pusd, user_shares), pusd_spl::instruction::Unstake { shares: 0 });
+    assert_eq!(pusd.send(unstake).unwrap_err(), pusd_error(PusdError::InvalidAmount));
+}
+
+#[test]
+fn distributed_yield_accrues_to_stakers() {
+    let mut pusd = Pusd::new();
+    let mint = pusd.mint;
+    initialize_staking_vault(&mut pusd, mint);
+    let user = new_key();
+    let user_pusd = pusd.create_ata(&user, &mint);
+    let operator = pusd.operator;
+    pusd.send(pusd.mint_by_operator_ix(operator, user_pusd, 10_000)).unwrap();
+    let user_shares = pusd.create_ata(&user, &share_mint_pda(&mint));
+    pusd.send(ix(vault_user(user, mint, user_pusd, user_shares), pusd_spl::instruction::Stake { assets: 10_000 }))
+        .unwrap();
+
+    pusd.send(distribute_yield(&pusd, 1_000)).unwrap();
+    assert_eq!(total_assets(&pusd, &mint), 11_000);
+
+    let shares = pusd.balance(&user_shares);
+    let unstake = pusd_spl::instruction::Unstake { shares };
+    pusd.send(ix(vault_user(user, mint, user_pusd, user_shares), unstake)).unwrap();
+    // The virtual share keeps a sliver of the yield in the vault
+    let received = pusd.balance(&user_pusd);
+    assert!((10_999..=11_000).contains(&received), "{received}");
+}
+
+#[test]
+fn yield_is_capped_by_attested_reserves() {
+    let mut pusd = Pusd::new();
+    let mint = pusd.mint;
+    initialize_staking_vault(&mut pusd, mint);
+    pusd.attest(1_000);
+
+    pusd.send(distribute_yield(&pusd, 600)).unwrap();
+    assert_eq!(
+        pusd.send(distribute_yield(&pusd, 401)).unwrap_err(),
+        pusd_error(PusdError::InsufficientReserves)
+    );
+    assert_eq!(total_assets(&pusd, &mint), 600);
+    assert_eq!(pusd.supply(), 600);
+}
+
+#[test]
+fn yield_counts_towards_the_operator_mint_window() {
+    let mut pusd = Pusd::new();
+    let mint = pusd.mint;
+    initialize_staking_vault(&mut pusd, mint);
+    pusd.update_state(|state| state.large_mint_threshold = 10_000);
+    let (operator, recipient) = (pusd.operator, pusd.create_ata(&new_key(), &mint));
+
+    pusd.send(pusd.mint_by_operator_ix(operator, recipient, 6_000)).unwrap();
+    assert_eq!(
+        pusd.send(distribute_yield(&pusd, 4_001)).unwrap_err(),
+        pusd_error(PusdError::RequiresApproval)
+    );
+    pusd.send(distribute_yield(&pusd, 4_000)).unwrap();
+    assert_eq!(
+        pusd.send(pusd.mint_by_operator_ix(operator, recipient, 1)).unwrap_err(),
+        pusd_error(PusdError::RequiresApproval)
+    );
+
+    let window: OperatorMintWindow = pusd.anchor_account(&mint_window_pda(&operator)).unwrap();
+    assert_eq!(window.minted, 10_000);
+    assert_eq!(total_assets(&pusd, &mint), 4_000);
+}
+
+#[test]
+fn yield_is_refused_while_direct_minting_is_disabled() {
+    let mut pusd = Pusd::new();
+    let mint = pusd.mint;
+    initialize_staking_vault(&mut pusd, mint);
+    pusd.update_state(|state| state.direct_mint_disabled = true);
+
+    assert_eq!(
+        pusd.send(distribute_yield(&pusd, 1_000)).unwrap_err(),
+        pusd_error(PusdError::DirectMintDisabled)
+    );
+    assert_eq!(total_assets(&pusd, &mint), 0);
+}
+
+#[test]
+fn sunset_blocks_stake_but_not_unstake() {
+    let mut pusd = Pusd::new();
+    let mint = new_key();
+    let authority = pusd.upgrade_authority;
+    pusd.create_mint(mint, authority, DECIMALS);
+    initialize_staking_vault(&mut pusd, mint);
+    let (user, user_pusd, user_shares) = staker(&mut pusd, mint, 10_000);
+    pusd.send(ix(vault_user(user, mint, user_pusd, user_shares), pusd_spl::instruction::Stake { assets: 4_000 }))
+        .unwrap();
+    pusd.sunset();
+
+    let stake = pusd_spl::instruction::Stake { assets: 1_000 };
+    let result = pusd.send(ix(vault_user(user, mint, user_pusd, user_shares), stake));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::ProgramSunset));
+
+    let unstake = pusd_spl::instruction::Unstake { shares: 4_000_000_000 };
+    pusd.send(ix(vault_user(user, mint, user_pusd, user_shares), unstake)).unwrap();
+    assert_eq!(pusd.balance(&user_pusd), 10_000);
+}
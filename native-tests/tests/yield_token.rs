@@ -0,0 +1,311 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::interest_bearing_mint::InterestBearingConfig;
+use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+use common::*;
+use pusd_spl::{assets_for_yield_redemption, PusdError, RateBounds, RateConfig, Role};
+
+const BOUNDS: RateBounds = RateBounds { min_rate: 0, max_rate: 1_000, max_rate_change: 50, min_update_interval: 86_400 };
+
+fn rate_config_pda(mint: &Pubkey) -> Pubkey {
+    pda(&[b"rate_config", mint.as_ref()])
+}
+
+fn reserve_pda(mint: &Pubkey) -> Pubkey {
+    pda(&[b"yield_reserve", mint.as_ref()])
+}
+
+fn initialize_yield_mint(pusd: &Pusd, signer: Pubkey, mint: Pubkey, initial_rate: i16, bounds: RateBounds) -> Instruction {
+    ix(
+        pusd_spl::accounts::InitializeYieldMint {
+            owner_role: role_pda(&signer),
+            owner: signer,
+            mint,
+            pusd_mint: pusd.mint,
+            rate_config: rate_config_pda(&mint),
+            rate_authority: pda(&[b"rate_authority"]),
+            mint_authority: pda(&[b"yield_mint_authority"]),
+            reserve: reserve_pda(&mint),
+            sunset_state: sunset_pda(),
+            token_program: spl_token_2022::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        pusd_spl::instruction::InitializeYieldMint { initial_rate, bounds },
+    )
+}
+
+fn yield_token_user(pusd: &Pusd, user: Pubkey, mint: Pubkey) -> pusd_spl::accounts::YieldTokenUser {
+    pusd_spl::accounts::YieldTokenUser {
+        user,
+        rate_config: rate_config_pda(&mint),
+        mint,
+        pusd_mint: pusd.mint,
+        reserve: reserve_pda(&mint),
+        user_pusd: pusd.ata(&user, &pusd.mint),
+        user_yield: pusd.ata(&user, &mint),
+        mint_authority: pda(&[b"yield_mint_authority"]),
+        sunset_state: sunset_pda(),
+        token_program: spl_token_2022::ID,
+    }
+}
+
+fn deposit(pusd: &Pusd, user: Pubkey, mint: Pubkey, assets: u64) -> Instruction {
+    ix(yield_token_user(pusd, user, mint), pusd_spl::instruction::DepositYieldToken { assets })
+}
+
+fn redeem(pusd: &Pusd, user: Pubkey, mint: Pubkey, amount: u64) -> Instruction {
+    ix(yield_token_user(pusd, user, mint), pusd_spl::instruction::RedeemYieldToken { amount })
+}
+
+fn set_rate_bounds(signer: Pubkey, mint: Pubkey, bounds: RateBounds) -> Instruction {
+    ix(
+        pusd_spl::accounts::SetRateBounds {
+            owner_role: role_pda(&signer),
+            owner: signer,
+            rate_config: rate_config_pda(&mint),
+            sunset_state: sunset_pda(),
+        },
+        pusd_spl::instruction::SetRateBounds { bounds },
+    )
+}
+
+fn update_rate(signer: Pubkey, mint: Pubkey, rate: i16) -> Instruction {
+    ix(
+        pusd_spl::accounts::UpdateRate {
+            rate_manager_role: role_pda(&signer),
+            rate_manager: signer,
+            rate_config: rate_config_pda(&mint),
+            mint,
+            rate_authority: pda(&[b"rate_authority"]),
+            sunset_state: sunset_pda(),
+            token_program: spl_token_2022::ID,
+        },
+        pusd_spl::instruction::UpdateRate { rate },
+    )
+}
+
+fn rate_config(pusd: &Pusd, mint: &Pubkey) -> RateConfig {
+    pusd.anchor_account(&rate_config_pda(mint)).unwrap()
+}
+
+fn mint_rate(pusd: &Pusd, mint: &Pubkey) -> i16 {
+    let data = &pusd.svm.account(mint).unwrap().data;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(data).unwrap();
+    i16::from(state.get_extension::<InterestBearingConfig>().unwrap().current_rate)
+}
+
+fn interest_bearing_config(pusd: &Pusd, mint: &Pubkey) -> InterestBearingConfig {
+    let data = &pusd.svm.account(mint).unwrap().data;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(data).unwrap();
+    *state.get_extension::<InterestBearingConfig>().unwrap()
+}
+
+/// A holder of `amount` PUSD with an empty account of the yield mint
+fn holder(pusd: &mut Pusd, mint: Pubkey, amount: u64) -> Pubkey {
+    let (user, pusd_mint, operator) = (new_key(), pusd.mint, pusd.operator);
+    let user_pusd = pusd.create_ata(&user, &pusd_mint);
+    pusd.send(pusd.mint_by_operator_ix(operator, user_pusd, amount)).unwrap();
+    pusd.create_ata(&user, &mint);
+    user
+}
+
+/// A yield mint at 500 bps under BOUNDS, and a RateManager allowed to move it
+fn yield_mint(pusd: &mut Pusd) -> (Pubkey, Pubkey) {
+    let mint = new_key();
+    let owner = pusd.owner;
+    pusd.send(initialize_yield_mint(pusd, owner, mint, 500, BOUNDS)).unwrap();
+    let rate_manager = new_key();
+    pusd.set_role(rate_manager, Role::RateManager);
+    (mint, rate_manager)
+}
+
+#[test]
+fn yield_mint_has_its_own_mint_authority() {
+    let mut pusd = Pusd::new();
+    let (mint, _) = yield_mint(&mut pusd);
+
+    let yield_authority = pda(&[b"yield_mint_authority"]);
+    assert_ne!(yield_authority, mint_authority_pda());
+    assert_eq!(pusd.mint_state(&mint).mint_authority, COption::Some(yield_authority));
+    assert_eq!(mint_rate(&pusd, &mint), 500);
+
+    let config = rate_config(&pusd, &mint);
+    assert_eq!((config.mint, config.bounds, config.current_rate), (mint, BOUNDS, 500));
+    assert_eq!((config.pusd_mint, config.reserve), (pusd.mint, reserve_pda(&mint)));
+    assert_eq!(pusd.mint_state(&mint).decimals, DECIMALS);
+    assert_eq!(pusd.token_account(&reserve_pda(&mint)).owner, yield_authority);
+}
+
+#[test]
+fn initialize_yield_mint_checks_the_initial_rate_and_bounds() {
+    let mut pusd = Pusd::new();
+    let owner = pusd.owner;
+
+    let result = pusd.send(initialize_yield_mint(&pusd, owner, new_key(), 1_001, BOUNDS));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::RateOutOfBounds));
+
+    let inverted = RateBounds { min_rate: 100, max_rate: 0, ..BOUNDS };
+    let result = pusd.send(initialize_yield_mint(&pusd, owner, new_key(), 50, inverted));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::InvalidConfig));
+
+    let operator = pusd.operator;
+    let result = pusd.send(initialize_yield_mint(&pusd, operator, new_key(), 500, BOUNDS));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::Unauthorized));
+}
+
+#[test]
+fn update_rate_moves_the_mint_rate_within_bounds() {
+    let mut pusd = Pusd::new();
+    let (mint, rate_manager) = yield_mint(&mut pusd);
+    pusd.svm.warp(BOUNDS.min_update_interval, 1);
+
+    pusd.send(update_rate(rate_manager, mint, 550)).unwrap();
+
+    assert_eq!(mint_rate(&pusd, &mint), 550);
+    let config = rate_config(&pusd, &mint);
+    assert_eq!(config.current_rate, 550);
+    assert_eq!(config.last_update, pusd.svm.clock().unix_timestamp);
+}
+
+#[test]
+fn update_rate_rejects_rates_out_of_bounds() {
+    let mut pusd = Pusd::new();
+    let owner = pusd.owner;
+    let (mint, rate_manager) = yield_mint(&mut pusd);
+    pusd.send(set_rate_bounds(owner, mint, RateBounds { max_rate_change: 1_000, ..BOUNDS })).unwrap();
+    pusd.svm.warp(BOUNDS.min_update_interval, 1);
+
+    let result = pusd.send(update_rate(rate_manager, mint, BOUNDS.max_rate + 1));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::RateOutOfBounds));
+    let result = pusd.send(update_rate(rate_manager, mint, BOUNDS.min_rate - 1));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::RateOutOfBounds));
+
+    pusd.send(update_rate(rate_manager, mint, BOUNDS.max_rate)).unwrap();
+    assert_eq!(mint_rate(&pusd, &mint), BOUNDS.max_rate);
+}
+
+#[test]
+fn update_rate_limits_the_size_of_each_change() {
+    let mut pusd = Pusd::new();
+    let (mint, rate_manager) = yield_mint(&mut pusd);
+    pusd.svm.warp(BOUNDS.min_update_interval, 1);
+
+    let result = pusd.send(update_rate(rate_manager, mint, 500 + BOUNDS.max_rate_change as i16 + 1));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::RateChangeTooLarge));
+    let result = pusd.send(update_rate(rate_manager, mint, 500 - BOUNDS.max_rate_change as i16 - 1));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::RateChangeTooLarge));
+
+    pusd.send(update_rate(rate_manager, mint, 500 - BOUNDS.max_rate_change as i16)).unwrap();
+    assert_eq!(mint_rate(&pusd, &mint), 450);
+}
+
+#[test]
+fn update_rate_enforces_the_update_interval() {
+    let mut pusd = Pusd::new();
+    let (mint, rate_manager) = yield_mint(&mut pusd);
+
+    // Creation counts as an update
+    let result = pusd.send(update_rate(rate_manager, mint, 510));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::RateUpdateTooSoon));
+
+    pusd.svm.warp(BOUNDS.min_update_interval, 1);
+    pusd.send(update_rate(rate_manager, mint, 510)).unwrap();
+
+    pusd.svm.warp(BOUNDS.min_update_interval - 1, 1);
+    let result = pusd.send(update_rate(rate_manager, mint, 520));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::RateUpdateTooSoon));
+
+    pusd.svm.warp(1, 1);
+    pusd.send(update_rate(rate_manager, mint, 520)).unwrap();
+}
+
+#[test]
+fn rate_changes_are_role_gated() {
+    let mut pusd = Pusd::new();
+    let (mint, rate_manager) = yield_mint(&mut pusd);
+    pusd.svm.warp(BOUNDS.min_update_interval, 1);
+
+    let owner = pusd.owner;
+    let result = pusd.send(update_rate(owner, mint, 510));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::Unauthorized));
+
+    let result = pusd.send(set_rate_bounds(rate_manager, mint, RateBounds { max_rate: 5_000, ..BOUNDS }));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::Unauthorized));
+
+    let result = pusd.send(set_rate_bounds(owner, mint, RateBounds { min_update_interval: -1, ..BOUNDS }));
+    assert_eq!(result.unwrap_err(), pusd_error(PusdError::InvalidConfig));
+}
+
+#[test]
+fn deposits_issue_yield_tokens_against_the_reserve() {
+    let mut pusd = Pusd::new();
+    let (mint, _) = yield_mint(&mut pusd);
+    let user = holder(&mut pusd, mint, 10_000);
+
+    pusd.send(deposit(&pusd, user, mint, 10_000)).unwrap();
+    assert_eq!(pusd.balance(&pusd.ata(&user, &mint)), 10_000);
+    assert_eq!(pusd.balance(&reserve_pda(&mint)), 10_000);
+    assert_eq!(pusd.mint_state(&mint).supply, 10_000);
+
+    pusd.send(redeem(&pusd, user, mint, 10_000)).unwrap();
+    assert_eq!(pusd.balance(&pusd.ata(&user, &mint)), 0);
+    assert_eq!(pusd.balance(&pusd.ata(&user, &pusd.mint)), 10_000);
+    assert_eq!(pusd.balance(&reserve_pda(&mint)), 0);
+    assert_eq!(pusd.mint_state(&mint).supply, 0);
+}
+
+#[test]
+fn redemptions_pay_accrued_interest_from_the_reserve() {
+    let mut pusd = Pusd::new();
+    let (mint, _) = yield_mint(&mut pusd);
+    let user = holder(&mut pusd, mint, 1_000_000);
+    pusd.send(deposit(&pusd, user, mint, 1_000_000)).unwrap();
+
+    pusd.svm.warp(365 * 24 * 60 * 60, 1);
+    pusd.attest(RESERVES);
+    let now = pusd.svm.clock().unix_timestamp;
+    let owed = assets_for_yield_redemption(&interest_bearing_config(&pusd, &mint), 1_000_000, DECIMALS, now).unwrap();
+    assert!(owed > 1_050_000, "{owed}");
+
+    // Accrued interest is only paid once PUSD covering it reaches the reserve
+    assert_eq!(
+        pusd.send(redeem(&pusd, user, mint, 1_000_000)).unwrap_err(),
+        pusd_error(PusdError::InsufficientYieldReserve)
+    );
+    let operator = pusd.operator;
+    pusd.send(pusd.mint_by_operator_ix(operator, reserve_pda(&mint), owed - 1_000_000)).unwrap();
+
+    pusd.send(redeem(&pusd, user, mint, 1_000_000)).unwrap();
+    assert_eq!(pusd.balance(&pusd.ata(&user, &pusd.mint)), owed);
+    assert_eq!(pusd.balance(&reserve_pda(&mint)), 0);
+}
+
+#[test]
+fn later_deposits_receive_fewer_yield_tokens() {
+    let mut pusd = Pusd::new();
+    let (mint, _) = yield_mint(&mut pusd);
+    let user = holder(&mut pusd, mint, 1_000_000);
+
+    pusd.svm.warp(365 * 24 * 60 * 60, 1);
+    pusd.send(deposit(&pusd, user, mint, 1_000_000)).unwrap();
+
+    let issued = pusd.balance(&pusd.ata(&user, &mint));
+    assert!(issued < 952_000, "{issued}");
+    let now = pusd.svm.clock().unix_timestamp;
+    let value = assets_for_yield_redemption(&interest_bearing_config(&pusd, &mint), issued, DECIMALS, now).unwrap();
+    assert!(value <= 1_000_000, "{value}");
+}
+
+#[test]
+fn deposits_and_redemptions_reject_empty_amounts() {
+    let mut pusd = Pusd::new();
+    let (mint, _) = yield_mint(&mut pusd);
+    let user = holder(&mut pusd, mint, 10_000);
+
+    assert_eq!(pusd.send(deposit(&pusd, user, mint, 0)).unwrap_err(), pusd_error(PusdError::InvalidAmount));
+    assert_eq!(pusd.send(redeem(&pusd, user, mint, 0)).unwrap_err(), pusd_error(PusdError::InvalidAmount));
+}
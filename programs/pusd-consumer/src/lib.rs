@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use pusd_spl_cpi::{MintByProgram, PusdSpl};
+
+declare_id!("Bx7CQpEpVitqjfmuCxHZMQVspqnjB8oZUtBx8fHmmnn9");
+
+// Seed of the PDA registered with PUSD as an AuthorizedContract
+pub const MINTER_SEED: &[u8] = b"pusd_minter";
+
+/// Example consumer minting PUSD through CPI
+/// Register it with `register_authorized_program(pusd_consumer::ID, [MINTER_SEED])`
+#[program]
+pub mod pusd_consumer {
+    use super::*;
+
+    /// Mint PUSD to the recipient, signed by this program's minter PDA
+    pub fn mint_reward(ctx: Context<MintReward>, amount: u64) -> Result<()> {
+        let seeds = &[MINTER_SEED, &[ctx.bumps.minter]];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = MintByProgram {
+            contract_role: ctx.accounts.contract_role.to_account_info(),
+            program_authority: ctx.accounts.minter.to_account_info(),
+            contract_registration: ctx.accounts.contract_registration.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            program_state: ctx.accounts.program_state.to_account_info(),
+            reserve_attestation: ctx.accounts.reserve_attestation.to_account_info(),
+            recipient: ctx.accounts.recipient.to_account_info(),
+            mint_authority: ctx.accounts.mint_authority.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.pusd_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds
+        );
+        pusd_spl_cpi::mint(cpi_ctx, amount)?;
+
+        msg!("Minted {} PUSD reward via CPI", amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct MintReward<'info> {
+    /// This program's PDA registered as an AuthorizedContract
+    /// CHECK: PDA derived from MINTER_SEED, signs the CPI
+    #[account(seeds = [MINTER_SEED], bump)]
+    pub minter: AccountInfo<'info>,
+
+    /// The minter's role account in the PUSD program
+    /// CHECK: Validated by the PUSD program
+    pub contract_role: AccountInfo<'info>,
+
+    /// The minter's registration in the PUSD program
+    /// CHECK: Validated by the PUSD program
+    pub contract_registration: AccountInfo<'info>,
+
+    /// The PUSD Token-2022 mint
+    /// CHECK: Validated by the PUSD program
+    #[account(mut)]
+    pub mint: AccountInfo<'info>,
+
+    /// The PUSD program state holding the pause flag
+    /// CHECK: Validated by the PUSD program
+    pub program_state: AccountInfo<'info>,
+
+    /// Latest proof-of-reserves attestation for the mint
+    /// CHECK: Validated by the PUSD program
+    pub reserve_attestation: AccountInfo<'info>,
+
+    /// The recipient's token account
+    /// CHECK: Validated by the Token-2022 program
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+
+    /// The PUSD mint authority PDA
+    /// CHECK: Validated by the PUSD program
+    pub mint_authority: AccountInfo<'info>,
+
+    /// The Token-2022 program
+    /// CHECK: Validated by the PUSD program
+    pub token_program: AccountInfo<'info>,
+
+    pub pusd_program: Program<'info, PusdSpl>,
+}
@@ -0,0 +1,380 @@
+use anchor_lang::prelude::*;
+use solana_instructions_sysvar::{load_current_index_checked, load_instruction_at_checked};
+use solana_sdk_ids::{ed25519_program, sysvar};
+use anchor_spl::token_interface::{burn, Burn, Mint, Token2022, TokenAccount};
+
+use crate::*;
+
+// ============================================================================
+// Burn-and-mint bridge
+// Outbound: a holder burns PUSD and an OutboundBridgeMessage PDA records the
+// nonce, mint, destination chain, recipient and amount for off-chain relayers.
+// Inbound: anyone may relay a message attested by at least `threshold`
+// guardians. Signatures are checked by the ed25519 precompile in earlier
+// instructions of the same transaction and read back through the
+// instructions sysvar. The InboundBridgeMessage PDA prevents replays.
+// ============================================================================
+
+// Size of one signature offsets entry in ed25519 precompile instruction data
+const ED25519_OFFSETS_LEN: usize = 14;
+// Offsets start after the signature count and a padding byte
+const ED25519_OFFSETS_START: usize = 2;
+
+/// Canonical bytes guardians sign to attest an inbound transfer
+pub fn bridge_message(
+    source_chain: u16,
+    destination_chain: u16,
+    nonce: u64,
+    mint: &Pubkey,
+    recipient: &Pubkey,
+    amount: u64
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(BRIDGE_MESSAGE_DOMAIN.len() + 2 + 2 + 8 + 32 + 32 + 8);
+    message.extend_from_slice(BRIDGE_MESSAGE_DOMAIN);
+    message.extend_from_slice(&source_chain.to_le_bytes());
+    message.extend_from_slice(&destination_chain.to_le_bytes());
+    message.extend_from_slice(&nonce.to_le_bytes());
+    message.extend_from_slice(mint.as_ref());
+    message.extend_from_slice(recipient.as_ref());
+    message.extend_from_slice(&amount.to_le_bytes());
+    message
+}
+
+/// Returns the (public key, message) pairs verified by an ed25519 precompile
+/// instruction. Every offset must point into the instruction itself.
+pub fn parse_ed25519_signers(data: &[u8]) -> Result<Vec<(Pubkey, &[u8])>> {
+    let count = *data.first().ok_or(PusdError::InvalidSignatureInstruction)? as usize;
+
+    let mut signers = Vec::with_capacity(count);
+    for i in 0..count {
+        let start = ED25519_OFFSETS_START + i * ED25519_OFFSETS_LEN;
+        let offsets = data
+            .get(start..start + ED25519_OFFSETS_LEN)
+            .ok_or(PusdError::InvalidSignatureInstruction)?;
+        let read = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]) as usize;
+
+        // signature, public key and message must all live in this instruction
+        require!(
+            read(2) == u16::MAX as usize && read(6) == u16::MAX as usize && read(12) == u16::MAX as usize,
+            PusdError::InvalidSignatureInstruction
+        );
+
+        let pubkey_offset = read(4);
+        let pubkey = data
+            .get(pubkey_offset..pubkey_offset + 32)
+            .ok_or(PusdError::InvalidSignatureInstruction)?;
+        let message_offset = read(8);
+        let message = data
+            .get(message_offset..message_offset + read(10))
+            .ok_or(PusdError::InvalidSignatureInstruction)?;
+
+        signers.push((Pubkey::try_from(pubkey).unwrap(), message));
+    }
+    Ok(signers)
+}
+
+/// Counts distinct guardians that signed exactly `message`
+pub fn count_guardian_approvals(signers: &[(Pubkey, &[u8])], guardians: &[Pubkey], message: &[u8]) -> usize {
+    let mut approved: Vec<Pubkey> = Vec::with_capacity(guardians.len());
+    for (signer, signed) in signers {
+        if *signed == message && guardians.contains(signer) && !approved.contains(signer) {
+            approved.push(*signer);
+        }
+    }
+    approved.len()
+}
+
+/// Returns the data of every ed25519 precompile instruction before the current one
+pub(crate) fn _ed25519_instruction_data(instructions: &AccountInfo) -> Result<Vec<Vec<u8>>> {
+    let current_index = load_current_index_checked(instructions)?;
+    let mut precompile_data = Vec::new();
+    for index in 0..current_index {
+        let ix = load_instruction_at_checked(index as usize, instructions)?;
+        if ix.program_id == ed25519_program::ID {
+            precompile_data.push(ix.data);
+        }
+    }
+    Ok(precompile_data)
+}
+
+/// Set the local chain ID and the guardian set
+pub(crate) fn set_bridge_config(
+    ctx: Context<SetBridgeConfig>,
+    local_chain_id: u16,
+    guardians: Vec<Pubkey>,
+    threshold: u8
+) -> Result<()> {
+    require_not_sunset!(ctx.accounts.sunset_state);
+
+    // Verify the caller has Owner role
+    require_role!(ctx.accounts.owner_role, Role::Owner);
+
+    require!(
+        guardians.len() <= MAX_BRIDGE_GUARDIANS && threshold > 0 && threshold as usize <= guardians.len(),
+        PusdError::InvalidGuardianSet
+    );
+    for (i, guardian) in guardians.iter().enumerate() {
+        require!(
+            *guardian != Pubkey::default() && !guardians[..i].contains(guardian),
+            PusdError::InvalidGuardianSet
+        );
+    }
+
+    let bridge_config = &mut ctx.accounts.bridge_config;
+    bridge_config.local_chain_id = local_chain_id;
+    bridge_config.threshold = threshold;
+    bridge_config.guardians = guardians;
+    bridge_config.bump = ctx.bumps.bridge_config;
+
+    msg!(
+        "Bridge configured for chain {} with {}-of-{} guardians",
+        local_chain_id,
+        threshold,
+        bridge_config.guardians.len()
+    );
+    Ok(())
+}
+
+/// Burn PUSD and record an outbound message for the destination chain
+pub(crate) fn burn_for_bridge(
+    ctx: Context<BurnForBridge>,
+    destination_chain: u16,
+    recipient: [u8; 32],
+    amount: u64
+) -> Result<()> {
+    require_not_sunset!(ctx.accounts.sunset_state);
+
+    require!(amount > 0, PusdError::InvalidAmount);
+    require!(
+        destination_chain != ctx.accounts.bridge_config.local_chain_id,
+        PusdError::InvalidChain
+    );
+    require!(recipient != [0u8; 32], PusdError::InvalidAddress);
+
+    let cpi_accounts = Burn {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.holder_token.to_account_info(),
+        authority: ctx.accounts.holder.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    burn(cpi_ctx, amount)?;
+
+    let bridge_config = &mut ctx.accounts.bridge_config;
+    let nonce = bridge_config.outbound_nonce;
+    bridge_config.outbound_nonce = nonce.checked_add(1).ok_or(PusdError::MathOverflow)?;
+
+    let outbound = &mut ctx.accounts.outbound_message;
+    outbound.nonce = nonce;
+    outbound.mint = ctx.accounts.mint.key();
+    outbound.sender = ctx.accounts.holder.key();
+    outbound.destination_chain = destination_chain;
+    outbound.recipient = recipient;
+    outbound.amount = amount;
+    outbound.timestamp = Clock::get()?.unix_timestamp;
+    outbound.bump = ctx.bumps.outbound_message;
+
+    emit!(BridgeBurned {
+        nonce,
+        mint: outbound.mint,
+        sender: outbound.sender,
+        destination_chain,
+        recipient,
+        amount,
+    });
+    Ok(())
+}
+
+/// Mint PUSD for an inbound message attested by the guardian threshold
+pub(crate) fn receive_bridge_mint(
+    ctx: Context<ReceiveBridgeMint>,
+    source_chain: u16,
+    nonce: u64,
+    amount: u64
+) -> Result<()> {
+    require!(amount > 0, PusdError::InvalidAmount);
+
+    let bridge_config = &ctx.accounts.bridge_config;
+    require!(source_chain != bridge_config.local_chain_id, PusdError::InvalidChain);
+
+    let message = bridge_message(
+        source_chain,
+        bridge_config.local_chain_id,
+        nonce,
+        &ctx.accounts.mint.key(),
+        &ctx.accounts.recipient_token.key(),
+        amount
+    );
+
+    // Collect signers from ed25519 precompile instructions earlier in the transaction
+    let precompile_data = _ed25519_instruction_data(&ctx.accounts.instructions)?;
+    let mut signers = Vec::new();
+    for data in &precompile_data {
+        signers.extend(parse_ed25519_signers(data)?);
+    }
+    let approvals = count_guardian_approvals(&signers, &bridge_config.guardians, &message);
+    require!(
+        approvals >= bridge_config.threshold as usize,
+        PusdError::InsufficientGuardianSignatures
+    );
+
+    _mint_unattested(
+        &ctx.accounts.program_state,
+        &ctx.accounts.mint,
+        &ctx.accounts.recipient_token.to_account_info(),
+        &ctx.accounts.mint_authority,
+        &ctx.accounts.token_program,
+        ctx.bumps.mint_authority,
+        amount
+    )?;
+
+    let inbound = &mut ctx.accounts.inbound_message;
+    inbound.source_chain = source_chain;
+    inbound.nonce = nonce;
+    inbound.recipient = ctx.accounts.recipient_token.key();
+    inbound.amount = amount;
+    inbound.timestamp = Clock::get()?.unix_timestamp;
+    inbound.bump = ctx.bumps.inbound_message;
+
+    emit!(BridgeMinted {
+        source_chain,
+        nonce,
+        recipient: inbound.recipient,
+        amount,
+    });
+    Ok(())
+}
+
+// ============================================================================
+// Account Validation Structs
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct SetBridgeConfig<'info> {
+    /// The owner configuring the bridge
+    #[account(
+        seeds = [b"user_role", owner.key().as_ref()],
+        bump = owner_role.bump,
+        constraint = owner_role.role == Role::Owner @ PusdError::Unauthorized
+    )]
+    pub owner_role: Account<'info, UserRole>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Bridge configuration (created on first use)
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = BridgeConfig::LEN,
+        seeds = [b"bridge_config"],
+        bump
+    )]
+    pub bridge_config: Account<'info, BridgeConfig>,
+
+    /// Sunset tombstone; rejects the instruction once executed
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BurnForBridge<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    /// Bridge configuration holding the outbound nonce
+    #[account(mut, seeds = [b"bridge_config"], bump = bridge_config.bump)]
+    pub bridge_config: Account<'info, BridgeConfig>,
+
+    /// Outbound message for this burn
+    #[account(
+        init,
+        payer = holder,
+        space = OutboundBridgeMessage::LEN,
+        seeds = [b"bridge_out", bridge_config.outbound_nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub outbound_message: Account<'info, OutboundBridgeMessage>,
+
+    /// Program state holding the canonical mint
+    #[account(seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// The PUSD Token-2022 mint
+    #[account(
+        mut,
+        address = program_state.mint @ PusdError::InvalidMint,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The holder's PUSD token account
+    /// CHECK: Validated by Token-2022 program (owner must be the signing holder)
+    #[account(mut)]
+    pub holder_token: AccountInfo<'info>,
+
+    /// Sunset tombstone; rejects the instruction once executed
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(source_chain: u16, nonce: u64)]
+pub struct ReceiveBridgeMint<'info> {
+    /// Relayer paying for the inbound message account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Bridge configuration holding the guardian set
+    #[account(seeds = [b"bridge_config"], bump = bridge_config.bump)]
+    pub bridge_config: Account<'info, BridgeConfig>,
+
+    /// Inbound message marker; `init` fails if the nonce was already used
+    #[account(
+        init,
+        payer = payer,
+        space = InboundBridgeMessage::LEN,
+        seeds = [b"bridge_in", source_chain.to_le_bytes().as_ref(), nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub inbound_message: Account<'info, InboundBridgeMessage>,
+
+    /// The PUSD Token-2022 mint account
+    /// CHECK: Validated by Token-2022 program
+    #[account(mut)]
+    pub mint: AccountInfo<'info>,
+
+    /// The recipient token account named in the signed message
+    #[account(
+        mut,
+        constraint = recipient_token.mint == mint.key() @ PusdError::InvalidRecipientAccount
+    )]
+    pub recipient_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// Program state holding the pause flag
+    #[account(seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// The mint authority PDA controlled by this program
+    /// CHECK: PDA derived from "mint_authority" seed
+    #[account(seeds = [b"mint_authority"], bump)]
+    pub mint_authority: AccountInfo<'info>,
+
+    /// The instructions sysvar holding the ed25519 verification instructions
+    /// CHECK: Address checked against the instructions sysvar ID
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,233 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token_interface::{
+    set_authority,
+    Mint,
+    SetAuthority,
+    Token2022,
+    spl_token_2022::{
+        extension::confidential_transfer::instruction::{approve_account, update_mint},
+        instruction::AuthorityType,
+        solana_zk_sdk::encryption::pod::elgamal::PodElGamalPubkey,
+    },
+};
+
+use crate::*;
+
+// ============================================================================
+// Token-2022 ConfidentialTransferMint extension management
+// The mint_authority PDA holds the confidential transfer authority. New
+// accounts are never auto-approved: a Compliance role holder approves each
+// account, and the auditor ElGamal key lets the auditor decrypt every
+// confidential transfer so balances stay auditable. Every instruction is
+// pinned to the PUSD mint recorded in ProgramState.
+// ============================================================================
+
+/// Hand the confidential transfer mint authority to the program PDA
+pub(crate) fn confidential_authority_to_pda(ctx: Context<ConfidentialAuthorityToPda>) -> Result<()> {
+    require_not_sunset!(ctx.accounts.sunset_state);
+
+    // Verify the caller has Owner role
+    require_role!(ctx.accounts.owner_role, Role::Owner);
+
+    let pda = ctx.accounts.mint_authority.key();
+
+    let cpi_accounts = SetAuthority {
+        current_authority: ctx.accounts.current_authority.to_account_info(),
+        account_or_mint: ctx.accounts.mint.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    set_authority(cpi_ctx, AuthorityType::ConfidentialTransferMint, Some(pda))?;
+
+    msg!("Confidential transfer authority transferred to PDA: {}", pda);
+    Ok(())
+}
+
+/// Set the auditor ElGamal key and require manual account approval
+pub(crate) fn configure_confidential_transfers(
+    ctx: Context<ConfigureConfidentialTransfers>,
+    auditor_elgamal_pubkey: Option<[u8; 32]>
+) -> Result<()> {
+    require_not_sunset!(ctx.accounts.sunset_state);
+
+    // Verify the caller has Owner role
+    require_role!(ctx.accounts.owner_role, Role::Owner);
+
+    let ix = update_mint(
+        &ctx.accounts.token_program.key(),
+        &ctx.accounts.mint.key(),
+        &ctx.accounts.mint_authority.key(),
+        &[],
+        false,
+        auditor_elgamal_pubkey.map(PodElGamalPubkey::from)
+    )?;
+
+    let seeds = &[b"mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
+    invoke_signed(
+        &ix,
+        &[ctx.accounts.mint.to_account_info(), ctx.accounts.mint_authority.to_account_info()],
+        &[&seeds[..]]
+    )?;
+
+    msg!(
+        "Confidential transfers configured - auditor {}",
+        if auditor_elgamal_pubkey.is_some() { "set" } else { "cleared" }
+    );
+    Ok(())
+}
+
+/// Approve a token account for confidential transfers
+pub(crate) fn approve_confidential_account(ctx: Context<ApproveConfidentialAccount>) -> Result<()> {
+    require_not_sunset!(ctx.accounts.sunset_state);
+
+    // Verify the caller has Compliance role
+    require_role!(ctx.accounts.compliance_role, Role::Compliance);
+
+    let ix = approve_account(
+        &ctx.accounts.token_program.key(),
+        &ctx.accounts.token_account.key(),
+        &ctx.accounts.mint.key(),
+        &ctx.accounts.mint_authority.key(),
+        &[]
+    )?;
+
+    let seeds = &[b"mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
+    invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.token_account.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.mint_authority.to_account_info(),
+        ],
+        &[&seeds[..]]
+    )?;
+
+    msg!(
+        "Confidential transfers approved for {} by {}",
+        ctx.accounts.token_account.key(),
+        ctx.accounts.compliance.key()
+    );
+    Ok(())
+}
+
+// ============================================================================
+// Account Validation Structs
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct ConfidentialAuthorityToPda<'info> {
+    /// The owner authorizing the handover
+    #[account(
+        seeds = [b"user_role", owner.key().as_ref()],
+        bump = owner_role.bump,
+        constraint = owner_role.role == Role::Owner @ PusdError::Unauthorized
+    )]
+    pub owner_role: Account<'info, UserRole>,
+
+    pub owner: Signer<'info>,
+
+    /// The current confidential transfer mint authority
+    pub current_authority: Signer<'info>,
+
+    /// Program state recording the canonical PUSD mint
+    #[account(seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// The PUSD Token-2022 mint with the ConfidentialTransferMint extension
+    #[account(
+        mut,
+        address = program_state.mint @ PusdError::InvalidMint,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The PDA that becomes the new authority
+    /// CHECK: PDA derived from "mint_authority" seed
+    #[account(seeds = [b"mint_authority"], bump)]
+    pub mint_authority: AccountInfo<'info>,
+
+    /// Sunset tombstone; rejects the instruction once executed
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureConfidentialTransfers<'info> {
+    /// The owner configuring confidential transfers
+    #[account(
+        seeds = [b"user_role", owner.key().as_ref()],
+        bump = owner_role.bump,
+        constraint = owner_role.role == Role::Owner @ PusdError::Unauthorized
+    )]
+    pub owner_role: Account<'info, UserRole>,
+
+    pub owner: Signer<'info>,
+
+    /// Program state recording the canonical PUSD mint
+    #[account(seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// The PUSD Token-2022 mint with the ConfidentialTransferMint extension
+    #[account(
+        mut,
+        address = program_state.mint @ PusdError::InvalidMint,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The PDA holding the confidential transfer authority
+    /// CHECK: PDA derived from "mint_authority" seed
+    #[account(seeds = [b"mint_authority"], bump)]
+    pub mint_authority: AccountInfo<'info>,
+
+    /// Sunset tombstone; rejects the instruction once executed
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveConfidentialAccount<'info> {
+    /// The compliance officer's role account
+    #[account(
+        seeds = [b"user_role", compliance.key().as_ref()],
+        bump = compliance_role.bump,
+        constraint = compliance_role.role == Role::Compliance @ PusdError::Unauthorized
+    )]
+    pub compliance_role: Account<'info, UserRole>,
+
+    pub compliance: Signer<'info>,
+
+    /// The token account configured for confidential transfers
+    /// CHECK: Validated by Token-2022 program
+    #[account(mut)]
+    pub token_account: AccountInfo<'info>,
+
+    /// Program state recording the canonical PUSD mint
+    #[account(seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// The PUSD Token-2022 mint with the ConfidentialTransferMint extension
+    #[account(address = program_state.mint @ PusdError::InvalidMint, mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The PDA holding the confidential transfer authority
+    /// CHECK: PDA derived from "mint_authority" seed
+    #[account(seeds = [b"mint_authority"], bump)]
+    pub mint_authority: AccountInfo<'info>,
+
+    /// Sunset tombstone; rejects the instruction once executed
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+}
@@ -3,3 +3,61 @@ pub const VERSION: &str = "1.0";
 
 // Role activation delay (24 hours in seconds)
 pub const ROLE_ACTIVATION_DELAY: i64 = 24 * 60 * 60; // 86400 seconds
+
+
+// Maximum recipients per batch mint
+// Each recipient adds 41 bytes to the transaction (account key, account index
+// and amount); 18 is the most that fit a 1232-byte legacy transaction paid by
+// the operator alongside a SetComputeUnitLimit instruction. Larger batches
+// would need a v0 transaction with an address lookup table
+pub const MAX_BATCH_MINT_SIZE: usize = 18;
+
+// Maximum age of a mint_with_reference reference, and minimum age before its
+// receipt can be closed (~7 days at 400ms slots). Keep it longer than any
+// backend retry window; a closed receipt's reference is already too old to mint
+pub const MINT_RECEIPT_MIN_AGE_SLOTS: u64 = 7 * 24 * 60 * 60 * 5 / 2; // 1,512,000 slots
+
+// Window over which an operator's direct mints count towards the large mint threshold (24 hours in seconds)
+pub const LARGE_MINT_WINDOW: i64 = 24 * 60 * 60; // 86400 seconds
+
+// Lifetime of a pending mint request (24 hours in seconds)
+pub const MINT_REQUEST_TTL: i64 = 24 * 60 * 60; // 86400 seconds
+
+// Default maximum age of a reserve attestation before minting is blocked (7 days in seconds)
+pub const DEFAULT_RESERVE_STALENESS_WINDOW: i64 = 7 * 24 * 60 * 60; // 604800 seconds
+
+// Maximum length of the reserve report URI
+pub const MAX_REPORT_URI_LEN: usize = 200;
+
+// Basis point denominator (100% = 10,000 bps)
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+// Maximum redemption or PSM swap fee (5%)
+pub const MAX_FEE_BPS: u16 = 500;
+
+// Maximum decimals accepted for collateral and PUSD mints (10^18 fits in u64)
+pub const MAX_TOKEN_DECIMALS: u8 = 18;
+
+// Fixed-point scale for collateral prices in USD (8 decimals, 1.0 = 100,000,000)
+pub const PRICE_SCALE: u64 = 100_000_000;
+
+// Virtual shares and assets added to the staking vault share price
+// Makes first-depositor donation attacks cost ~10^6 times what they can steal
+pub const VAULT_VIRTUAL_SHARES: u64 = 1_000_000;
+pub const VAULT_VIRTUAL_ASSETS: u64 = 1;
+
+// Maximum number of guardian keys in the bridge guardian set
+pub const MAX_BRIDGE_GUARDIANS: usize = 10;
+
+// Domain separator prefixed to every bridge attestation message
+pub const BRIDGE_MESSAGE_DOMAIN: &[u8] = b"PUSD_BRIDGE_V1";
+
+// Domain separator prefixed to every mint permit message, followed by the program ID
+pub const PERMIT_MESSAGE_DOMAIN: &[u8] = b"PUSD_PERMIT_V2";
+
+// Seed limits for program-derived AuthorizedContract authorities
+pub const MAX_CONTRACT_SEEDS: usize = 15;
+pub const MAX_CONTRACT_SEED_LEN: usize = 32;
+
+// Delay between scheduling and executing a sunset (7 days in seconds)
+pub const SUNSET_TIMELOCK: i64 = 7 * 24 * 60 * 60;
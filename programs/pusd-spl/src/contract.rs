@@ -0,0 +1,182 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
+use anchor_spl::token_interface::Token2022;
+
+use crate::*;
+
+// ============================================================================
+// Program-derived AuthorizedContract holders
+// A consumer program is registered by program ID plus seeds. The role is
+// granted to the PDA of (seeds, program_id); since only that program can
+// sign for the PDA via invoke_signed, a valid signature identifies the
+// calling program. mint_by_program additionally rejects top-level calls.
+// ============================================================================
+
+/// Derives the authority PDA a program signs with for the given seeds
+pub fn derive_program_authority(program_id: &Pubkey, seeds: &[Vec<u8>]) -> Result<(Pubkey, u8)> {
+    require!(
+        !seeds.is_empty() &&
+            seeds.len() <= MAX_CONTRACT_SEEDS &&
+            seeds.iter().all(|seed| seed.len() <= MAX_CONTRACT_SEED_LEN),
+        PusdError::InvalidProgramAuthority
+    );
+    let seed_refs: Vec<&[u8]> = seeds.iter().map(|seed| seed.as_slice()).collect();
+    Pubkey::try_find_program_address(&seed_refs, program_id).ok_or(
+        PusdError::InvalidProgramAuthority.into()
+    )
+}
+
+/// Grant the AuthorizedContract role to a program-derived authority
+pub(crate) fn register_authorized_program(
+    ctx: Context<RegisterAuthorizedProgram>,
+    program_id: Pubkey,
+    seeds: Vec<Vec<u8>>
+) -> Result<()> {
+    require_not_sunset!(ctx.accounts.sunset_state);
+
+    // Verify the caller has Owner role
+    require_role!(ctx.accounts.owner_role, Role::Owner);
+
+    let (authority, authority_bump) = derive_program_authority(&program_id, &seeds)?;
+    require_keys_eq!(
+        authority,
+        ctx.accounts.program_authority.key(),
+        PusdError::InvalidProgramAuthority
+    );
+
+    let registration = &mut ctx.accounts.contract_registration;
+    registration.authority = authority;
+    registration.program_id = program_id;
+    registration.authority_bump = authority_bump;
+    registration.bump = ctx.bumps.contract_registration;
+
+    msg!("Registering program {} with authority {}", program_id, authority);
+
+    // Grant role with 24-hour activation delay
+    _grant_role(&mut ctx.accounts.user_role, authority, Role::AuthorizedContract, ctx.bumps.user_role)
+}
+
+/// Mint tokens via CPI from a registered program
+pub(crate) fn mint_by_program(ctx: Context<MintByProgram>, amount: u64) -> Result<()> {
+    // Verify the program authority has AuthorizedContract role
+    require_role!(ctx.accounts.contract_role, Role::AuthorizedContract);
+
+    require!(get_stack_height() > TRANSACTION_LEVEL_STACK_HEIGHT, PusdError::CpiRequired);
+    require!(amount > 0, PusdError::InvalidAmount);
+
+    msg!(
+        "Minting {} tokens via CPI from program {}",
+        amount,
+        ctx.accounts.contract_registration.program_id
+    );
+
+    _mint(
+        &ctx.accounts.program_state,
+        &ctx.accounts.mint,
+        &ctx.accounts.recipient,
+        &ctx.accounts.mint_authority,
+        &ctx.accounts.token_program,
+        &ctx.accounts.reserve_attestation,
+        ctx.bumps.mint_authority,
+        amount
+    )
+}
+
+// ============================================================================
+// Account Validation Structs
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct RegisterAuthorizedProgram<'info> {
+    /// The owner registering the program
+    #[account(
+        seeds = [b"user_role", owner.key().as_ref()],
+        bump = owner_role.bump,
+        constraint = owner_role.role == Role::Owner @ PusdError::Unauthorized
+    )]
+    pub owner_role: Account<'info, UserRole>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The program-derived authority receiving the role
+    /// CHECK: Checked against the PDA of the given program ID and seeds
+    pub program_authority: AccountInfo<'info>,
+
+    /// Role account for the program authority
+    #[account(
+        init,
+        payer = owner,
+        space = UserRole::LEN,
+        seeds = [b"user_role", program_authority.key().as_ref()],
+        bump
+    )]
+    pub user_role: Account<'info, UserRole>,
+
+    /// Registration binding the authority to its program (re-used when a removed program is registered again)
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = ContractRegistration::LEN,
+        seeds = [b"contract_registration", program_authority.key().as_ref()],
+        bump
+    )]
+    pub contract_registration: Account<'info, ContractRegistration>,
+
+    /// Sunset tombstone; rejects the instruction once executed
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MintByProgram<'info> {
+    /// The program authority's role account
+    #[account(
+        seeds = [b"user_role", program_authority.key().as_ref()],
+        bump = contract_role.bump,
+        constraint = contract_role.role == Role::AuthorizedContract @ PusdError::Unauthorized
+    )]
+    pub contract_role: Account<'info, UserRole>,
+
+    /// The registered program's PDA, signed via invoke_signed
+    pub program_authority: Signer<'info>,
+
+    /// Registration binding the authority to its program
+    #[account(
+        seeds = [b"contract_registration", program_authority.key().as_ref()],
+        bump = contract_registration.bump
+    )]
+    pub contract_registration: Account<'info, ContractRegistration>,
+
+    /// The Token-2022 mint account
+    /// CHECK: Validated by Token-2022 program
+    #[account(mut)]
+    pub mint: AccountInfo<'info>,
+
+    /// Program state holding the pause flag
+    #[account(seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Latest proof-of-reserves attestation for the mint
+    #[account(
+        seeds = [b"reserve_attestation", mint.key().as_ref()],
+        bump = reserve_attestation.bump
+    )]
+    pub reserve_attestation: Account<'info, ReserveAttestation>,
+
+    /// The recipient's token account
+    /// CHECK: Validated by Token-2022 program
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+
+    /// The mint authority PDA controlled by this program
+    /// CHECK: PDA derived from "mint_authority" seed
+    #[account(seeds = [b"mint_authority"], bump)]
+    pub mint_authority: AccountInfo<'info>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+}
@@ -27,4 +27,140 @@ pub enum PusdError {
     AlreadyInitialized,
     #[msg("Invalid program data")]
     InvalidProgramData,
+    #[msg("Batch must contain at least one recipient")]
+    EmptyBatch,
+    #[msg("Batch exceeds the maximum number of recipients")]
+    BatchTooLarge,
+    #[msg("Number of amounts does not match number of recipient accounts")]
+    BatchLengthMismatch,
+    #[msg("Recipient is not a token account for this mint")]
+    InvalidRecipientAccount,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("A mint with this reference has already been executed")]
+    DuplicateMintReference,
+    #[msg("Mint receipt is too recent to be closed")]
+    ReceiptTooRecent,
+    #[msg("Mint request has expired")]
+    MintRequestExpired,
+    #[msg("Mint request has not expired yet")]
+    MintRequestNotExpired,
+    #[msg("Approver must be different from the requester")]
+    SelfApproval,
+    #[msg("Direct operator minting is disabled; use request_mint")]
+    DirectMintDisabled,
+    #[msg("Amount exceeds the large mint threshold and requires approval")]
+    RequiresApproval,
+    #[msg("Amount exceeds the large mint threshold and requires Owner approval")]
+    RequiresOwnerApproval,
+    #[msg("Mint would push supply above the attested reserves")]
+    InsufficientReserves,
+    #[msg("Reserve attestation is older than the staleness window")]
+    ReserveAttestationStale,
+    #[msg("Reserve attestation does not belong to this mint")]
+    InvalidReserveAttestation,
+    #[msg("Report URI exceeds the maximum length")]
+    ReportUriTooLong,
+    #[msg("Invalid configuration value")]
+    InvalidConfig,
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+    #[msg("Fee exceeds the maximum allowed")]
+    FeeTooHigh,
+    #[msg("Collateral is disabled")]
+    CollateralDisabled,
+    #[msg("Swap would exceed the collateral debt ceiling")]
+    DebtCeilingExceeded,
+    #[msg("Token decimals are not supported")]
+    UnsupportedDecimals,
+    #[msg("Not enough collateral debt or vault liquidity for this swap")]
+    InsufficientPsmLiquidity,
+    #[msg("Invalid price")]
+    InvalidPrice,
+    #[msg("Price is older than the maximum allowed age")]
+    StalePrice,
+    #[msg("Price confidence interval is too wide")]
+    PriceConfidenceTooWide,
+    #[msg("Oracle account does not match the configured price source")]
+    InvalidOracleAccount,
+    #[msg("Not enough fees in the treasury")]
+    InsufficientTreasuryBalance,
+    #[msg("Interest rate is outside the configured bounds")]
+    RateOutOfBounds,
+    #[msg("Interest rate change exceeds the maximum step")]
+    RateChangeTooLarge,
+    #[msg("Interest rate was updated too recently")]
+    RateUpdateTooSoon,
+    #[msg("Deposit is too small to receive any shares")]
+    ZeroShares,
+    #[msg("KYC allowlist mode is not enabled")]
+    KycModeDisabled,
+    #[msg("Token account has already been KYC approved")]
+    AlreadyKycApproved,
+    #[msg("Token account is not KYC approved")]
+    NotKycApproved,
+    #[msg("Invalid bridge guardian set or threshold")]
+    InvalidGuardianSet,
+    #[msg("Not enough guardian signatures for the bridge message")]
+    InsufficientGuardianSignatures,
+    #[msg("Malformed ed25519 signature instruction")]
+    InvalidSignatureInstruction,
+    #[msg("Invalid bridge chain")]
+    InvalidChain,
+    #[msg("Mint permit has expired")]
+    PermitExpired,
+    #[msg("Mint permit is not signed by the authorized signer")]
+    InvalidPermitSignature,
+    #[msg("Authority is not the PDA of the given program and seeds")]
+    InvalidProgramAuthority,
+    #[msg("This instruction must be called through CPI")]
+    CpiRequired,
+    #[msg("Account is already at the current layout version")]
+    AccountAlreadyMigrated,
+    #[msg("Account type or layout cannot be migrated")]
+    UnsupportedAccountLayout,
+    #[msg("Program is immutable and has no upgrade authority")]
+    ProgramImmutable,
+    #[msg("Only the governance admin can perform this action")]
+    OnlyAdmin,
+    #[msg("Governance admin has not been set")]
+    AdminNotSet,
+    #[msg("Upgrade authority privileges have already been renounced")]
+    UpgradeAuthorityRenounced,
+    #[msg("Sunset timelock has not elapsed")]
+    SunsetNotReady,
+    #[msg("Program has been sunset")]
+    ProgramSunset,
+    #[msg("Program has not been sunset")]
+    SunsetNotExecuted,
+    #[msg("Minting is paused")]
+    ProgramPaused,
+    #[msg("Role is not a minter role")]
+    NotAMinter,
+    #[msg("Role is suspended")]
+    RoleSuspended,
+    #[msg("Role is not pending activation")]
+    RoleNotPending,
+    #[msg("Role is not suspended")]
+    RoleNotSuspended,
+    #[msg("The last active Owner cannot renounce")]
+    LastOwner,
+    #[msg("Invalid rent recipient")]
+    InvalidRentRecipient,
+    #[msg("Canonical mint has already been recorded")]
+    MintAlreadyRecorded,
+    #[msg("Mint is not the canonical PUSD mint")]
+    InvalidMint,
+    #[msg("Mint lacks the DefaultAccountState extension")]
+    MissingDefaultAccountState,
+    #[msg("No governance admin handover is pending")]
+    NoPendingAdmin,
+    #[msg("Only the proposed governance admin can accept")]
+    OnlyPendingAdmin,
+    #[msg("Treasury must be emptied before the sunset")]
+    TreasuryNotEmpty,
+    #[msg("Mint reference is too old or from a future slot")]
+    ReferenceExpired,
+    #[msg("Yield token reserve cannot cover the redemption")]
+    InsufficientYieldReserve,
 }
\ No newline at end of file
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::Role;
+
+// Emitted once per recipient by batch minting
+#[event]
+pub struct TokensMinted {
+    pub minter: Pubkey,            // signer that authorized the mint
+    pub recipient: Pubkey,         // recipient token account
+    pub amount: u64,               // amount minted in base units
+}
+
+// Emitted when a holder burns PUSD for off-chain redemption
+#[event]
+pub struct RedemptionRequested {
+    pub mint: Pubkey,              // mint the tokens were burned from
+    pub holder: Pubkey,            // holder that burned the tokens
+    pub amount: u64,               // total amount surrendered, including the fee
+    pub fee: u64,                  // portion transferred to the treasury
+    pub burned: u64,               // portion burned and owed to the holder off-chain
+}
+
+// Emitted when PUSD is burned to be minted on another chain
+#[event]
+pub struct BridgeBurned {
+    pub nonce: u64,                // outbound message nonce
+    pub mint: Pubkey,              // mint the tokens were burned from
+    pub sender: Pubkey,            // holder that burned the tokens
+    pub destination_chain: u16,    // chain the tokens are minted on
+    pub recipient: [u8; 32],       // recipient address on the destination chain
+    pub amount: u64,               // amount burned in base units
+}
+
+// Emitted when PUSD is minted for a guardian-attested inbound message
+#[event]
+pub struct BridgeMinted {
+    pub source_chain: u16,         // chain the tokens were burned on
+    pub nonce: u64,                // source chain message nonce
+    pub recipient: Pubkey,         // recipient token account
+    pub amount: u64,               // amount minted in base units
+}
+
+// Emitted when a role holder gives up its own role
+#[event]
+pub struct RoleRenounced {
+    pub user: Pubkey,              // holder that renounced the role
+    pub role: Role,                // role that was renounced
+    pub rent_recipient: Pubkey,    // account refunded the role account rent
+}
@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+
+use crate::*;
+
+// ============================================================================
+// Guardian
+// The Guardian is assigned at initialization and active immediately. It can
+// only take powers away: pause all minting and close a single minter's role.
+// Unpausing and granting roles stay with the Owner.
+// ============================================================================
+
+/// Block every minting path until an Owner unpauses
+pub(crate) fn pause(ctx: Context<GuardianAction>) -> Result<()> {
+    // Verify the caller has Guardian role
+    require_role!(ctx.accounts.guardian_role, Role::Guardian);
+
+    ctx.accounts.program_state.paused = true;
+
+    msg!("Minting paused by guardian {}", ctx.accounts.guardian.key());
+    Ok(())
+}
+
+/// Resume minting after a pause
+pub(crate) fn unpause(ctx: Context<UpdateConfig>) -> Result<()> {
+    // Verify the caller has Owner role
+    require_role!(ctx.accounts.owner_role, Role::Owner);
+
+    ctx.accounts.program_state.paused = false;
+
+    msg!("Minting unpaused by owner {}", ctx.accounts.owner.key());
+    Ok(())
+}
+
+/// Close a minter's role account immediately
+pub(crate) fn guardian_revoke_minter(ctx: Context<GuardianRevokeMinter>) -> Result<()> {
+    require_not_sunset!(ctx.accounts.sunset_state);
+
+    // Verify the caller has Guardian role
+    require_role!(ctx.accounts.guardian_role, Role::Guardian);
+
+    require!(
+        matches!(
+            ctx.accounts.minter_role.role,
+            Role::Operator | Role::AuthorizedContract | Role::AuthorizedSigner
+        ),
+        PusdError::NotAMinter
+    );
+
+    msg!(
+        "Guardian {} revoking role {:?} for minter: {}",
+        ctx.accounts.guardian.key(),
+        ctx.accounts.minter_role.role,
+        ctx.accounts.minter_role.user
+    );
+
+    // The account will be closed automatically by Anchor's close constraint
+    // Rent will be refunded to an Owner, never to the minter or the guardian
+    Ok(())
+}
+
+// ============================================================================
+// Account Validation Structs
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct GuardianAction<'info> {
+    /// The guardian taking the emergency action
+    #[account(
+        seeds = [b"user_role", guardian.key().as_ref()],
+        bump = guardian_role.bump,
+        constraint = guardian_role.role == Role::Guardian @ PusdError::Unauthorized
+    )]
+    pub guardian_role: Account<'info, UserRole>,
+
+    pub guardian: Signer<'info>,
+
+    /// Program state holding the pause flag
+    #[account(mut, seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+#[derive(Accounts)]
+pub struct GuardianRevokeMinter<'info> {
+    /// The guardian revoking the minter
+    #[account(
+        seeds = [b"user_role", guardian.key().as_ref()],
+        bump = guardian_role.bump,
+        constraint = guardian_role.role == Role::Guardian @ PusdError::Unauthorized
+    )]
+    pub guardian_role: Account<'info, UserRole>,
+
+    #[account(mut)]
+    pub guardian: Signer<'info>,
+
+    /// The minter role account being closed
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"user_role", minter_role.user.as_ref()],
+        bump = minter_role.bump
+    )]
+    pub minter_role: Account<'info, UserRole>,
+
+    /// The role account of the Owner refunded the rent
+    #[account(
+        seeds = [b"user_role", owner.key().as_ref()],
+        bump = owner_role.bump,
+        constraint = owner_role.role == Role::Owner @ PusdError::Unauthorized
+    )]
+    pub owner_role: Account<'info, UserRole>,
+
+    /// An Owner receiving the role account rent; the revoked key gets nothing back
+    /// CHECK: Holds the Owner role, checked through owner_role
+    #[account(mut)]
+    pub owner: AccountInfo<'info>,
+
+    /// Sunset tombstone; rejects the instruction once executed
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+}
@@ -0,0 +1,302 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token_interface::{
+    freeze_account,
+    set_authority,
+    thaw_account,
+    FreezeAccount,
+    Mint,
+    SetAuthority,
+    ThawAccount,
+    Token2022,
+    TokenAccount,
+    spl_token_2022::{
+        extension::{
+            default_account_state::{instruction::update_default_account_state, DefaultAccountState},
+            BaseStateWithExtensions,
+            StateWithExtensions,
+        },
+        instruction::AuthorityType,
+        state::{AccountState, Mint as MintState},
+    },
+};
+
+use crate::*;
+
+// ============================================================================
+// KYC allowlist mode
+// In a permissioned deployment the mint uses DefaultAccountState=Frozen with
+// the "freeze_authority" PDA as freeze authority, so every new token account
+// starts frozen. The mint is created with the extension off-chain and
+// freeze_authority_to_pda hands the freeze authority to the PDA. A KycOfficer
+// thaws an account after KYC and can re-freeze it later; each decision is
+// recorded in a KycRecord PDA.
+// ============================================================================
+
+/// Hand the mint's freeze authority to the program PDA and default new accounts to frozen
+pub(crate) fn freeze_authority_to_pda(ctx: Context<FreezeAuthorityToPda>) -> Result<()> {
+    // Verify the caller has Owner role
+    require_role!(ctx.accounts.owner_role, Role::Owner);
+
+    require!(ctx.accounts.program_state.kyc_required, PusdError::KycModeDisabled);
+
+    // DefaultAccountState can only be added when the mint is created
+    {
+        let mint_info = ctx.accounts.mint.to_account_info();
+        let mint_data = mint_info.try_borrow_data()?;
+        let mint = StateWithExtensions::<MintState>::unpack(&mint_data)?;
+        require!(
+            mint.get_extension::<DefaultAccountState>().is_ok(),
+            PusdError::MissingDefaultAccountState
+        );
+    }
+
+    let pda = ctx.accounts.freeze_authority.key();
+
+    let cpi_accounts = SetAuthority {
+        current_authority: ctx.accounts.current_authority.to_account_info(),
+        account_or_mint: ctx.accounts.mint.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    set_authority(cpi_ctx, AuthorityType::FreezeAccount, Some(pda))?;
+
+    let ix = update_default_account_state(
+        &ctx.accounts.token_program.key(),
+        &ctx.accounts.mint.key(),
+        &pda,
+        &[],
+        &AccountState::Frozen
+    )?;
+
+    let seeds = &[b"freeze_authority".as_ref(), &[ctx.bumps.freeze_authority]];
+    invoke_signed(
+        &ix,
+        &[ctx.accounts.mint.to_account_info(), ctx.accounts.freeze_authority.to_account_info()],
+        &[&seeds[..]]
+    )?;
+
+    msg!("Freeze authority transferred to PDA {}; new accounts start frozen", pda);
+    Ok(())
+}
+
+/// Record a KYC approval and thaw the token account
+pub(crate) fn kyc_approve(ctx: Context<KycApprove>) -> Result<()> {
+    require_not_sunset!(ctx.accounts.sunset_state);
+
+    // Verify the caller has KycOfficer role
+    require_role!(ctx.accounts.kyc_officer_role, Role::KycOfficer);
+
+    require!(ctx.accounts.program_state.kyc_required, PusdError::KycModeDisabled);
+    require!(!ctx.accounts.kyc_record.approved, PusdError::AlreadyKycApproved);
+
+    if ctx.accounts.token_account.is_frozen() {
+        let seeds = &[b"freeze_authority".as_ref(), &[ctx.bumps.freeze_authority]];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = ThawAccount {
+            account: ctx.accounts.token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.freeze_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds
+        );
+        thaw_account(cpi_ctx)?;
+    }
+
+    let kyc_record = &mut ctx.accounts.kyc_record;
+    kyc_record.token_account = ctx.accounts.token_account.key();
+    kyc_record.holder = ctx.accounts.token_account.owner;
+    kyc_record.mint = ctx.accounts.mint.key();
+    kyc_record.officer = ctx.accounts.kyc_officer.key();
+    kyc_record.approved = true;
+    kyc_record.updated_at = Clock::get()?.unix_timestamp;
+    kyc_record.bump = ctx.bumps.kyc_record;
+
+    msg!(
+        "KYC approved for {} (holder {}) by {}",
+        kyc_record.token_account,
+        kyc_record.holder,
+        kyc_record.officer
+    );
+    Ok(())
+}
+
+/// Revoke a KYC approval and re-freeze the token account
+pub(crate) fn kyc_revoke(ctx: Context<KycRevoke>) -> Result<()> {
+    require_not_sunset!(ctx.accounts.sunset_state);
+
+    // Verify the caller has KycOfficer role
+    require_role!(ctx.accounts.kyc_officer_role, Role::KycOfficer);
+
+    require!(ctx.accounts.program_state.kyc_required, PusdError::KycModeDisabled);
+    require!(ctx.accounts.kyc_record.approved, PusdError::NotKycApproved);
+
+    if !ctx.accounts.token_account.is_frozen() {
+        let seeds = &[b"freeze_authority".as_ref(), &[ctx.bumps.freeze_authority]];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = FreezeAccount {
+            account: ctx.accounts.token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.freeze_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds
+        );
+        freeze_account(cpi_ctx)?;
+    }
+
+    let kyc_record = &mut ctx.accounts.kyc_record;
+    kyc_record.officer = ctx.accounts.kyc_officer.key();
+    kyc_record.approved = false;
+    kyc_record.updated_at = Clock::get()?.unix_timestamp;
+
+    msg!(
+        "KYC revoked for {} (holder {}) by {}",
+        kyc_record.token_account,
+        kyc_record.holder,
+        kyc_record.officer
+    );
+    Ok(())
+}
+
+// ============================================================================
+// Account Validation Structs
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct FreezeAuthorityToPda<'info> {
+    /// The owner authorizing the handover
+    #[account(
+        seeds = [b"user_role", owner.key().as_ref()],
+        bump = owner_role.bump,
+        constraint = owner_role.role == Role::Owner @ PusdError::Unauthorized
+    )]
+    pub owner_role: Account<'info, UserRole>,
+
+    pub owner: Signer<'info>,
+
+    /// Program state holding the KYC mode flag and the canonical mint
+    #[account(seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// The current freeze authority of the mint
+    pub current_authority: Signer<'info>,
+
+    /// The PUSD Token-2022 mint, created with the DefaultAccountState extension
+    #[account(
+        mut,
+        address = program_state.mint @ PusdError::InvalidMint,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The PDA that becomes the freeze authority
+    /// CHECK: PDA derived from "freeze_authority" seed
+    #[account(seeds = [b"freeze_authority"], bump)]
+    pub freeze_authority: AccountInfo<'info>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct KycApprove<'info> {
+    /// The KYC officer's role account
+    #[account(
+        seeds = [b"user_role", kyc_officer.key().as_ref()],
+        bump = kyc_officer_role.bump,
+        constraint = kyc_officer_role.role == Role::KycOfficer @ PusdError::Unauthorized
+    )]
+    pub kyc_officer_role: Account<'info, UserRole>,
+
+    #[account(mut)]
+    pub kyc_officer: Signer<'info>,
+
+    /// Program state holding the KYC mode flag
+    #[account(seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// KYC record for the token account (re-used after a revocation)
+    #[account(
+        init_if_needed,
+        payer = kyc_officer,
+        space = KycRecord::LEN,
+        seeds = [b"kyc_record", token_account.key().as_ref()],
+        bump
+    )]
+    pub kyc_record: Account<'info, KycRecord>,
+
+    /// The holder's token account to thaw
+    #[account(mut, token::mint = mint, token::token_program = token_program)]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The Token-2022 mint
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The PDA holding the mint's freeze authority
+    /// CHECK: PDA derived from "freeze_authority" seed
+    #[account(seeds = [b"freeze_authority"], bump)]
+    pub freeze_authority: AccountInfo<'info>,
+
+    /// Sunset tombstone; rejects the instruction once executed
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct KycRevoke<'info> {
+    /// The KYC officer's role account
+    #[account(
+        seeds = [b"user_role", kyc_officer.key().as_ref()],
+        bump = kyc_officer_role.bump,
+        constraint = kyc_officer_role.role == Role::KycOfficer @ PusdError::Unauthorized
+    )]
+    pub kyc_officer_role: Account<'info, UserRole>,
+
+    pub kyc_officer: Signer<'info>,
+
+    /// Program state holding the KYC mode flag
+    #[account(seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// KYC record for the token account
+    #[account(
+        mut,
+        seeds = [b"kyc_record", token_account.key().as_ref()],
+        bump = kyc_record.bump,
+        has_one = mint
+    )]
+    pub kyc_record: Account<'info, KycRecord>,
+
+    /// The holder's token account to re-freeze
+    #[account(mut, token::mint = mint, token::token_program = token_program)]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The Token-2022 mint
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The PDA holding the mint's freeze authority
+    /// CHECK: PDA derived from "freeze_authority" seed
+    #[account(seeds = [b"freeze_authority"], bump)]
+    pub freeze_authority: AccountInfo<'info>,
+
+    /// Sunset tombstone; rejects the instruction once executed
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+}
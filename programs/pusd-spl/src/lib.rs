@@ -1,11 +1,15 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_interface::{
     mint_to,
     set_authority,
+    Mint,
     MintTo,
     SetAuthority,
     Token2022,
+    TokenAccount,
     spl_token_2022::instruction::AuthorityType,
 };
 
@@ -14,11 +18,42 @@ mod state;
 mod errors;
 mod constants;
 mod modifiers;
+mod events;
+pub mod psm;
+pub mod math;
+pub mod oracle;
+pub mod treasury;
+pub mod transfer_fee;
+pub mod yield_token;
+pub mod vault;
+pub mod confidential;
+pub mod kyc;
+pub mod bridge;
+pub mod permit;
+pub mod contract;
+pub mod migrate;
+pub mod upgrade;
+pub mod sunset;
+pub mod guardian;
 
 // Re-export for convenience
 pub use state::*;
 pub use errors::*;
 pub use constants::*;
+pub use events::*;
+pub use psm::*;
+pub use treasury::*;
+pub use transfer_fee::*;
+pub use yield_token::*;
+pub use vault::*;
+pub use confidential::*;
+pub use kyc::*;
+pub use bridge::*;
+pub use permit::*;
+pub use contract::*;
+pub use migrate::*;
+pub use sunset::*;
+pub use guardian::*;
 
 declare_id!("9ftLECMyJfEk27kjxyrs3cPh8h6EtKET7gpj8v2RqN1e");
 
@@ -26,26 +61,35 @@ declare_id!("9ftLECMyJfEk27kjxyrs3cPh8h6EtKET7gpj8v2RqN1e");
 pub mod pusd_spl {
     use super::*;
 
-    /// Initialize the program with owner and operator roles
+    /// Initialize the program with owner, operator and guardian roles
     /// SECURITY: Only the program upgrade authority (deployer) can call this function
-    /// The deployer assigns two separate addresses for owner and operator roles
+    /// The deployer assigns separate addresses for owner and operator roles
+    /// The guardian must differ from both and is active immediately
     /// The deployer themselves does not receive any role
     /// This function can only be called once during program setup
     pub fn initialize(
         ctx: Context<Initialize>,
         owner_address: Pubkey,
-        operator_address: Pubkey
+        operator_address: Pubkey,
+        guardian_address: Pubkey,
+        kyc_required: bool
     ) -> Result<()> {
         // Verify that the caller is the program upgrade authority
         require_upgrade_authority!(ctx.accounts.program_data, ctx.accounts.payer.key());
 
-        // Ensure the program hasn't been initialized already
+        // Ensure the program hasn't been initialized already, or sunset
         let program_state = &mut ctx.accounts.program_state;
         require_not_initialized!(program_state);
+        require!(ctx.accounts.sunset_state.data_is_empty(), PusdError::ProgramSunset);
 
         // Validate that the provided addresses are not zero addresses
         require_valid_address!(owner_address);
         require_valid_address!(operator_address);
+        require_valid_address!(guardian_address);
+        require!(
+            guardian_address != owner_address && guardian_address != operator_address,
+            PusdError::InvalidAddress
+        );
 
         msg!(
             "Initializing program with owner: {:?} and operator: {:?}",
@@ -69,13 +113,28 @@ pub mod pusd_spl {
             ctx.bumps.operator_role
         )?;
 
+        // Grant guardian role; emergency powers must not wait out the activation delay
+        _grant_role(
+            &mut ctx.accounts.guardian_role,
+            guardian_address,
+            Role::Guardian,
+            ctx.bumps.guardian_role
+        )?;
+        ctx.accounts.guardian_role.role_active_time = Clock::get()?.unix_timestamp;
+        ctx.accounts.guardian_role.status = RoleStatus::Active;
+
         // Set program state as initialized
+        program_state.version = ProgramState::VERSION;
         program_state.is_initialized = true;
         program_state.bump = ctx.bumps.program_state;
+        program_state.kyc_required = kyc_required;
+        program_state.admin = owner_address;
 
         msg!("Program initialized successfully - version {}", VERSION);
         msg!("Owner: {}", owner_address);
         msg!("Operator: {}", operator_address);
+        msg!("Guardian: {}", guardian_address);
+        msg!("KYC allowlist mode: {}", kyc_required);
         msg!("Initialized by deployer: {}", ctx.accounts.payer.key());
         Ok(())
     }
@@ -83,9 +142,12 @@ pub mod pusd_spl {
     /// Transfer the mint authority from the current owner to the program PDA
     /// This must be called once after deployment to enable program-controlled minting
     /// After this, only the program can mint new tokens
+    /// Only the governance admin (or the upgrade authority, unless renounced) can execute this function
     pub fn transfer_mint_authority_to_pda(ctx: Context<TransferAuthority>) -> Result<()> {
-        // Verify that the caller is the program upgrade authority
-        require_upgrade_authority!(ctx.accounts.program_data, ctx.accounts.payer.key());
+        _require_governance(&ctx.accounts.program_state, &ctx.accounts.program_data, ctx.accounts.payer.key())?;
+
+        // The canonical mint is recorded once; every mint path is pinned to it
+        require_keys_eq!(ctx.accounts.program_state.mint, Pubkey::default(), PusdError::MintAlreadyRecorded);
 
         msg!("Transferring mint authority to PDA");
 
@@ -95,25 +157,73 @@ pub mod pusd_spl {
             ctx.program_id
         );
 
-        // Transfer the mint authority to the PDA
-        let cpi_accounts = SetAuthority {
-            current_authority: ctx.accounts.current_authority.to_account_info(),
-            account_or_mint: ctx.accounts.mint.to_account_info(),
-        };
+        // Mints handed over before the canonical mint was recorded only need recording
+        if ctx.accounts.mint.mint_authority != COption::Some(_mint_authority_pda) {
+            // Transfer the mint authority to the PDA
+            let cpi_accounts = SetAuthority {
+                current_authority: ctx.accounts.current_authority.to_account_info(),
+                account_or_mint: ctx.accounts.mint.to_account_info(),
+            };
+
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
 
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            set_authority(cpi_ctx, AuthorityType::MintTokens, Some(_mint_authority_pda))?;
+        }
 
-        set_authority(cpi_ctx, AuthorityType::MintTokens, Some(_mint_authority_pda))?;
+        ctx.accounts.program_state.mint = ctx.accounts.mint.key();
 
         msg!("Successfully transferred mint authority to PDA: {}", _mint_authority_pda);
         Ok(())
     }
+
+    /// Propose a new governance admin (e.g. a multisig); it takes over once it accepts
+    /// Proposing again replaces the pending admin
+    /// Only the governance admin (or the upgrade authority, unless renounced) can execute this function
+    pub fn propose_admin(ctx: Context<UpdateGovernance>, new_admin: Pubkey) -> Result<()> {
+        _require_governance(&ctx.accounts.program_state, &ctx.accounts.program_data, ctx.accounts.signer.key())?;
+
+        require_valid_address!(new_admin);
+
+        msg!("Governance admin {} proposed {}", ctx.accounts.program_state.admin, new_admin);
+        ctx.accounts.program_state.pending_admin = new_admin;
+        Ok(())
+    }
+
+    /// Accept a pending governance admin handover
+    /// Only the proposed admin can execute this function, proving it can sign
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        let program_state = &mut ctx.accounts.program_state;
+        require!(program_state.pending_admin != Pubkey::default(), PusdError::NoPendingAdmin);
+        require_keys_eq!(ctx.accounts.new_admin.key(), program_state.pending_admin, PusdError::OnlyPendingAdmin);
+
+        msg!("Governance admin changed from {} to {}", program_state.admin, program_state.pending_admin);
+        program_state.admin = program_state.pending_admin;
+        program_state.pending_admin = Pubkey::default();
+        Ok(())
+    }
+
+    /// Permanently remove the upgrade authority from the program's trust model
+    /// Only the governance admin can execute this function
+    pub fn renounce_upgrade_authority_privileges(ctx: Context<UpdateGovernance>) -> Result<()> {
+        let program_state = &mut ctx.accounts.program_state;
+        require!(program_state.admin != Pubkey::default(), PusdError::AdminNotSet);
+        require_keys_eq!(ctx.accounts.signer.key(), program_state.admin, PusdError::OnlyAdmin);
+        require!(!program_state.upgrade_authority_renounced, PusdError::UpgradeAuthorityRenounced);
+
+        program_state.upgrade_authority_renounced = true;
+
+        msg!("Upgrade authority privileges renounced by admin {}", program_state.admin);
+        Ok(())
+    }
+
     /// Administrative function to assign or update a user's role
     /// Only the Owner can execute this function
     /// Role will be activated after 24 hours
     /// Note: User must not have any existing role. Remove existing role first.
     pub fn add_role(ctx: Context<AddRole>, user: Pubkey, role: Role) -> Result<()> {
+        require_not_sunset!(ctx.accounts.sunset_state);
+
         // Verify the caller has Owner role
         require_role!(ctx.accounts.owner_role, Role::Owner);
 
@@ -144,6 +254,8 @@ pub mod pusd_spl {
     /// Only the Owner can execute this function
     /// The role account will be closed and rent refunded to the owner
     pub fn remove_role(ctx: Context<RemoveRole>) -> Result<()> {
+        require_not_sunset!(ctx.accounts.sunset_state);
+
         // Verify the caller has Owner role
         require_role!(ctx.accounts.owner_role, Role::Owner);
 
@@ -158,11 +270,115 @@ pub mod pusd_spl {
         Ok(())
     }
 
+    /// Cancel a role that is still waiting out its activation delay
+    /// Only the Owner can execute this function
+    /// The role account will be closed and rent refunded to the owner
+    pub fn cancel_pending_role(ctx: Context<RemoveRole>) -> Result<()> {
+        require_not_sunset!(ctx.accounts.sunset_state);
+
+        // Verify the caller has Owner role
+        require_role!(ctx.accounts.owner_role, Role::Owner);
+
+        let current_time = Clock::get()?.unix_timestamp;
+        require!(
+            ctx.accounts.user_role.effective_status(current_time) == RoleStatus::Pending,
+            PusdError::RoleNotPending
+        );
+
+        msg!(
+            "Cancelling pending role {:?} for user: {}",
+            ctx.accounts.user_role.role,
+            ctx.accounts.user_role.user
+        );
+
+        // The account will be closed automatically by Anchor's close constraint
+        Ok(())
+    }
+
+    /// Temporarily disable a user's role without closing its account
+    /// Only the Owner can execute this function
+    pub fn suspend_role(ctx: Context<UpdateRoleStatus>) -> Result<()> {
+        require_not_sunset!(ctx.accounts.sunset_state);
+
+        // Verify the caller has Owner role
+        require_role!(ctx.accounts.owner_role, Role::Owner);
+
+        let user_role = &mut ctx.accounts.user_role;
+        require!(user_role.user != ctx.accounts.owner.key(), PusdError::Unauthorized);
+        require!(user_role.status != RoleStatus::Suspended, PusdError::RoleSuspended);
+
+        user_role.status = RoleStatus::Suspended;
+
+        msg!("Suspended role {:?} for user: {}", user_role.role, user_role.user);
+        Ok(())
+    }
+
+    /// Re-enable a suspended role
+    /// Only the Owner can execute this function
+    /// The original activation time still applies
+    pub fn resume_role(ctx: Context<UpdateRoleStatus>) -> Result<()> {
+        require_not_sunset!(ctx.accounts.sunset_state);
+
+        // Verify the caller has Owner role
+        require_role!(ctx.accounts.owner_role, Role::Owner);
+
+        let user_role = &mut ctx.accounts.user_role;
+        require!(user_role.status == RoleStatus::Suspended, PusdError::RoleNotSuspended);
+
+        let current_time = Clock::get()?.unix_timestamp;
+        user_role.status = if current_time < user_role.role_active_time {
+            RoleStatus::Pending
+        } else {
+            RoleStatus::Active
+        };
+
+        msg!("Resumed role {:?} for user: {} - {:?}", user_role.role, user_role.user, user_role.status);
+        Ok(())
+    }
+
+    /// Give up the caller's own role, e.g. on suspected key compromise
+    /// Any role holder can execute this function, whatever the role status
+    /// An Owner must pass another active Owner's role account so the last Owner cannot renounce
+    /// The role account will be closed and rent refunded per the configured recipient
+    pub fn renounce_role(ctx: Context<RenounceRole>) -> Result<()> {
+        let user_role = &ctx.accounts.user_role;
+
+        if user_role.role == Role::Owner {
+            let other_owner_role = ctx.accounts.other_owner_role.as_ref().ok_or(PusdError::LastOwner)?;
+            let current_time = Clock::get()?.unix_timestamp;
+            require!(
+                other_owner_role.user != user_role.user &&
+                    other_owner_role.has_active_role(Role::Owner, current_time),
+                PusdError::LastOwner
+            );
+        }
+
+        let rent_recipient = ctx.accounts.program_state.renounce_rent_recipient_for(user_role.user);
+        require_keys_eq!(
+            ctx.accounts.rent_recipient.key(),
+            rent_recipient,
+            PusdError::InvalidRentRecipient
+        );
+
+        msg!("User {} renouncing role {:?}", user_role.user, user_role.role);
+
+        emit!(RoleRenounced {
+            user: user_role.user,
+            role: user_role.role,
+            rent_recipient,
+        });
+
+        // The account will be closed automatically by Anchor's close constraint
+        Ok(())
+    }
+
     /// Query function to check if a user has a specific role
-    /// Returns true if the user has the specified role, false otherwise
+    /// Returns true if the user holds the specified role and it is active, false otherwise
+    /// Pending and suspended roles report false, matching require_role!
     /// Note: If the account doesn't exist, the transaction will fail during validation
     pub fn has_role(ctx: Context<HasRole>, _user: Pubkey, _role: Role) -> Result<bool> {
-        let has_role = ctx.accounts.user_role.role == _role;
+        let current_time = Clock::get()?.unix_timestamp;
+        let has_role = ctx.accounts.user_role.has_active_role(_role, current_time);
 
         msg!("Checking role {:?} for user {}: {}", _role, _user, has_role);
 
@@ -193,10 +409,12 @@ pub mod pusd_spl {
 
         // Call the internal mint function
         _mint(
+            &ctx.accounts.program_state,
             &ctx.accounts.mint,
             &ctx.accounts.recipient,
             &ctx.accounts.mint_authority,
             &ctx.accounts.token_program,
+            &ctx.accounts.reserve_attestation,
             ctx.bumps.mint_authority,
             amount
         )?;
@@ -209,11 +427,20 @@ pub mod pusd_spl {
     /// Only users with Operator role can call this function
     /// Operators have elevated permissions to manage token supply
     pub fn mint_by_operator(ctx: Context<MintByOperator>, amount: u64) -> Result<()> {
-
-        
         // Verify the caller has Operator role
         require_role!(ctx.accounts.operator_role, Role::Operator);
 
+        // Direct minting can be switched off in favour of the request/approve flow
+        require!(!ctx.accounts.program_state.direct_mint_disabled, PusdError::DirectMintDisabled);
+
+        // Large mints, alone or summed over the window, must go through request_mint and be approved
+        _record_operator_mint(
+            &ctx.accounts.program_state,
+            &mut ctx.accounts.mint_window,
+            ctx.accounts.operator.key(),
+            ctx.bumps.mint_window,
+            amount
+        )?;
 
         // Ensure recipient is not a zero address
         require!(
@@ -225,10 +452,12 @@ pub mod pusd_spl {
 
         // Call the internal mint function
         _mint(
+            &ctx.accounts.program_state,
             &ctx.accounts.mint,
             &ctx.accounts.recipient,
             &ctx.accounts.mint_authority,
             &ctx.accounts.token_program,
+            &ctx.accounts.reserve_attestation,
             ctx.bumps.mint_authority,
             amount
         )?;
@@ -236,198 +465,1498 @@ pub mod pusd_spl {
         msg!("Successfully minted {} tokens by operator", amount);
         Ok(())
     }
-}
 
-/// Private helper function to execute token minting via CPI
-/// This internal function handles the actual minting logic
-fn _mint<'info>(
-    mint: &AccountInfo<'info>,
-    recipient: &AccountInfo<'info>,
-    mint_authority: &AccountInfo<'info>,
-    token_program: &AccountInfo<'info>,
-    mint_authority_bump: u8,
-    amount: u64
-) -> Result<()> {
-    // Prepare PDA signer seeds for cross-program invocation
-    let seeds = &[b"mint_authority".as_ref(), &[mint_authority_bump]];
-    let signer_seeds = &[&seeds[..]];
+    /// Operator-controlled batch mint for payroll-style disbursement
+    /// Only users with Operator role can call this function
+    /// Recipient token accounts are passed via remaining_accounts in the same order as amounts
+    /// Every recipient must be a token account of the given mint
+    /// A full batch fits one legacy transaction; callers should raise its compute unit limit
+    pub fn batch_mint_by_operator<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchMintByOperator<'info>>,
+        amounts: Vec<u64>
+    ) -> Result<()> {
+        // Verify the caller has Operator role
+        require_role!(ctx.accounts.operator_role, Role::Operator);
 
-    // Execute mint via CPI to Token-2022 program using PDA authority
-    let cpi_accounts = MintTo {
-        mint: mint.to_account_info(),
-        to: recipient.to_account_info(),
-        authority: mint_authority.to_account_info(),
-    };
+        // Direct minting can be switched off in favour of the request/approve flow
+        require!(!ctx.accounts.program_state.direct_mint_disabled, PusdError::DirectMintDisabled);
 
-    let cpi_ctx = CpiContext::new_with_signer(
-        token_program.to_account_info(),
-        cpi_accounts,
-        signer_seeds
-    );
+        // Validate batch shape before doing any CPI
+        require!(!amounts.is_empty(), PusdError::EmptyBatch);
+        require!(amounts.len() <= MAX_BATCH_MINT_SIZE, PusdError::BatchTooLarge);
+        require!(
+            amounts.len() == ctx.remaining_accounts.len(),
+            PusdError::BatchLengthMismatch
+        );
 
-    mint_to(cpi_ctx, amount)?;
+        // Total must fit in u64 so the batch can never wrap the supply
+        let total = amounts
+            .iter()
+            .try_fold(0u64, |acc, amount| acc.checked_add(*amount))
+            .ok_or(PusdError::MathOverflow)?;
+
+        // Large mints, alone or summed over the window, must go through request_mint and be approved
+        _record_operator_mint(
+            &ctx.accounts.program_state,
+            &mut ctx.accounts.mint_window,
+            ctx.accounts.operator.key(),
+            ctx.bumps.mint_window,
+            total
+        )?;
 
-    Ok(())
-}
+        msg!("Batch minting {} tokens to {} recipients by operator", total, amounts.len());
+
+        for (recipient, amount) in ctx.remaining_accounts.iter().zip(amounts.iter()) {
+            _require_mint_token_account(recipient, &ctx.accounts.program_state.mint, &ctx.accounts.token_program)?;
+
+            _mint(
+                &ctx.accounts.program_state,
+                &ctx.accounts.mint,
+                recipient,
+                &ctx.accounts.mint_authority,
+                &ctx.accounts.token_program,
+                &ctx.accounts.reserve_attestation,
+                ctx.bumps.mint_authority,
+                *amount
+            )?;
+
+            emit!(TokensMinted {
+                minter: ctx.accounts.operator.key(),
+                recipient: recipient.key(),
+                amount: *amount,
+            });
+        }
 
-/// Private helper function to grant a role to a user
-/// This internal function handles the role assignment logic
-///
-/// Parameters:
-/// - user_role: The UserRole account to populate
-/// - user: The public key of the user receiving the role
-/// - role: The role being granted (Owner, Operator, or AuthorizedContract)
-/// - bump: The PDA bump seed
-fn _grant_role(user_role: &mut UserRole, user: Pubkey, role: Role, bump: u8) -> Result<()> {
-    user_role.user = user;
-    user_role.role = role;
-    user_role.bump = bump;
+        msg!("Successfully batch minted {} tokens by operator", total);
+        Ok(())
+    }
 
-    // Set activation time to current time + 24 hours
-    let current_time = Clock::get()?.unix_timestamp;
-    user_role.role_active_time = current_time + ROLE_ACTIVATION_DELAY;
+    /// Idempotent mint keyed by an off-chain reference
+    /// Only accounts with AuthorizedContract role can call this function
+    /// A MintReceipt PDA is created per (caller, reference); retrying with the same reference fails
+    /// reference_slot is the slot the reference was issued at; references older than
+    /// MINT_RECEIPT_MIN_AGE_SLOTS are refused, so a closed receipt's reference cannot mint again
+    pub fn mint_with_reference(
+        ctx: Context<MintWithReference>,
+        reference: [u8; 32],
+        reference_slot: u64,
+        amount: u64
+    ) -> Result<()> {
+        // Verify the caller has AuthorizedContract role
+        require_role!(ctx.accounts.contract_role, Role::AuthorizedContract);
 
-    msg!("Role {:?} granted to {} - activates at {}", role, user, user_role.role_active_time);
+        // Only fresh references mint; receipts become closable once references this old are refused
+        let current_slot = Clock::get()?.slot;
+        require!(
+            reference_slot <= current_slot
+                && current_slot - reference_slot < MINT_RECEIPT_MIN_AGE_SLOTS,
+            PusdError::ReferenceExpired
+        );
 
-    Ok(())
-}
+        // Ensure recipient is not a zero address
+        require!(
+            ctx.accounts.recipient.key() != Pubkey::default(),
+            PusdError::RecipientIsZeroAddress
+        );
 
-/// Returns the program version string
-pub fn get_version() -> String {
-    VERSION.to_string()
-}
+        // If the receipt already holds data, this reference was minted before
+        let receipt = &mut ctx.accounts.mint_receipt;
+        if receipt.minter != Pubkey::default() {
+            msg!(
+                "Reference already minted {} tokens at slot {}",
+                receipt.amount,
+                receipt.slot
+            );
+            return Err(PusdError::DuplicateMintReference.into());
+        }
 
-// ============================================================================
-// Account Validation Structs
-// ============================================================================
+        receipt.reference = reference;
+        receipt.minter = ctx.accounts.authorized_contract.key();
+        receipt.recipient = ctx.accounts.recipient.key();
+        receipt.amount = amount;
+        receipt.slot = current_slot;
+        receipt.bump = ctx.bumps.mint_receipt;
 
-#[derive(Accounts)]
-#[instruction(owner_address: Pubkey, operator_address: Pubkey)]
-pub struct Initialize<'info> {
-    /// Program state account to track initialization status
-    #[account(
-        init_if_needed,
-        payer = payer,
-        space = ProgramState::LEN,
-        seeds = [b"program_state"],
-        bump
-    )]
-    pub program_state: Account<'info, ProgramState>,
+        msg!(
+            "Minting {} tokens to {} with reference receipt {}",
+            amount,
+            ctx.accounts.recipient.key(),
+            receipt.key()
+        );
 
-    /// The owner role account with admin privileges
-    #[account(
-        init_if_needed,
-        payer = payer,
-        space = UserRole::LEN,
-        seeds = [b"user_role", owner_address.as_ref()],
-        bump
-    )]
-    pub owner_role: Account<'info, UserRole>,
+        // Call the internal mint function
+        _mint(
+            &ctx.accounts.program_state,
+            &ctx.accounts.mint,
+            &ctx.accounts.recipient,
+            &ctx.accounts.mint_authority,
+            &ctx.accounts.token_program,
+            &ctx.accounts.reserve_attestation,
+            ctx.bumps.mint_authority,
+            amount
+        )?;
 
-    /// The operator role account with minting privileges
-    #[account(
-        init_if_needed,
-        payer = payer,
-        space = UserRole::LEN,
-        seeds = [b"user_role", operator_address.as_ref()],
-        bump
-    )]
-    pub operator_role: Account<'info, UserRole>,
+        msg!("Successfully minted {} tokens", amount);
+        Ok(())
+    }
 
-    /// The program data account to verify upgrade authority
-    /// CHECK: This is the BPF Loader Upgradeable program data account
-    #[account(seeds = [crate::ID.as_ref()], bump, seeds::program = bpf_loader_upgradeable::ID)]
-    pub program_data: UncheckedAccount<'info>,
+    /// Administrative function to close an old mint receipt
+    /// Only the Owner can execute this function
+    /// The receipt must be older than MINT_RECEIPT_MIN_AGE_SLOTS, by which point mint_with_reference
+    /// refuses its reference; rent is refunded to the owner
+    pub fn close_mint_receipt(ctx: Context<CloseMintReceipt>) -> Result<()> {
+        // Verify the caller has Owner role
+        require_role!(ctx.accounts.owner_role, Role::Owner);
 
-    #[account(mut)]
-    pub payer: Signer<'info>,
+        let current_slot = Clock::get()?.slot;
+        require!(
+            current_slot.saturating_sub(ctx.accounts.mint_receipt.slot) >= MINT_RECEIPT_MIN_AGE_SLOTS,
+            PusdError::ReceiptTooRecent
+        );
 
-    pub system_program: Program<'info, System>,
-}
+        msg!(
+            "Closing mint receipt {} from slot {}",
+            ctx.accounts.mint_receipt.key(),
+            ctx.accounts.mint_receipt.slot
+        );
 
-#[derive(Accounts)]
-#[instruction(user: Pubkey, role: Role)]
-pub struct AddRole<'info> {
-    /// The owner who is adding the role
-    #[account(
-        seeds = [b"user_role", owner.key().as_ref()],
-        bump = owner_role.bump,
-        constraint = owner_role.role == Role::Owner @ PusdError::Unauthorized
-    )]
-    pub owner_role: Account<'info, UserRole>,
+        // The account will be closed automatically by Anchor's close constraint
+        Ok(())
+    }
 
-    #[account(mut)]
-    pub owner: Signer<'info>,
+    /// Propose a mint that must be confirmed by an Approver
+    /// Only users with Operator role can call this function
+    /// The request expires MINT_REQUEST_TTL seconds after creation
+    pub fn request_mint(ctx: Context<RequestMint>, request_id: u64, amount: u64) -> Result<()> {
+        require_not_sunset!(ctx.accounts.sunset_state);
 
-    /// The user role account being created or updated
-    #[account(
-        init_if_needed,
-        payer = owner,
-        space = UserRole::LEN,
-        seeds = [b"user_role", user.as_ref()],
-        bump
-    )]
-    pub user_role: Account<'info, UserRole>,
+        // Verify the caller has Operator role
+        require_role!(ctx.accounts.operator_role, Role::Operator);
 
-    pub system_program: Program<'info, System>,
-}
+        // Ensure recipient is not a zero address
+        require!(
+            ctx.accounts.recipient.key() != Pubkey::default(),
+            PusdError::RecipientIsZeroAddress
+        );
 
-#[derive(Accounts)]
-pub struct RemoveRole<'info> {
-    /// The owner who is removing the role
-    #[account(
-        seeds = [b"user_role", owner.key().as_ref()],
+        let current_time = Clock::get()?.unix_timestamp;
+        let request = &mut ctx.accounts.mint_request;
+        request.requester = ctx.accounts.operator.key();
+        request.mint = ctx.accounts.mint.key();
+        request.recipient = ctx.accounts.recipient.key();
+        request.amount = amount;
+        request.request_id = request_id;
+        request.expiry = current_time + MINT_REQUEST_TTL;
+        request.bump = ctx.bumps.mint_request;
+
+        msg!(
+            "Mint request {} created: {} tokens to {} - expires at {}",
+            request.key(),
+            amount,
+            request.recipient,
+            request.expiry
+        );
+        Ok(())
+    }
+
+    /// Approve a pending mint request and execute the mint
+    /// Only users with Approver role can call this function, and never for their own request
+    /// The request account is closed and rent refunded to the requester
+    pub fn approve_mint(ctx: Context<ApproveMint>) -> Result<()> {
+        // Verify the caller has Approver role
+        require_role!(ctx.accounts.approver_role, Role::Approver);
+
+        let request = &ctx.accounts.mint_request;
+        require!(
+            ctx.accounts.approver.key() != request.requester,
+            PusdError::SelfApproval
+        );
+        require!(
+            Clock::get()?.unix_timestamp <= request.expiry,
+            PusdError::MintRequestExpired
+        );
+        require!(
+            !ctx.accounts.program_state.requires_approval(request.amount),
+            PusdError::RequiresOwnerApproval
+        );
+
+        msg!(
+            "Approving mint request {}: {} tokens to {}",
+            request.key(),
+            request.amount,
+            request.recipient
+        );
+
+        // Call the internal mint function
+        _mint(
+            &ctx.accounts.program_state,
+            &ctx.accounts.mint,
+            &ctx.accounts.recipient,
+            &ctx.accounts.mint_authority,
+            &ctx.accounts.token_program,
+            &ctx.accounts.reserve_attestation,
+            ctx.bumps.mint_authority,
+            request.amount
+        )?;
+
+        msg!("Successfully minted {} tokens by approval", request.amount);
+        Ok(())
+    }
+
+    /// Approve a pending mint request above the large mint threshold and execute the mint
+    /// Only the Owner can execute this function; the Owner may itself be a multisig
+    /// The request account is closed and rent refunded to the requester
+    pub fn approve_large_mint(ctx: Context<ApproveLargeMint>) -> Result<()> {
+        // Verify the caller has Owner role
+        require_role!(ctx.accounts.owner_role, Role::Owner);
+
+        let request = &ctx.accounts.mint_request;
+        require!(
+            ctx.accounts.owner.key() != request.requester,
+            PusdError::SelfApproval
+        );
+        require!(
+            Clock::get()?.unix_timestamp <= request.expiry,
+            PusdError::MintRequestExpired
+        );
+
+        msg!(
+            "Owner approving mint request {}: {} tokens to {}",
+            request.key(),
+            request.amount,
+            request.recipient
+        );
+
+        // Call the internal mint function
+        _mint(
+            &ctx.accounts.program_state,
+            &ctx.accounts.mint,
+            &ctx.accounts.recipient,
+            &ctx.accounts.mint_authority,
+            &ctx.accounts.token_program,
+            &ctx.accounts.reserve_attestation,
+            ctx.bumps.mint_authority,
+            request.amount
+        )?;
+
+        msg!("Successfully minted {} tokens by owner approval", request.amount);
+        Ok(())
+    }
+
+    /// Reject a pending mint request
+    /// Only users with Approver role can call this function
+    /// The request account is closed and rent refunded to the requester
+    pub fn reject_mint(ctx: Context<RejectMint>) -> Result<()> {
+        require_not_sunset!(ctx.accounts.sunset_state);
+
+        // Verify the caller has Approver role
+        require_role!(ctx.accounts.approver_role, Role::Approver);
+
+        msg!(
+            "Rejecting mint request {} from {}",
+            ctx.accounts.mint_request.key(),
+            ctx.accounts.mint_request.requester
+        );
+        Ok(())
+    }
+
+    /// Close a mint request that has passed its expiry
+    /// Anyone can call this function; rent is refunded to the requester
+    pub fn close_expired_mint_request(ctx: Context<CloseExpiredMintRequest>) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp > ctx.accounts.mint_request.expiry,
+            PusdError::MintRequestNotExpired
+        );
+
+        msg!("Closing expired mint request {}", ctx.accounts.mint_request.key());
+        Ok(())
+    }
+
+    /// Administrative function to enable or disable direct operator minting
+    /// Only the Owner can execute this function
+    /// When disabled, operators can only mint through request_mint / approve_mint
+    pub fn set_direct_mint_enabled(ctx: Context<UpdateConfig>, enabled: bool) -> Result<()> {
+        // Verify the caller has Owner role
+        require_role!(ctx.accounts.owner_role, Role::Owner);
+
+        ctx.accounts.program_state.direct_mint_disabled = !enabled;
+
+        msg!("Direct operator minting enabled: {}", enabled);
+        Ok(())
+    }
+
+    /// Administrative function to set the large mint threshold
+    /// Only the Owner can execute this function
+    /// Direct operator mints that take the operator above the threshold within LARGE_MINT_WINDOW fail
+    /// and must be approved; 0 disables the check
+    pub fn set_large_mint_threshold(ctx: Context<UpdateConfig>, threshold: u64) -> Result<()> {
+        // Verify the caller has Owner role
+        require_role!(ctx.accounts.owner_role, Role::Owner);
+
+        ctx.accounts.program_state.large_mint_threshold = threshold;
+
+        msg!("Large mint threshold set to {}", threshold);
+        Ok(())
+    }
+
+    /// Set the account refunded when a role is renounced
+    /// Only the Owner can execute this function
+    /// Pubkey::default() refunds the role holder
+    pub fn set_renounce_rent_recipient(ctx: Context<UpdateConfig>, recipient: Pubkey) -> Result<()> {
+        // Verify the caller has Owner role
+        require_role!(ctx.accounts.owner_role, Role::Owner);
+
+        ctx.accounts.program_state.renounce_rent_recipient = recipient;
+
+        msg!("Renounce rent recipient set to {}", recipient);
+        Ok(())
+    }
+
+    /// Publish a proof-of-reserves attestation for the PUSD mint
+    /// Only users with Attestor role can call this function
+    /// reserve_amount is expressed in the mint's base units
+    pub fn attest_reserves(
+        ctx: Context<AttestReserves>,
+        reserve_amount: u64,
+        report_hash: [u8; 32],
+        report_uri: String
+    ) -> Result<()> {
+        // Verify the caller has Attestor role
+        require_role!(ctx.accounts.attestor_role, Role::Attestor);
+
+        require!(report_uri.len() <= MAX_REPORT_URI_LEN, PusdError::ReportUriTooLong);
+
+        let attestation = &mut ctx.accounts.reserve_attestation;
+
+        // First attestation for this mint starts with the default staleness window
+        if attestation.mint == Pubkey::default() {
+            attestation.mint = ctx.accounts.mint.key();
+            attestation.staleness_window = DEFAULT_RESERVE_STALENESS_WINDOW;
+            attestation.bump = ctx.bumps.reserve_attestation;
+        }
+
+        attestation.attestor = ctx.accounts.attestor.key();
+        attestation.reserve_amount = reserve_amount;
+        attestation.timestamp = Clock::get()?.unix_timestamp;
+        attestation.report_hash = report_hash;
+        attestation.report_uri = report_uri;
+
+        msg!(
+            "Reserves attested for mint {}: {} at {}",
+            attestation.mint,
+            reserve_amount,
+            attestation.timestamp
+        );
+        Ok(())
+    }
+
+    /// Administrative function to set how old a reserve attestation may be before minting is blocked
+    /// Only the Owner can execute this function
+    pub fn set_reserve_staleness_window(
+        ctx: Context<SetReserveStalenessWindow>,
+        staleness_window: i64
+    ) -> Result<()> {
+        require_not_sunset!(ctx.accounts.sunset_state);
+
+        // Verify the caller has Owner role
+        require_role!(ctx.accounts.owner_role, Role::Owner);
+
+        require!(staleness_window > 0, PusdError::InvalidConfig);
+
+        ctx.accounts.reserve_attestation.staleness_window = staleness_window;
+
+        msg!("Reserve staleness window set to {} seconds", staleness_window);
+        Ok(())
+    }
+
+    /// Register a collateral mint for the peg stability module
+    /// Only the Owner can execute this function
+    /// Creates the collateral config and a vault owned by the config PDA
+    pub fn add_collateral(
+        ctx: Context<AddCollateral>,
+        price: u64,
+        debt_ceiling: u64
+    ) -> Result<()> {
+        psm::add_collateral(ctx, price, debt_ceiling)
+    }
+
+    /// Update a peg stability module collateral
+    /// Only the Owner can execute this function
+    pub fn update_collateral(
+        ctx: Context<UpdateCollateral>,
+        price: u64,
+        debt_ceiling: u64,
+        enabled: bool
+    ) -> Result<()> {
+        psm::update_collateral(ctx, price, debt_ceiling, enabled)
+    }
+
+    /// Configure where a collateral's price is read from
+    /// Only the Owner can execute this function
+    /// oracle must be the PriceFeed or Pyth account for Feed/Pyth sources
+    pub fn set_collateral_price_source(
+        ctx: Context<UpdateCollateral>,
+        price_source: PriceSourceKind,
+        oracle: Pubkey,
+        max_price_age: i64,
+        max_confidence_bps: u16
+    ) -> Result<()> {
+        psm::set_collateral_price_source(ctx, price_source, oracle, max_price_age, max_confidence_bps)
+    }
+
+    /// Push a new price to a collateral's PriceFeed account
+    /// Only the Owner can execute this function
+    pub fn update_price_feed(ctx: Context<UpdatePriceFeed>, price: u64, confidence: u64) -> Result<()> {
+        psm::update_price_feed(ctx, price, confidence)
+    }
+
+    /// Swap collateral for PUSD at the configured price minus the swap-in fee
+    /// Anyone can call this function, subject to the collateral debt ceiling
+    pub fn swap_in(ctx: Context<SwapIn>, amount: u64) -> Result<()> {
+        psm::swap_in(ctx, amount)
+    }
+
+    /// Swap PUSD for collateral at the configured price minus the swap-out fee
+    /// Anyone can call this function; the PUSD is burned
+    pub fn swap_out(ctx: Context<SwapOut>, amount: u64) -> Result<()> {
+        psm::swap_out(ctx, amount)
+    }
+
+    /// Administrative function to set redemption and PSM swap fees
+    /// Only the Owner can execute this function
+    /// Each fee is bounded by MAX_FEE_BPS
+    pub fn set_fees(
+        ctx: Context<UpdateConfig>,
+        psm_fee_in_bps: u16,
+        psm_fee_out_bps: u16,
+        redemption_fee_bps: u16
+    ) -> Result<()> {
+        // Verify the caller has Owner role
+        require_role!(ctx.accounts.owner_role, Role::Owner);
+
+        require!(
+            psm_fee_in_bps <= MAX_FEE_BPS
+                && psm_fee_out_bps <= MAX_FEE_BPS
+                && redemption_fee_bps <= MAX_FEE_BPS,
+            PusdError::FeeTooHigh
+        );
+
+        let program_state = &mut ctx.accounts.program_state;
+        program_state.psm_fee_in_bps = psm_fee_in_bps;
+        program_state.psm_fee_out_bps = psm_fee_out_bps;
+        program_state.redemption_fee_bps = redemption_fee_bps;
+
+        msg!(
+            "Fees set - PSM in {} bps, PSM out {} bps, redemption {} bps",
+            psm_fee_in_bps,
+            psm_fee_out_bps,
+            redemption_fee_bps
+        );
+        Ok(())
+    }
+
+    /// Burn PUSD for off-chain redemption, paying the redemption fee to the treasury
+    /// Any holder can call this function
+    pub fn redeem(ctx: Context<Redeem>, amount: u64) -> Result<()> {
+        treasury::redeem(ctx, amount)
+    }
+
+    /// Withdraw collected fees from the treasury
+    /// Only the Owner can execute this function
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+        treasury::withdraw_fees(ctx, amount)
+    }
+
+    /// Make the program PDA the transfer-fee config and withdraw-withheld authority
+    /// Only the Owner can execute this function, co-signed by the current authority
+    pub fn transfer_fee_authorities_to_pda(ctx: Context<TransferFeeAuthoritiesToPda>) -> Result<()> {
+        transfer_fee::transfer_fee_authorities_to_pda(ctx)
+    }
+
+    /// Update the Token-2022 transfer fee
+    /// Only the Owner can execute this function; bounded by MAX_FEE_BPS
+    pub fn set_transfer_fee(
+        ctx: Context<ManageTransferFee>,
+        transfer_fee_basis_points: u16,
+        maximum_fee: u64
+    ) -> Result<()> {
+        transfer_fee::set_transfer_fee(ctx, transfer_fee_basis_points, maximum_fee)
+    }
+
+    /// Harvest withheld transfer fees from token accounts into the mint
+    /// Only the Owner can execute this function
+    /// Token accounts are passed via remaining_accounts
+    pub fn harvest_withheld_to_mint<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ManageTransferFee<'info>>
+    ) -> Result<()> {
+        transfer_fee::harvest_withheld_to_mint(ctx)
+    }
+
+    /// Withdraw transfer fees withheld in the mint to the treasury
+    /// Only the Owner can execute this function
+    pub fn withdraw_withheld_to_treasury(ctx: Context<WithdrawWithheldToTreasury>) -> Result<()> {
+        transfer_fee::withdraw_withheld_to_treasury(ctx)
+    }
+
+    /// Create an interest-bearing sibling mint (e.g. sPUSD) and the PUSD reserve backing it
+    /// Only the Owner can execute this function
+    /// The rate authority is the "rate_authority" PDA and the mint authority is the "yield_mint_authority" PDA
+    pub fn initialize_yield_mint(
+        ctx: Context<InitializeYieldMint>,
+        initial_rate: i16,
+        bounds: RateBounds
+    ) -> Result<()> {
+        yield_token::initialize_yield_mint(ctx, initial_rate, bounds)
+    }
+
+    /// Administrative function to change the rate bounds of an interest-bearing mint
+    /// Only the Owner can execute this function
+    pub fn set_rate_bounds(ctx: Context<SetRateBounds>, bounds: RateBounds) -> Result<()> {
+        yield_token::set_rate_bounds(ctx, bounds)
+    }
+
+    /// Update the interest rate of an interest-bearing mint
+    /// Only users with RateManager role can call this function, within the configured bounds
+    pub fn update_rate(ctx: Context<UpdateRate>, rate: i16) -> Result<()> {
+        yield_token::update_rate(ctx, rate)
+    }
+
+    /// Deposit PUSD into the reserve of an interest-bearing mint and receive its yield token
+    pub fn deposit_yield_token(ctx: Context<YieldTokenUser>, assets: u64) -> Result<()> {
+        yield_token::deposit_yield_token(ctx, assets)
+    }
+
+    /// Burn yield tokens for the PUSD their accrued UI amount shows
+    pub fn redeem_yield_token(ctx: Context<YieldTokenUser>, amount: u64) -> Result<()> {
+        yield_token::redeem_yield_token(ctx, amount)
+    }
+
+    /// Create the PUSD staking vault and its share mint
+    /// Only the Owner can execute this function
+    pub fn initialize_staking_vault(ctx: Context<InitializeStakingVault>) -> Result<()> {
+        vault::initialize_staking_vault(ctx)
+    }
+
+    /// Stake PUSD into the staking vault in exchange for shares
+    /// Anyone can call this function; shares are issued for the amount the vault receives
+    pub fn stake(ctx: Context<StakingVaultUser>, assets: u64) -> Result<()> {
+        vault::stake(ctx, assets)
+    }
+
+    /// Burn staking vault shares and unstake PUSD
+    /// Anyone holding shares can call this function
+    pub fn unstake(ctx: Context<StakingVaultUser>, shares: u64) -> Result<()> {
+        vault::unstake(ctx, shares)
+    }
+
+    /// Mint PUSD yield into the staking vault
+    /// Only users with Operator role can call this function
+    pub fn distribute_yield(ctx: Context<DistributeYield>, amount: u64) -> Result<()> {
+        vault::distribute_yield(ctx, amount)
+    }
+
+    /// Transfer the confidential transfer mint authority to the program PDA
+    /// Only the Owner can execute this function
+    pub fn confidential_authority_to_pda(ctx: Context<ConfidentialAuthorityToPda>) -> Result<()> {
+        confidential::confidential_authority_to_pda(ctx)
+    }
+
+    /// Set the confidential transfer auditor key and disable auto-approval
+    /// Only the Owner can execute this function
+    pub fn configure_confidential_transfers(
+        ctx: Context<ConfigureConfidentialTransfers>,
+        auditor_elgamal_pubkey: Option<[u8; 32]>
+    ) -> Result<()> {
+        confidential::configure_confidential_transfers(ctx, auditor_elgamal_pubkey)
+    }
+
+    /// Approve a token account for confidential transfers
+    /// Only users with Compliance role can call this function
+    pub fn approve_confidential_account(ctx: Context<ApproveConfidentialAccount>) -> Result<()> {
+        confidential::approve_confidential_account(ctx)
+    }
+
+    /// Record a KYC approval and thaw the holder's token account
+    /// Only users with KycOfficer role can call this function
+    pub fn kyc_approve(ctx: Context<KycApprove>) -> Result<()> {
+        kyc::kyc_approve(ctx)
+    }
+
+    /// Revoke a KYC approval and re-freeze the holder's token account
+    /// Only users with KycOfficer role can call this function
+    pub fn kyc_revoke(ctx: Context<KycRevoke>) -> Result<()> {
+        kyc::kyc_revoke(ctx)
+    }
+
+    /// Make the program PDA the mint's freeze authority and default new token accounts to frozen
+    /// Only the Owner can execute this function in KYC allowlist mode, co-signed by the current freeze authority
+    pub fn freeze_authority_to_pda(ctx: Context<FreezeAuthorityToPda>) -> Result<()> {
+        kyc::freeze_authority_to_pda(ctx)
+    }
+
+    /// Set the bridge chain ID and guardian set
+    /// Only the Owner can execute this function
+    pub fn set_bridge_config(
+        ctx: Context<SetBridgeConfig>,
+        local_chain_id: u16,
+        guardians: Vec<Pubkey>,
+        threshold: u8
+    ) -> Result<()> {
+        bridge::set_bridge_config(ctx, local_chain_id, guardians, threshold)
+    }
+
+    /// Burn PUSD to be minted on another chain
+    /// Anyone holding PUSD can call this function
+    pub fn burn_for_bridge(
+        ctx: Context<BurnForBridge>,
+        destination_chain: u16,
+        recipient: [u8; 32],
+        amount: u64
+    ) -> Result<()> {
+        bridge::burn_for_bridge(ctx, destination_chain, recipient, amount)
+    }
+
+    /// Mint PUSD for a guardian-attested message from another chain
+    /// Anyone can relay a message; guardian ed25519 signatures must precede this instruction
+    pub fn receive_bridge_mint(
+        ctx: Context<ReceiveBridgeMint>,
+        source_chain: u16,
+        nonce: u64,
+        amount: u64
+    ) -> Result<()> {
+        bridge::receive_bridge_mint(ctx, source_chain, nonce, amount)
+    }
+
+    /// Mint tokens authorized by an ed25519 permit from an AuthorizedSigner
+    /// Anyone can relay a permit; the signature instruction must precede this instruction
+    pub fn mint_with_permit(
+        ctx: Context<MintWithPermit>,
+        amount: u64,
+        nonce: u64,
+        expiry: i64
+    ) -> Result<()> {
+        permit::mint_with_permit(ctx, amount, nonce, expiry)
+    }
+
+    /// Grant the AuthorizedContract role to the PDA of a program ID and seeds
+    /// Only the Owner can execute this function
+    pub fn register_authorized_program(
+        ctx: Context<RegisterAuthorizedProgram>,
+        program_id: Pubkey,
+        seeds: Vec<Vec<u8>>
+    ) -> Result<()> {
+        contract::register_authorized_program(ctx, program_id, seeds)
+    }
+
+    /// Mint tokens from a registered program via CPI
+    /// Only registered program authorities with AuthorizedContract role can call this function
+    pub fn mint_by_program(ctx: Context<MintByProgram>, amount: u64) -> Result<()> {
+        contract::mint_by_program(ctx, amount)
+    }
+
+    /// Upgrade a UserRole or ProgramState account to the current layout
+    /// Anyone can call this function; the payer tops up rent for the larger account
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        migrate::migrate_account(ctx)
+    }
+
+    /// Schedule an irreversible sunset after the timelock
+    /// Only the Owner can execute this function
+    pub fn schedule_sunset(ctx: Context<ScheduleSunset>) -> Result<()> {
+        sunset::schedule_sunset(ctx)
+    }
+
+    /// Cancel a scheduled sunset before it is executed
+    /// Only the Owner can execute this function
+    pub fn cancel_sunset(ctx: Context<CancelSunset>) -> Result<()> {
+        sunset::cancel_sunset(ctx)
+    }
+
+    /// Permanently disable minting and reclaim ProgramState rent
+    /// Only the Owner can execute this function, after the timelock and with an empty treasury
+    pub fn execute_sunset(ctx: Context<ExecuteSunset>) -> Result<()> {
+        sunset::execute_sunset(ctx)
+    }
+
+    /// Close leftover role accounts after sunset, refunding rent to the Owner
+    /// Anyone can call this function
+    pub fn close_roles_after_sunset<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseRolesAfterSunset<'info>>
+    ) -> Result<()> {
+        sunset::close_roles_after_sunset(ctx)
+    }
+
+    /// Burn PUSD for off-chain redemption after sunset (no fee)
+    /// Anyone holding PUSD can call this function
+    pub fn redeem_after_sunset(ctx: Context<RedeemAfterSunset>, amount: u64) -> Result<()> {
+        sunset::redeem_after_sunset(ctx, amount)
+    }
+
+    /// Burn PUSD for its pro-rata share of a PSM collateral vault after sunset (no fee)
+    /// Anyone holding PUSD can call this function
+    pub fn swap_out_after_sunset(ctx: Context<SwapOutAfterSunset>, amount: u64) -> Result<()> {
+        sunset::swap_out_after_sunset(ctx, amount)
+    }
+
+    /// Pause all minting immediately
+    /// Only the Guardian can execute this function
+    pub fn pause(ctx: Context<GuardianAction>) -> Result<()> {
+        guardian::pause(ctx)
+    }
+
+    /// Resume minting after a Guardian pause
+    /// Only the Owner can execute this function
+    pub fn unpause(ctx: Context<UpdateConfig>) -> Result<()> {
+        guardian::unpause(ctx)
+    }
+
+    /// Close a minter's role immediately, refunding rent to an Owner
+    /// Only the Guardian can execute this function
+    pub fn guardian_revoke_minter(ctx: Context<GuardianRevokeMinter>) -> Result<()> {
+        guardian::guardian_revoke_minter(ctx)
+    }
+
+    /// Mint tokens to a wallet using an authorized contract
+    /// Only accounts with AuthorizedContract role can call this function
+    /// The wallet's Token-2022 associated token account is created if missing (paid by the caller)
+    pub fn mint_to_wallet(ctx: Context<MintToWalletByContract>, amount: u64) -> Result<()> {
+        // Verify the caller has AuthorizedContract role
+        require_role!(ctx.accounts.contract_role, Role::AuthorizedContract);
+
+        // Ensure wallet is not a zero address
+        require!(
+            ctx.accounts.wallet.key() != Pubkey::default(),
+            PusdError::RecipientIsZeroAddress
+        );
+
+        msg!(
+            "Minting {} tokens to wallet {} (ATA {}) by authorized contract",
+            amount,
+            ctx.accounts.wallet.key(),
+            ctx.accounts.recipient.key()
+        );
+
+        // Call the internal mint function
+        _mint(
+            &ctx.accounts.program_state,
+            &ctx.accounts.mint,
+            &ctx.accounts.recipient.to_account_info(),
+            &ctx.accounts.mint_authority,
+            &ctx.accounts.token_program,
+            &ctx.accounts.reserve_attestation,
+            ctx.bumps.mint_authority,
+            amount
+        )?;
+
+        msg!("Successfully minted {} tokens", amount);
+        Ok(())
+    }
+
+    /// Operator-controlled mint to a wallet
+    /// Only users with Operator role can call this function
+    /// The wallet's Token-2022 associated token account is created if missing (paid by the operator)
+    pub fn mint_to_wallet_by_operator(ctx: Context<MintToWalletByOperator>, amount: u64) -> Result<()> {
+        // Verify the caller has Operator role
+        require_role!(ctx.accounts.operator_role, Role::Operator);
+
+        // Direct minting can be switched off in favour of the request/approve flow
+        require!(!ctx.accounts.program_state.direct_mint_disabled, PusdError::DirectMintDisabled);
+
+        // Large mints, alone or summed over the window, must go through request_mint and be approved
+        _record_operator_mint(
+            &ctx.accounts.program_state,
+            &mut ctx.accounts.mint_window,
+            ctx.accounts.operator.key(),
+            ctx.bumps.mint_window,
+            amount
+        )?;
+
+        // Ensure wallet is not a zero address
+        require!(
+            ctx.accounts.wallet.key() != Pubkey::default(),
+            PusdError::RecipientIsZeroAddress
+        );
+
+        msg!(
+            "Minting {} tokens to wallet {} (ATA {}) by operator",
+            amount,
+            ctx.accounts.wallet.key(),
+            ctx.accounts.recipient.key()
+        );
+
+        // Call the internal mint function
+        _mint(
+            &ctx.accounts.program_state,
+            &ctx.accounts.mint,
+            &ctx.accounts.recipient.to_account_info(),
+            &ctx.accounts.mint_authority,
+            &ctx.accounts.token_program,
+            &ctx.accounts.reserve_attestation,
+            ctx.bumps.mint_authority,
+            amount
+        )?;
+
+        msg!("Successfully minted {} tokens by operator", amount);
+        Ok(())
+    }
+}
+
+/// Private helper function to execute fiat-backed token minting via CPI
+/// This internal function handles the actual minting logic
+/// Minting is refused if the attestation is stale or the new supply would exceed attested reserves
+#[allow(clippy::too_many_arguments)]
+fn _mint<'info>(
+    program_state: &ProgramState,
+    mint: &AccountInfo<'info>,
+    recipient: &AccountInfo<'info>,
+    mint_authority: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    reserve_attestation: &ReserveAttestation,
+    mint_authority_bump: u8,
+    amount: u64
+) -> Result<()> {
+    _require_reserves(mint, reserve_attestation, amount)?;
+    _mint_unattested(program_state, mint, recipient, mint_authority, token_program, mint_authority_bump, amount)
+}
+
+/// Private helper function to mint supply backed on-chain (PSM collateral, bridged burns)
+/// The reserve attestation only covers fiat-backed issuance, so it is not checked here;
+/// the minted supply still counts towards the supply later fiat mints are checked against
+/// Minting is refused while paused and for any mint other than the recorded PUSD mint
+fn _mint_unattested<'info>(
+    program_state: &ProgramState,
+    mint: &AccountInfo<'info>,
+    recipient: &AccountInfo<'info>,
+    mint_authority: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    mint_authority_bump: u8,
+    amount: u64
+) -> Result<()> {
+    require!(!program_state.paused, PusdError::ProgramPaused);
+    require_keys_eq!(mint.key(), program_state.mint, PusdError::InvalidMint);
+
+    // Prepare PDA signer seeds for cross-program invocation
+    let seeds = &[b"mint_authority".as_ref(), &[mint_authority_bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    // Execute mint via CPI to Token-2022 program using PDA authority
+    let cpi_accounts = MintTo {
+        mint: mint.to_account_info(),
+        to: recipient.to_account_info(),
+        authority: mint_authority.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds
+    );
+
+    mint_to(cpi_ctx, amount)?;
+
+    Ok(())
+}
+
+/// Private helper function to count a direct operator mint towards the large mint threshold
+/// Splitting a large mint into smaller calls within LARGE_MINT_WINDOW still requires approval
+fn _record_operator_mint(
+    program_state: &ProgramState,
+    mint_window: &mut OperatorMintWindow,
+    operator: Pubkey,
+    bump: u8,
+    amount: u64
+) -> Result<()> {
+    mint_window.operator = operator;
+    mint_window.bump = bump;
+    let minted = mint_window.record(amount, Clock::get()?.unix_timestamp)?;
+    require!(!program_state.requires_approval(minted), PusdError::RequiresApproval);
+    Ok(())
+}
+
+/// Private helper function to check proof-of-reserves before minting
+/// Reads the current supply from the mint so batch mints see each preceding CPI
+fn _require_reserves(
+    mint: &AccountInfo,
+    reserve_attestation: &ReserveAttestation,
+    amount: u64
+) -> Result<()> {
+    require!(
+        reserve_attestation.mint == mint.key(),
+        PusdError::InvalidReserveAttestation
+    );
+
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        current_time.saturating_sub(reserve_attestation.timestamp) <= reserve_attestation.staleness_window,
+        PusdError::ReserveAttestationStale
+    );
+
+    let supply = {
+        let data = mint.try_borrow_data()?;
+        Mint::try_deserialize(&mut &data[..])?.supply
+    };
+    let new_supply = supply.checked_add(amount).ok_or(PusdError::MathOverflow)?;
+    require!(
+        new_supply <= reserve_attestation.reserve_amount,
+        PusdError::InsufficientReserves
+    );
+
+    Ok(())
+}
+
+/// Private helper function to validate a token account passed outside the Accounts struct
+/// Ensures the account is owned by the token program and holds the canonical mint
+fn _require_mint_token_account<'info>(
+    account: &AccountInfo<'info>,
+    canonical_mint: &Pubkey,
+    token_program: &AccountInfo<'info>
+) -> Result<()> {
+    require!(
+        account.owner == token_program.key && account.is_writable,
+        PusdError::InvalidRecipientAccount
+    );
+
+    let data = account.try_borrow_data()?;
+    let token_account = TokenAccount::try_deserialize(&mut &data[..])
+        .map_err(|_| PusdError::InvalidRecipientAccount)?;
+    require!(token_account.mint == *canonical_mint, PusdError::InvalidRecipientAccount);
+
+    Ok(())
+}
+
+/// Authorizes post-initialization privileged actions
+/// The governance admin always passes; the upgrade authority only until renounced
+fn _require_governance(
+    program_state: &ProgramState,
+    program_data: &ProgramData,
+    signer: Pubkey
+) -> Result<()> {
+    if program_state.admin != Pubkey::default() && signer == program_state.admin {
+        return Ok(());
+    }
+    require!(!program_state.upgrade_authority_renounced, PusdError::OnlyAdmin);
+    upgrade::check_upgrade_authority(program_data.upgrade_authority_address, signer)
+}
+
+/// Private helper function to grant a role to a user
+/// This internal function handles the role assignment logic
+///
+/// Parameters:
+/// - user_role: The UserRole account to populate
+/// - user: The public key of the user receiving the role
+/// - role: The role being granted (Owner, Operator, or AuthorizedContract)
+/// - bump: The PDA bump seed
+fn _grant_role(user_role: &mut UserRole, user: Pubkey, role: Role, bump: u8) -> Result<()> {
+    user_role.version = UserRole::VERSION;
+    user_role.user = user;
+    user_role.role = role;
+    user_role.bump = bump;
+
+    // Set activation time to current time + 24 hours
+    let current_time = Clock::get()?.unix_timestamp;
+    user_role.role_active_time = current_time + ROLE_ACTIVATION_DELAY;
+    user_role.status = RoleStatus::Pending;
+
+    msg!("Role {:?} granted to {} - activates at {}", role, user, user_role.role_active_time);
+
+    Ok(())
+}
+
+/// Returns the program version string
+pub fn get_version() -> String {
+    VERSION.to_string()
+}
+
+// ============================================================================
+// Account Validation Structs
+// ============================================================================
+
+#[derive(Accounts)]
+#[instruction(owner_address: Pubkey, operator_address: Pubkey, guardian_address: Pubkey)]
+pub struct Initialize<'info> {
+    /// Program state account to track initialization status
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = ProgramState::LEN,
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// The owner role account with admin privileges
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = UserRole::LEN,
+        seeds = [b"user_role", owner_address.as_ref()],
+        bump
+    )]
+    pub owner_role: Account<'info, UserRole>,
+
+    /// The operator role account with minting privileges
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = UserRole::LEN,
+        seeds = [b"user_role", operator_address.as_ref()],
+        bump
+    )]
+    pub operator_role: Account<'info, UserRole>,
+
+    /// The guardian role account with emergency pause and revoke powers
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = UserRole::LEN,
+        seeds = [b"user_role", guardian_address.as_ref()],
+        bump
+    )]
+    pub guardian_role: Account<'info, UserRole>,
+
+    /// Sunset tombstone; must not exist
+    /// CHECK: PDA derived from "sunset" seed, only checked for emptiness
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+
+    /// The program data account to verify upgrade authority
+    #[account(seeds = [crate::ID.as_ref()], bump, seeds::program = bpf_loader_upgradeable::ID)]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey, role: Role)]
+pub struct AddRole<'info> {
+    /// The owner who is adding the role
+    #[account(
+        seeds = [b"user_role", owner.key().as_ref()],
+        bump = owner_role.bump,
+        constraint = owner_role.role == Role::Owner @ PusdError::Unauthorized
+    )]
+    pub owner_role: Account<'info, UserRole>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The user role account being created or updated
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = UserRole::LEN,
+        seeds = [b"user_role", user.as_ref()],
+        bump
+    )]
+    pub user_role: Account<'info, UserRole>,
+
+    /// Sunset tombstone; rejects the instruction once executed
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveRole<'info> {
+    /// The owner who is removing the role
+    #[account(
+        seeds = [b"user_role", owner.key().as_ref()],
+        bump = owner_role.bump,
+        constraint = owner_role.role == Role::Owner @ PusdError::Unauthorized
+    )]
+    pub owner_role: Account<'info, UserRole>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The user role account being closed
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"user_role", user_role.user.as_ref()],
+        bump = user_role.bump
+    )]
+    pub user_role: Account<'info, UserRole>,
+
+    /// Sunset tombstone; rejects the instruction once executed
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RenounceRole<'info> {
+    /// The role holder giving up its role
+    pub holder: Signer<'info>,
+
+    /// The holder's role account being closed
+    #[account(
+        mut,
+        close = rent_recipient,
+        seeds = [b"user_role", holder.key().as_ref()],
+        bump = user_role.bump
+    )]
+    pub user_role: Account<'info, UserRole>,
+
+    /// Program state holding the rent recipient configuration
+    #[account(seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Receives the role account rent
+    /// CHECK: Must match program_state.renounce_rent_recipient_for(holder)
+    #[account(mut)]
+    pub rent_recipient: AccountInfo<'info>,
+
+    /// Another active Owner, required when an Owner renounces
+    #[account(
+        seeds = [b"user_role", other_owner_role.user.as_ref()],
+        bump = other_owner_role.bump
+    )]
+    pub other_owner_role: Option<Account<'info, UserRole>>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRoleStatus<'info> {
+    /// The owner changing the role status
+    #[account(
+        seeds = [b"user_role", owner.key().as_ref()],
         bump = owner_role.bump,
         constraint = owner_role.role == Role::Owner @ PusdError::Unauthorized
     )]
-    pub owner_role: Account<'info, UserRole>,
+    pub owner_role: Account<'info, UserRole>,
+
+    pub owner: Signer<'info>,
+
+    /// The user role account being suspended or resumed
+    #[account(
+        mut,
+        seeds = [b"user_role", user_role.user.as_ref()],
+        bump = user_role.bump
+    )]
+    pub user_role: Account<'info, UserRole>,
+
+    /// Sunset tombstone; rejects the instruction once executed
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey, role: Role)]
+pub struct HasRole<'info> {
+    /// The user role account to verify
+    /// Transaction fails if this account doesn't exist (meaning no role assigned)
+    #[account(seeds = [b"user_role", user.as_ref()], bump = user_role.bump)]
+    pub user_role: Account<'info, UserRole>,
+}
+
+#[derive(Accounts)]
+pub struct TransferAuthority<'info> {
+    /// Program state holding the governance admin; records the canonical mint
+    #[account(mut, seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// The Token-2022 mint that becomes the canonical PUSD mint
+    #[account(mut, mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The current mint authority who will transfer control
+    pub current_authority: Signer<'info>,
+
+    /// The program data account to verify upgrade authority
+    #[account(seeds = [crate::ID.as_ref()], bump, seeds::program = bpf_loader_upgradeable::ID)]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct MintByContract<'info> {
+    /// The authorized contract's role account
+    #[account(
+        seeds = [b"user_role", authorized_contract.key().as_ref()],
+        bump = contract_role.bump,
+        constraint = contract_role.role == Role::AuthorizedContract @ PusdError::Unauthorized
+    )]
+    pub contract_role: Account<'info, UserRole>,
+
+    pub authorized_contract: Signer<'info>,
+
+    /// The Token-2022 mint account
+    /// CHECK: Validated by Token-2022 program
+    #[account(mut)]
+    pub mint: AccountInfo<'info>,
+
+    /// Program state holding the pause flag
+    #[account(seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Latest proof-of-reserves attestation for the mint
+    #[account(
+        seeds = [b"reserve_attestation", mint.key().as_ref()],
+        bump = reserve_attestation.bump
+    )]
+    pub reserve_attestation: Account<'info, ReserveAttestation>,
+
+    /// The recipient's token account
+    /// CHECK: Validated by Token-2022 program
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+
+    /// The mint authority PDA controlled by this program
+    /// CHECK: PDA derived from "mint_authority" seed
+    #[account(seeds = [b"mint_authority"], bump)]
+    pub mint_authority: AccountInfo<'info>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct MintByOperator<'info> {
+    /// The operator's role account
+    #[account(
+        seeds = [b"user_role", operator.key().as_ref()],
+        bump = operator_role.bump,
+        constraint = operator_role.role == Role::Operator @ PusdError::Unauthorized
+    )]
+    pub operator_role: Account<'info, UserRole>,
+
+    /// Program state holding mint configuration
+    #[account(seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Pays for the mint window account on first use
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    /// Running total of this operator's direct mints in the current window
+    #[account(
+        init_if_needed,
+        payer = operator,
+        space = OperatorMintWindow::LEN,
+        seeds = [b"operator_mint_window", operator.key().as_ref()],
+        bump
+    )]
+    pub mint_window: Account<'info, OperatorMintWindow>,
+
+    /// The Token-2022 mint account
+    /// CHECK: Validated by Token-2022 program
+    #[account(mut)]
+    pub mint: AccountInfo<'info>,
+
+    /// Latest proof-of-reserves attestation for the mint
+    #[account(
+        seeds = [b"reserve_attestation", mint.key().as_ref()],
+        bump = reserve_attestation.bump
+    )]
+    pub reserve_attestation: Account<'info, ReserveAttestation>,
+
+    /// The recipient's token account associated with the mint
+    /// CHECK: Validated by Token-2022 program
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+
+    /// The mint authority PDA controlled by this program
+    /// CHECK: PDA derived from "mint_authority" seed
+    #[account(seeds = [b"mint_authority"], bump)]
+    pub mint_authority: AccountInfo<'info>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+}
+
+
+#[derive(Accounts)]
+pub struct MintToWalletByContract<'info> {
+    /// The authorized contract's role account
+    #[account(
+        seeds = [b"user_role", authorized_contract.key().as_ref()],
+        bump = contract_role.bump,
+        constraint = contract_role.role == Role::AuthorizedContract @ PusdError::Unauthorized
+    )]
+    pub contract_role: Account<'info, UserRole>,
+
+    /// Pays for the associated token account if it has to be created
+    #[account(mut)]
+    pub authorized_contract: Signer<'info>,
+
+    /// The Token-2022 mint account
+    /// CHECK: Validated by Token-2022 program
+    #[account(mut)]
+    pub mint: AccountInfo<'info>,
+
+    /// Program state holding the pause flag
+    #[account(seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Latest proof-of-reserves attestation for the mint
+    #[account(
+        seeds = [b"reserve_attestation", mint.key().as_ref()],
+        bump = reserve_attestation.bump
+    )]
+    pub reserve_attestation: Account<'info, ReserveAttestation>,
+
+    /// The wallet receiving the tokens
+    /// CHECK: Any wallet may receive tokens; only used to derive the associated token account
+    pub wallet: UncheckedAccount<'info>,
+
+    /// The wallet's associated token account, created if missing
+    #[account(
+        init_if_needed,
+        payer = authorized_contract,
+        associated_token::mint = mint,
+        associated_token::authority = wallet,
+        associated_token::token_program = token_program
+    )]
+    pub recipient: InterfaceAccount<'info, TokenAccount>,
+
+    /// The mint authority PDA controlled by this program
+    /// CHECK: PDA derived from "mint_authority" seed
+    #[account(seeds = [b"mint_authority"], bump)]
+    pub mint_authority: AccountInfo<'info>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MintToWalletByOperator<'info> {
+    /// The operator's role account
+    #[account(
+        seeds = [b"user_role", operator.key().as_ref()],
+        bump = operator_role.bump,
+        constraint = operator_role.role == Role::Operator @ PusdError::Unauthorized
+    )]
+    pub operator_role: Account<'info, UserRole>,
+
+    /// Program state holding mint configuration
+    #[account(seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Pays for the mint window and associated token account if they have to be created
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    /// Running total of this operator's direct mints in the current window
+    #[account(
+        init_if_needed,
+        payer = operator,
+        space = OperatorMintWindow::LEN,
+        seeds = [b"operator_mint_window", operator.key().as_ref()],
+        bump
+    )]
+    pub mint_window: Account<'info, OperatorMintWindow>,
 
+    /// The Token-2022 mint account
+    /// CHECK: Validated by Token-2022 program
     #[account(mut)]
-    pub owner: Signer<'info>,
+    pub mint: AccountInfo<'info>,
 
-    /// The user role account being closed
+    /// Latest proof-of-reserves attestation for the mint
     #[account(
-        mut,
-        close = owner,
-        seeds = [b"user_role", user_role.user.as_ref()],
-        bump = user_role.bump
+        seeds = [b"reserve_attestation", mint.key().as_ref()],
+        bump = reserve_attestation.bump
     )]
-    pub user_role: Account<'info, UserRole>,
-}
+    pub reserve_attestation: Account<'info, ReserveAttestation>,
 
-#[derive(Accounts)]
-#[instruction(user: Pubkey, role: Role)]
-pub struct HasRole<'info> {
-    /// The user role account to verify
-    /// Transaction fails if this account doesn't exist (meaning no role assigned)
-    #[account(seeds = [b"user_role", user.as_ref()], bump = user_role.bump)]
-    pub user_role: Account<'info, UserRole>,
+    /// The wallet receiving the tokens
+    /// CHECK: Any wallet may receive tokens; only used to derive the associated token account
+    pub wallet: UncheckedAccount<'info>,
+
+    /// The wallet's associated token account, created if missing
+    #[account(
+        init_if_needed,
+        payer = operator,
+        associated_token::mint = mint,
+        associated_token::authority = wallet,
+        associated_token::token_program = token_program
+    )]
+    pub recipient: InterfaceAccount<'info, TokenAccount>,
+
+    /// The mint authority PDA controlled by this program
+    /// CHECK: PDA derived from "mint_authority" seed
+    #[account(seeds = [b"mint_authority"], bump)]
+    pub mint_authority: AccountInfo<'info>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct TransferAuthority<'info> {
-    /// The Token-2022 mint account
-    /// CHECK: Validated by Token-2022 program
+pub struct BatchMintByOperator<'info> {
+    /// The operator's role account
+    #[account(
+        seeds = [b"user_role", operator.key().as_ref()],
+        bump = operator_role.bump,
+        constraint = operator_role.role == Role::Operator @ PusdError::Unauthorized
+    )]
+    pub operator_role: Account<'info, UserRole>,
+
+    /// Program state holding mint configuration
+    #[account(seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Pays for the mint window account on first use
     #[account(mut)]
-    pub mint: AccountInfo<'info>,
+    pub operator: Signer<'info>,
 
-    /// The current mint authority who will transfer control
-    pub current_authority: Signer<'info>,
+    /// Running total of this operator's direct mints in the current window
+    #[account(
+        init_if_needed,
+        payer = operator,
+        space = OperatorMintWindow::LEN,
+        seeds = [b"operator_mint_window", operator.key().as_ref()],
+        bump
+    )]
+    pub mint_window: Account<'info, OperatorMintWindow>,
 
-    /// The program data account to verify upgrade authority
-    /// CHECK: This is the BPF Loader Upgradeable program data account
-    #[account(seeds = [crate::ID.as_ref()], bump, seeds::program = bpf_loader_upgradeable::ID)]
-    pub program_data: UncheckedAccount<'info>,
+    /// The canonical PUSD mint
+    /// CHECK: Address pinned to the mint recorded in program state
+    #[account(mut, address = program_state.mint @ PusdError::InvalidMint)]
+    pub mint: AccountInfo<'info>,
 
-    #[account(mut)]
-    pub payer: Signer<'info>,
+    /// Latest proof-of-reserves attestation for the mint
+    #[account(
+        seeds = [b"reserve_attestation", mint.key().as_ref()],
+        bump = reserve_attestation.bump
+    )]
+    pub reserve_attestation: Account<'info, ReserveAttestation>,
+
+    /// The mint authority PDA controlled by this program
+    /// CHECK: PDA derived from "mint_authority" seed
+    #[account(seeds = [b"mint_authority"], bump)]
+    pub mint_authority: AccountInfo<'info>,
 
     /// The Token-2022 program
     pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: recipient token accounts (writable), one per amount
 }
 
 #[derive(Accounts)]
-pub struct MintByContract<'info> {
+#[instruction(reference: [u8; 32])]
+pub struct MintWithReference<'info> {
     /// The authorized contract's role account
     #[account(
         seeds = [b"user_role", authorized_contract.key().as_ref()],
@@ -436,13 +1965,36 @@ pub struct MintByContract<'info> {
     )]
     pub contract_role: Account<'info, UserRole>,
 
+    /// Pays for the mint receipt
+    #[account(mut)]
     pub authorized_contract: Signer<'info>,
 
+    /// Receipt recording this caller's reference; existing data means a duplicate
+    #[account(
+        init_if_needed,
+        payer = authorized_contract,
+        space = MintReceipt::LEN,
+        seeds = [b"mint_receipt", authorized_contract.key().as_ref(), reference.as_ref()],
+        bump
+    )]
+    pub mint_receipt: Account<'info, MintReceipt>,
+
     /// The Token-2022 mint account
     /// CHECK: Validated by Token-2022 program
     #[account(mut)]
     pub mint: AccountInfo<'info>,
 
+    /// Program state holding the pause flag
+    #[account(seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Latest proof-of-reserves attestation for the mint
+    #[account(
+        seeds = [b"reserve_attestation", mint.key().as_ref()],
+        bump = reserve_attestation.bump
+    )]
+    pub reserve_attestation: Account<'info, ReserveAttestation>,
+
     /// The recipient's token account
     /// CHECK: Validated by Token-2022 program
     #[account(mut)]
@@ -455,10 +2007,36 @@ pub struct MintByContract<'info> {
 
     /// The Token-2022 program
     pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct MintByOperator<'info> {
+pub struct CloseMintReceipt<'info> {
+    /// The owner who is closing the receipt
+    #[account(
+        seeds = [b"user_role", owner.key().as_ref()],
+        bump = owner_role.bump,
+        constraint = owner_role.role == Role::Owner @ PusdError::Unauthorized
+    )]
+    pub owner_role: Account<'info, UserRole>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The receipt being closed
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"mint_receipt", mint_receipt.minter.as_ref(), mint_receipt.reference.as_ref()],
+        bump = mint_receipt.bump
+    )]
+    pub mint_receipt: Account<'info, MintReceipt>,
+}
+
+#[derive(Accounts)]
+#[instruction(request_id: u64)]
+pub struct RequestMint<'info> {
     /// The operator's role account
     #[account(
         seeds = [b"user_role", operator.key().as_ref()],
@@ -467,14 +2045,141 @@ pub struct MintByOperator<'info> {
     )]
     pub operator_role: Account<'info, UserRole>,
 
+    /// Pays for the mint request account
+    #[account(mut)]
     pub operator: Signer<'info>,
 
+    /// The pending mint request
+    #[account(
+        init,
+        payer = operator,
+        space = MintRequest::LEN,
+        seeds = [b"mint_request", operator.key().as_ref(), request_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub mint_request: Account<'info, MintRequest>,
+
+    /// The Token-2022 mint account
+    /// CHECK: Stored on the request; validated by Token-2022 program at approval
+    pub mint: AccountInfo<'info>,
+
+    /// The recipient's token account
+    /// CHECK: Stored on the request; validated by Token-2022 program at approval
+    pub recipient: AccountInfo<'info>,
+
+    /// Sunset tombstone; rejects the instruction once executed
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveMint<'info> {
+    /// The approver's role account
+    #[account(
+        seeds = [b"user_role", approver.key().as_ref()],
+        bump = approver_role.bump,
+        constraint = approver_role.role == Role::Approver @ PusdError::Unauthorized
+    )]
+    pub approver_role: Account<'info, UserRole>,
+
+    pub approver: Signer<'info>,
+
+    /// Program state holding the large mint threshold
+    #[account(seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// The pending mint request, closed on approval
+    #[account(
+        mut,
+        close = requester,
+        has_one = requester,
+        has_one = mint,
+        has_one = recipient,
+        seeds = [b"mint_request", mint_request.requester.as_ref(), mint_request.request_id.to_le_bytes().as_ref()],
+        bump = mint_request.bump
+    )]
+    pub mint_request: Account<'info, MintRequest>,
+
+    /// The operator that created the request; receives the rent refund
+    /// CHECK: Must match mint_request.requester
+    #[account(mut)]
+    pub requester: AccountInfo<'info>,
+
     /// The Token-2022 mint account
     /// CHECK: Validated by Token-2022 program
     #[account(mut)]
     pub mint: AccountInfo<'info>,
 
-    /// The recipient's token account associated with the mint
+    /// Latest proof-of-reserves attestation for the mint
+    #[account(
+        seeds = [b"reserve_attestation", mint.key().as_ref()],
+        bump = reserve_attestation.bump
+    )]
+    pub reserve_attestation: Account<'info, ReserveAttestation>,
+
+    /// The recipient's token account
+    /// CHECK: Validated by Token-2022 program
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+
+    /// The mint authority PDA controlled by this program
+    /// CHECK: PDA derived from "mint_authority" seed
+    #[account(seeds = [b"mint_authority"], bump)]
+    pub mint_authority: AccountInfo<'info>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveLargeMint<'info> {
+    /// The owner approving the request
+    #[account(
+        seeds = [b"user_role", owner.key().as_ref()],
+        bump = owner_role.bump,
+        constraint = owner_role.role == Role::Owner @ PusdError::Unauthorized
+    )]
+    pub owner_role: Account<'info, UserRole>,
+
+    pub owner: Signer<'info>,
+
+    /// The pending mint request, closed on approval
+    #[account(
+        mut,
+        close = requester,
+        has_one = requester,
+        has_one = mint,
+        has_one = recipient,
+        seeds = [b"mint_request", mint_request.requester.as_ref(), mint_request.request_id.to_le_bytes().as_ref()],
+        bump = mint_request.bump
+    )]
+    pub mint_request: Account<'info, MintRequest>,
+
+    /// The operator that created the request; receives the rent refund
+    /// CHECK: Must match mint_request.requester
+    #[account(mut)]
+    pub requester: AccountInfo<'info>,
+
+    /// The Token-2022 mint account
+    /// CHECK: Validated by Token-2022 program
+    #[account(mut)]
+    pub mint: AccountInfo<'info>,
+
+    /// Program state holding the pause flag
+    #[account(seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Latest proof-of-reserves attestation for the mint
+    #[account(
+        seeds = [b"reserve_attestation", mint.key().as_ref()],
+        bump = reserve_attestation.bump
+    )]
+    pub reserve_attestation: Account<'info, ReserveAttestation>,
+
+    /// The recipient's token account
     /// CHECK: Validated by Token-2022 program
     #[account(mut)]
     pub recipient: AccountInfo<'info>,
@@ -487,3 +2192,157 @@ pub struct MintByOperator<'info> {
     /// The Token-2022 program
     pub token_program: Program<'info, Token2022>,
 }
+
+#[derive(Accounts)]
+pub struct RejectMint<'info> {
+    /// The approver's role account
+    #[account(
+        seeds = [b"user_role", approver.key().as_ref()],
+        bump = approver_role.bump,
+        constraint = approver_role.role == Role::Approver @ PusdError::Unauthorized
+    )]
+    pub approver_role: Account<'info, UserRole>,
+
+    pub approver: Signer<'info>,
+
+    /// The pending mint request, closed on rejection
+    #[account(
+        mut,
+        close = requester,
+        has_one = requester,
+        seeds = [b"mint_request", mint_request.requester.as_ref(), mint_request.request_id.to_le_bytes().as_ref()],
+        bump = mint_request.bump
+    )]
+    pub mint_request: Account<'info, MintRequest>,
+
+    /// The operator that created the request; receives the rent refund
+    /// CHECK: Must match mint_request.requester
+    #[account(mut)]
+    pub requester: AccountInfo<'info>,
+
+    /// Sunset tombstone; rejects the instruction once executed
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseExpiredMintRequest<'info> {
+    /// The expired mint request
+    #[account(
+        mut,
+        close = requester,
+        has_one = requester,
+        seeds = [b"mint_request", mint_request.requester.as_ref(), mint_request.request_id.to_le_bytes().as_ref()],
+        bump = mint_request.bump
+    )]
+    pub mint_request: Account<'info, MintRequest>,
+
+    /// The operator that created the request; receives the rent refund
+    /// CHECK: Must match mint_request.requester
+    #[account(mut)]
+    pub requester: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    /// The owner updating the configuration
+    #[account(
+        seeds = [b"user_role", owner.key().as_ref()],
+        bump = owner_role.bump,
+        constraint = owner_role.role == Role::Owner @ PusdError::Unauthorized
+    )]
+    pub owner_role: Account<'info, UserRole>,
+
+    pub owner: Signer<'info>,
+
+    /// Program state holding the configuration
+    #[account(mut, seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateGovernance<'info> {
+    /// The governance admin, or the upgrade authority while not renounced
+    pub signer: Signer<'info>,
+
+    /// Program state holding the governance configuration
+    #[account(mut, seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// The program data account to verify upgrade authority
+    #[account(seeds = [crate::ID.as_ref()], bump, seeds::program = bpf_loader_upgradeable::ID)]
+    pub program_data: Account<'info, ProgramData>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    /// The proposed governance admin
+    pub new_admin: Signer<'info>,
+
+    /// Program state holding the governance configuration
+    #[account(mut, seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+#[derive(Accounts)]
+pub struct AttestReserves<'info> {
+    /// The attestor's role account
+    #[account(
+        seeds = [b"user_role", attestor.key().as_ref()],
+        bump = attestor_role.bump,
+        constraint = attestor_role.role == Role::Attestor @ PusdError::Unauthorized
+    )]
+    pub attestor_role: Account<'info, UserRole>,
+
+    /// Pays for the attestation account on first use
+    #[account(mut)]
+    pub attestor: Signer<'info>,
+
+    /// Program state holding the canonical mint
+    #[account(seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// The canonical PUSD mint the reserves back
+    /// CHECK: Address pinned to the mint recorded in program state
+    #[account(address = program_state.mint @ PusdError::InvalidMint)]
+    pub mint: AccountInfo<'info>,
+
+    /// The attestation account for this mint
+    #[account(
+        init_if_needed,
+        payer = attestor,
+        space = ReserveAttestation::LEN,
+        seeds = [b"reserve_attestation", mint.key().as_ref()],
+        bump
+    )]
+    pub reserve_attestation: Account<'info, ReserveAttestation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetReserveStalenessWindow<'info> {
+    /// The owner updating the configuration
+    #[account(
+        seeds = [b"user_role", owner.key().as_ref()],
+        bump = owner_role.bump,
+        constraint = owner_role.role == Role::Owner @ PusdError::Unauthorized
+    )]
+    pub owner_role: Account<'info, UserRole>,
+
+    pub owner: Signer<'info>,
+
+    /// The attestation account being configured
+    #[account(
+        mut,
+        seeds = [b"reserve_attestation", reserve_attestation.mint.as_ref()],
+        bump = reserve_attestation.bump
+    )]
+    pub reserve_attestation: Account<'info, ReserveAttestation>,
+
+    /// Sunset tombstone; rejects the instruction once executed
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+}
@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    PusdError,
+    BPS_DENOMINATOR,
+    MAX_TOKEN_DECIMALS,
+    PRICE_SCALE,
+    VAULT_VIRTUAL_ASSETS,
+    VAULT_VIRTUAL_SHARES,
+};
+
+// ============================================================================
+// Checked conversion math shared by the peg stability module and staking vault
+// Every conversion takes an explicit rounding direction; callers pick the one
+// that favors the protocol (round down what the user receives, round up what
+// the user owes). All intermediate products are computed in u128.
+// ============================================================================
+
+/// Rounding direction for integer division
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Rounding {
+    Down,
+    Up,
+}
+
+/// Computes a * b / c with the given rounding
+pub fn mul_div(a: u64, b: u64, c: u64, rounding: Rounding) -> Result<u64> {
+    require!(c != 0, PusdError::MathOverflow);
+
+    let product = a as u128 * b as u128;
+    let quotient = match rounding {
+        Rounding::Down => product / c as u128,
+        Rounding::Up => product.div_ceil(c as u128),
+    };
+
+    u64::try_from(quotient).map_err(|_| PusdError::MathOverflow.into())
+}
+
+/// Returns 10^decimals for supported token decimals
+pub fn pow10(decimals: u8) -> Result<u64> {
+    require!(decimals <= MAX_TOKEN_DECIMALS, PusdError::UnsupportedDecimals);
+    Ok(10u64.pow(decimals as u32))
+}
+
+/// Rescales an amount from one decimals precision to another
+pub fn normalize_decimals(
+    amount: u64,
+    from_decimals: u8,
+    to_decimals: u8,
+    rounding: Rounding
+) -> Result<u64> {
+    if from_decimals <= to_decimals {
+        let factor = pow10(to_decimals - from_decimals)?;
+        amount.checked_mul(factor).ok_or(PusdError::MathOverflow.into())
+    } else {
+        let factor = pow10(from_decimals - to_decimals)?;
+        mul_div(amount, 1, factor, rounding)
+    }
+}
+
+/// Converts a collateral amount into PUSD at price (scaled by PRICE_SCALE)
+pub fn collateral_to_pusd(
+    collateral_amount: u64,
+    collateral_decimals: u8,
+    pusd_decimals: u8,
+    price: u64,
+    rounding: Rounding
+) -> Result<u64> {
+    let value = mul_div(collateral_amount, price, PRICE_SCALE, rounding)?;
+    normalize_decimals(value, collateral_decimals, pusd_decimals, rounding)
+}
+
+/// Converts a PUSD amount into collateral at price (scaled by PRICE_SCALE)
+pub fn pusd_to_collateral(
+    pusd_amount: u64,
+    collateral_decimals: u8,
+    pusd_decimals: u8,
+    price: u64,
+    rounding: Rounding
+) -> Result<u64> {
+    require!(price != 0, PusdError::InvalidPrice);
+    let value = normalize_decimals(pusd_amount, pusd_decimals, collateral_decimals, rounding)?;
+    mul_div(value, PRICE_SCALE, price, rounding)
+}
+
+/// Fee in basis points, rounded up so the protocol never undercharges
+pub fn fee_amount(amount: u64, fee_bps: u16) -> Result<u64> {
+    mul_div(amount, fee_bps as u64, BPS_DENOMINATOR, Rounding::Up)
+}
+
+/// Splits an amount into (fee, remainder) using fee_amount
+pub fn split_fee(amount: u64, fee_bps: u16) -> Result<(u64, u64)> {
+    let fee = fee_amount(amount, fee_bps)?;
+    let remainder = amount.checked_sub(fee).ok_or(PusdError::MathOverflow)?;
+    Ok((fee, remainder))
+}
+
+/// Converts vault assets to shares using virtual shares/assets
+pub fn assets_to_shares(
+    assets: u64,
+    total_assets: u64,
+    total_shares: u64,
+    rounding: Rounding
+) -> Result<u64> {
+    let shares = total_shares.checked_add(VAULT_VIRTUAL_SHARES).ok_or(PusdError::MathOverflow)?;
+    let assets_total = total_assets.checked_add(VAULT_VIRTUAL_ASSETS).ok_or(PusdError::MathOverflow)?;
+    mul_div(assets, shares, assets_total, rounding)
+}
+
+/// Converts vault shares to assets using virtual shares/assets
+pub fn shares_to_assets(
+    shares: u64,
+    total_assets: u64,
+    total_shares: u64,
+    rounding: Rounding
+) -> Result<u64> {
+    let shares_total = total_shares.checked_add(VAULT_VIRTUAL_SHARES).ok_or(PusdError::MathOverflow)?;
+    let assets = total_assets.checked_add(VAULT_VIRTUAL_ASSETS).ok_or(PusdError::MathOverflow)?;
+    mul_div(shares, assets, shares_total, rounding)
+}
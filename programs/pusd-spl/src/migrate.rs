@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::Discriminator;
+
+use crate::*;
+
+// ============================================================================
+// Account layout migration
+// UserRole and ProgramState carry a version byte right after the
+// discriminator. Accounts still in the unversioned layout are recognised by
+// their exact size and rewritten in place; fields added since take their
+// defaults. Migrated roles are Pending, which require_role! treats as Active
+// once role_active_time has passed. Migrated ProgramState accounts keep the
+// default config, have no admin until one is accepted through accept_admin,
+// start unpaused, and refund renounced roles to their holders. They also have
+// no canonical mint until transfer_mint_authority_to_pda records it, and mint
+// nothing until then. The layout change is deterministic, so anyone may
+// migrate an account as long as they pay for the extra rent.
+// ============================================================================
+
+/// Returns the current-layout bytes for an unversioned UserRole or ProgramState account
+pub fn migrate_account_data(data: &[u8]) -> Result<Vec<u8>> {
+    let discriminator = data.get(..8).ok_or(PusdError::UnsupportedAccountLayout)?;
+
+    let mut migrated = Vec::new();
+    if discriminator == UserRole::DISCRIMINATOR {
+        require!(data.len() != UserRole::LEN, PusdError::AccountAlreadyMigrated);
+        require!(data.len() == UserRole::V1_LEN, PusdError::UnsupportedAccountLayout);
+
+        // unversioned: user, role, bump, role_active_time
+        let mut fields = &data[8..];
+        let user_role = UserRole {
+            version: UserRole::VERSION,
+            user: Pubkey::deserialize(&mut fields)?,
+            role: Role::deserialize(&mut fields)?,
+            bump: u8::deserialize(&mut fields)?,
+            role_active_time: i64::deserialize(&mut fields)?,
+            status: RoleStatus::Pending,
+        };
+        user_role.try_serialize(&mut migrated)?;
+    } else if discriminator == ProgramState::DISCRIMINATOR {
+        require!(data.len() != ProgramState::LEN, PusdError::AccountAlreadyMigrated);
+        require!(data.len() == ProgramState::V1_LEN, PusdError::UnsupportedAccountLayout);
+
+        // unversioned: is_initialized, bump
+        let mut fields = &data[8..];
+        let program_state = ProgramState {
+            version: ProgramState::VERSION,
+            is_initialized: bool::deserialize(&mut fields)?,
+            bump: u8::deserialize(&mut fields)?,
+            direct_mint_disabled: false,
+            large_mint_threshold: 0,
+            psm_fee_in_bps: 0,
+            psm_fee_out_bps: 0,
+            redemption_fee_bps: 0,
+            kyc_required: false,
+            admin: Pubkey::default(),
+            upgrade_authority_renounced: false,
+            paused: false,
+            renounce_rent_recipient: Pubkey::default(),
+            mint: Pubkey::default(),
+            pending_admin: Pubkey::default(),
+        };
+        program_state.try_serialize(&mut migrated)?;
+    } else {
+        return err!(PusdError::UnsupportedAccountLayout);
+    }
+    Ok(migrated)
+}
+
+/// Realloc an account to the current layout and rewrite its data in place
+pub(crate) fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+    require_not_sunset!(ctx.accounts.sunset_state);
+
+    let account = &ctx.accounts.account;
+    let migrated = migrate_account_data(&account.try_borrow_data()?)?;
+
+    // Top up rent for the larger account
+    let rent = Rent::get()?.minimum_balance(migrated.len());
+    let shortfall = rent.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.payer.to_account_info(),
+            to: account.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        transfer(cpi_ctx, shortfall)?;
+    }
+
+    account.resize(migrated.len())?;
+    account.try_borrow_mut_data()?.copy_from_slice(&migrated);
+
+    msg!("Migrated account {} to {} bytes", account.key(), migrated.len());
+    Ok(())
+}
+
+// ============================================================================
+// Account Validation Structs
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// Pays for the extra rent of the larger layout
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The UserRole or ProgramState account to migrate
+    /// CHECK: Owner checked here; discriminator and size checked by the handler
+    #[account(mut, owner = crate::ID)]
+    pub account: AccountInfo<'info>,
+
+    /// Sunset tombstone; rejects the instruction once executed
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -1,4 +1,4 @@
-/// Macro to check if a user has a specific role and if it's activated (24h delay)
+/// Macro to check if a user has a specific role and if it's activated (24h delay) and not suspended
 /// Usage: require_role!(user_role_account, Role::Owner)?;
 #[macro_export]
 macro_rules! require_role {
@@ -10,36 +10,32 @@ macro_rules! require_role {
             return Err(PusdError::Unauthorized.into());
         }
         
-        // Check if role is activated (24 hours have passed since assignment)
+        // Check if role is activated (24 hours have passed since assignment) and not suspended
         let current_time = Clock::get()?.unix_timestamp;
-        if current_time < $user_role.role_active_time {
-            return Err(PusdError::RoleNotActivated.into());
+        match $user_role.effective_status(current_time) {
+            RoleStatus::Active => {}
+            RoleStatus::Pending => return Err(PusdError::RoleNotActivated.into()),
+            RoleStatus::Suspended => return Err(PusdError::RoleSuspended.into()),
         }
     }};
 }
 
 /// Macro to check if caller is upgrade authority
-/// Usage: require_upgrade_authority!(program_data_account, payer_key)?;
+/// Usage: require_upgrade_authority!(program_data_account, payer_key);
 #[macro_export]
 macro_rules! require_upgrade_authority {
-    ($program_data:expr, $payer:expr) => {{
-        let data = $program_data.try_borrow_data()?;
-        if data.len() < 45 {
-            return Err(PusdError::InvalidProgramData.into());
-        }
-        
-        let upgrade_authority_option = data[4 + 8];
-        if upgrade_authority_option != 1 {
-            return Err(PusdError::OnlyUpgradeAuthority.into());
-        }
-        
-        let upgrade_authority = Pubkey::try_from(&data[4 + 8 + 1..4 + 8 + 1 + 32])
-            .map_err(|_| PusdError::InvalidProgramData)?;
-        
-        if upgrade_authority != $payer {
-            return Err(PusdError::OnlyUpgradeAuthority.into());
-        }
-    }};
+    ($program_data:expr, $payer:expr) => {
+        $crate::upgrade::check_upgrade_authority($program_data.upgrade_authority_address, $payer)?
+    };
+}
+
+/// Macro to check the program has not been sunset
+/// Usage: require_not_sunset!(sunset_state_account);
+#[macro_export]
+macro_rules! require_not_sunset {
+    ($sunset_state:expr) => {
+        $crate::sunset::check_not_sunset(&$sunset_state)?
+    };
 }
 
 /// Macro to check if program is not already initialized
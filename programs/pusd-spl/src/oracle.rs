@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+
+use crate::{PriceFeed, PusdError, BPS_DENOMINATOR, PRICE_SCALE};
+
+// ============================================================================
+// Price sources for non-USD-pegged collateral
+// Every source returns a USD price scaled by PRICE_SCALE after checking that
+// it is fresh enough and that its confidence interval is tight enough.
+// ============================================================================
+
+/// Freshness and confidence limits applied when reading a price
+#[derive(Clone, Copy, Debug)]
+pub struct PriceLimits {
+    pub now: i64,                  // current unix timestamp
+    pub max_age: i64,              // maximum seconds since publish_time
+    pub max_confidence_bps: u16,   // maximum confidence interval relative to price
+}
+
+/// A source of collateral prices
+pub trait PriceSource {
+    /// Returns the price scaled by PRICE_SCALE, or an error if it fails the limits
+    fn get_price(&self, limits: &PriceLimits) -> Result<u64>;
+}
+
+/// A constant price configured by the Owner
+pub struct FixedPrice(pub u64);
+
+impl PriceSource for FixedPrice {
+    fn get_price(&self, _limits: &PriceLimits) -> Result<u64> {
+        require!(self.0 > 0, PusdError::InvalidPrice);
+        Ok(self.0)
+    }
+}
+
+impl PriceSource for PriceFeed {
+    fn get_price(&self, limits: &PriceLimits) -> Result<u64> {
+        require!(self.price > 0, PusdError::InvalidPrice);
+        check_price(self.price, self.confidence, self.publish_time, limits)?;
+        Ok(self.price)
+    }
+}
+
+// Pyth v2 price account layout offsets
+const PYTH_MAGIC: u32 = 0xa1b2_c3d4;
+const PYTH_VERSION: u32 = 2;
+const PYTH_PRICE_ACCOUNT_TYPE: u32 = 3;
+const PYTH_STATUS_TRADING: u32 = 1;
+const PYTH_MAGIC_OFFSET: usize = 0;
+const PYTH_VERSION_OFFSET: usize = 4;
+const PYTH_TYPE_OFFSET: usize = 8;
+const PYTH_EXPO_OFFSET: usize = 20;
+const PYTH_TIMESTAMP_OFFSET: usize = 96;
+const PYTH_AGG_PRICE_OFFSET: usize = 208;
+const PYTH_AGG_CONF_OFFSET: usize = 216;
+const PYTH_AGG_STATUS_OFFSET: usize = 224;
+pub const PYTH_PRICE_ACCOUNT_MIN_LEN: usize = 240;
+
+/// Aggregate price read from a Pyth-style price account
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PythPrice {
+    pub price: i64,
+    pub confidence: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+    pub status: u32,
+}
+
+impl PythPrice {
+    /// Parses the aggregate price from raw Pyth v2 price account data
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        require!(data.len() >= PYTH_PRICE_ACCOUNT_MIN_LEN, PusdError::InvalidOracleAccount);
+        require!(
+            read_u32(data, PYTH_MAGIC_OFFSET) == PYTH_MAGIC
+                && read_u32(data, PYTH_VERSION_OFFSET) == PYTH_VERSION
+                && read_u32(data, PYTH_TYPE_OFFSET) == PYTH_PRICE_ACCOUNT_TYPE,
+            PusdError::InvalidOracleAccount
+        );
+
+        Ok(Self {
+            price: read_u64(data, PYTH_AGG_PRICE_OFFSET) as i64,
+            confidence: read_u64(data, PYTH_AGG_CONF_OFFSET),
+            expo: read_u32(data, PYTH_EXPO_OFFSET) as i32,
+            publish_time: read_u64(data, PYTH_TIMESTAMP_OFFSET) as i64,
+            status: read_u32(data, PYTH_AGG_STATUS_OFFSET),
+        })
+    }
+}
+
+impl PriceSource for PythPrice {
+    fn get_price(&self, limits: &PriceLimits) -> Result<u64> {
+        require!(self.status == PYTH_STATUS_TRADING, PusdError::InvalidPrice);
+        require!(self.price > 0, PusdError::InvalidPrice);
+
+        let price = rescale_expo(self.price as u64, self.expo)?;
+        let confidence = rescale_expo(self.confidence, self.expo)?;
+        require!(price > 0, PusdError::InvalidPrice);
+
+        check_price(price, confidence, self.publish_time, limits)?;
+        Ok(price)
+    }
+}
+
+/// Shared staleness and confidence checks
+fn check_price(price: u64, confidence: u64, publish_time: i64, limits: &PriceLimits) -> Result<()> {
+    require!(
+        limits.now.saturating_sub(publish_time) <= limits.max_age,
+        PusdError::StalePrice
+    );
+    require!(
+        confidence as u128 * BPS_DENOMINATOR as u128 <= price as u128 * limits.max_confidence_bps as u128,
+        PusdError::PriceConfidenceTooWide
+    );
+    Ok(())
+}
+
+/// Converts a value with a base-10 exponent to PRICE_SCALE, rounding down
+fn rescale_expo(value: u64, expo: i32) -> Result<u64> {
+    let shift = expo
+        .checked_add(PRICE_SCALE.ilog10() as i32)
+        .ok_or(PusdError::MathOverflow)?;
+    let factor = 10u64
+        .checked_pow(shift.unsigned_abs())
+        .ok_or(PusdError::MathOverflow)?;
+    if shift >= 0 {
+        value.checked_mul(factor).ok_or(PusdError::MathOverflow.into())
+    } else {
+        Ok(value / factor)
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
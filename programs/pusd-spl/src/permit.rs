@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Token2022;
+use solana_sdk_ids::sysvar;
+
+use crate::*;
+use crate::bridge::{_ed25519_instruction_data, parse_ed25519_signers};
+
+// ============================================================================
+// Signed mint permits
+// An AuthorizedSigner (e.g. a custody HSM key) signs
+// (program, mint, recipient, amount, nonce, expiry) off-chain; any relayer submits it
+// with an ed25519 precompile instruction placed before mint_with_permit.
+// A PermitNonce PDA per (signer, nonce) prevents replays.
+// ============================================================================
+
+/// Canonical bytes an AuthorizedSigner signs to authorize a mint
+/// The program ID binds the permit to this deployment, so it cannot be replayed against another one
+pub fn permit_message(mint: &Pubkey, recipient: &Pubkey, amount: u64, nonce: u64, expiry: i64) -> Vec<u8> {
+    let mut message = Vec::with_capacity(PERMIT_MESSAGE_DOMAIN.len() + 32 + 32 + 32 + 8 + 8 + 8);
+    message.extend_from_slice(PERMIT_MESSAGE_DOMAIN);
+    message.extend_from_slice(crate::ID.as_ref());
+    message.extend_from_slice(mint.as_ref());
+    message.extend_from_slice(recipient.as_ref());
+    message.extend_from_slice(&amount.to_le_bytes());
+    message.extend_from_slice(&nonce.to_le_bytes());
+    message.extend_from_slice(&expiry.to_le_bytes());
+    message
+}
+
+/// Mint to the recipient if the permit is signed, unexpired, and unused
+pub(crate) fn mint_with_permit(
+    ctx: Context<MintWithPermit>,
+    amount: u64,
+    nonce: u64,
+    expiry: i64
+) -> Result<()> {
+    // Verify the permit signer has AuthorizedSigner role
+    require_role!(ctx.accounts.signer_role, Role::AuthorizedSigner);
+
+    require!(amount > 0, PusdError::InvalidAmount);
+    require!(Clock::get()?.unix_timestamp <= expiry, PusdError::PermitExpired);
+
+    let message = permit_message(
+        &ctx.accounts.mint.key(),
+        &ctx.accounts.recipient.key(),
+        amount,
+        nonce,
+        expiry
+    );
+    let signer = ctx.accounts.signer.key();
+    let precompile_data = _ed25519_instruction_data(&ctx.accounts.instructions)?;
+    let mut signed = false;
+    for data in &precompile_data {
+        signed |= parse_ed25519_signers(data)?
+            .iter()
+            .any(|(key, signed_message)| *key == signer && *signed_message == message.as_slice());
+    }
+    require!(signed, PusdError::InvalidPermitSignature);
+
+    msg!("Minting {} tokens with permit {} from {}", amount, nonce, signer);
+
+    _mint(
+        &ctx.accounts.program_state,
+        &ctx.accounts.mint,
+        &ctx.accounts.recipient,
+        &ctx.accounts.mint_authority,
+        &ctx.accounts.token_program,
+        &ctx.accounts.reserve_attestation,
+        ctx.bumps.mint_authority,
+        amount
+    )?;
+
+    let permit_nonce = &mut ctx.accounts.permit_nonce;
+    permit_nonce.signer = signer;
+    permit_nonce.nonce = nonce;
+    permit_nonce.recipient = ctx.accounts.recipient.key();
+    permit_nonce.amount = amount;
+    permit_nonce.bump = ctx.bumps.permit_nonce;
+    Ok(())
+}
+
+// ============================================================================
+// Account Validation Structs
+// ============================================================================
+
+#[derive(Accounts)]
+#[instruction(amount: u64, nonce: u64)]
+pub struct MintWithPermit<'info> {
+    /// The permit signer's role account
+    #[account(
+        seeds = [b"user_role", signer.key().as_ref()],
+        bump = signer_role.bump,
+        constraint = signer_role.role == Role::AuthorizedSigner @ PusdError::Unauthorized
+    )]
+    pub signer_role: Account<'info, UserRole>,
+
+    /// The AuthorizedSigner key that signed the permit off-chain
+    /// CHECK: Authorized through signer_role and the ed25519 instruction
+    pub signer: AccountInfo<'info>,
+
+    /// Relayer paying for the nonce account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Permit nonce marker; `init` fails if the nonce was already used
+    #[account(
+        init,
+        payer = payer,
+        space = PermitNonce::LEN,
+        seeds = [b"permit_nonce", signer.key().as_ref(), nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub permit_nonce: Account<'info, PermitNonce>,
+
+    /// The Token-2022 mint account
+    /// CHECK: Validated by Token-2022 program
+    #[account(mut)]
+    pub mint: AccountInfo<'info>,
+
+    /// Program state holding the pause flag
+    #[account(seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Latest proof-of-reserves attestation for the mint
+    #[account(
+        seeds = [b"reserve_attestation", mint.key().as_ref()],
+        bump = reserve_attestation.bump
+    )]
+    pub reserve_attestation: Account<'info, ReserveAttestation>,
+
+    /// The recipient's token account named in the permit
+    /// CHECK: Validated by Token-2022 program
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+
+    /// The mint authority PDA controlled by this program
+    /// CHECK: PDA derived from "mint_authority" seed
+    #[account(seeds = [b"mint_authority"], bump)]
+    pub mint_authority: AccountInfo<'info>,
+
+    /// The instructions sysvar holding the ed25519 verification instruction
+    /// CHECK: Address checked against the instructions sysvar ID
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+}
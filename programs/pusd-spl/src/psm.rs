@@ -0,0 +1,574 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    burn,
+    transfer_checked,
+    Burn,
+    Mint,
+    Token2022,
+    TokenAccount,
+    TokenInterface,
+    TransferChecked,
+};
+
+use crate::*;
+use crate::math::{collateral_to_pusd, pusd_to_collateral, split_fee, Rounding};
+use crate::oracle::{FixedPrice, PriceLimits, PriceSource, PythPrice};
+
+// ============================================================================
+// Peg Stability Module
+// Users swap an approved collateral for PUSD at its configured price and back,
+// paying the ProgramState fees to the treasury. Amounts are normalized between collateral and PUSD decimals with
+// rounding that always favors the protocol. Collateral sits in a vault owned
+// by the CollateralConfig PDA; PUSD is minted through the shared
+// _mint_unattested helper using the mint_authority PDA, since collateral rather
+// than the fiat reserve attestation backs it.
+// ============================================================================
+
+/// Register a new collateral mint and create its vault
+pub(crate) fn add_collateral(
+    ctx: Context<AddCollateral>,
+    price: u64,
+    debt_ceiling: u64
+) -> Result<()> {
+    require_not_sunset!(ctx.accounts.sunset_state);
+
+    // Verify the caller has Owner role
+    require_role!(ctx.accounts.owner_role, Role::Owner);
+
+    require!(price > 0, PusdError::InvalidPrice);
+    require!(
+        ctx.accounts.collateral_mint.decimals <= MAX_TOKEN_DECIMALS
+            && ctx.accounts.pusd_mint.decimals <= MAX_TOKEN_DECIMALS,
+        PusdError::UnsupportedDecimals
+    );
+
+    let config = &mut ctx.accounts.collateral_config;
+    config.collateral_mint = ctx.accounts.collateral_mint.key();
+    config.pusd_mint = ctx.accounts.pusd_mint.key();
+    config.vault = ctx.accounts.vault.key();
+    config.debt = 0;
+    config.debt_ceiling = debt_ceiling;
+    config.enabled = true;
+    config.bump = ctx.bumps.collateral_config;
+    config.collateral_decimals = ctx.accounts.collateral_mint.decimals;
+    config.pusd_decimals = ctx.accounts.pusd_mint.decimals;
+    config.price = price;
+    config.price_source = PriceSourceKind::Fixed;
+
+    msg!(
+        "Collateral {} added - price {}, ceiling {}",
+        config.collateral_mint,
+        price,
+        debt_ceiling
+    );
+    Ok(())
+}
+
+/// Update the fixed price, ceiling, and enabled flag of an existing collateral
+pub(crate) fn update_collateral(
+    ctx: Context<UpdateCollateral>,
+    price: u64,
+    debt_ceiling: u64,
+    enabled: bool
+) -> Result<()> {
+    require_not_sunset!(ctx.accounts.sunset_state);
+
+    // Verify the caller has Owner role
+    require_role!(ctx.accounts.owner_role, Role::Owner);
+
+    require!(price > 0, PusdError::InvalidPrice);
+
+    let config = &mut ctx.accounts.collateral_config;
+    config.price = price;
+    config.debt_ceiling = debt_ceiling;
+    config.enabled = enabled;
+
+    msg!(
+        "Collateral {} updated - price {}, ceiling {}, enabled {}",
+        config.collateral_mint,
+        price,
+        debt_ceiling,
+        enabled
+    );
+    Ok(())
+}
+
+/// Select the price source used by swaps for a collateral
+pub(crate) fn set_collateral_price_source(
+    ctx: Context<UpdateCollateral>,
+    price_source: PriceSourceKind,
+    oracle: Pubkey,
+    max_price_age: i64,
+    max_confidence_bps: u16
+) -> Result<()> {
+    require_not_sunset!(ctx.accounts.sunset_state);
+
+    // Verify the caller has Owner role
+    require_role!(ctx.accounts.owner_role, Role::Owner);
+
+    if price_source != PriceSourceKind::Fixed {
+        require_valid_address!(oracle);
+        require!(max_price_age > 0, PusdError::InvalidConfig);
+        require!(max_confidence_bps as u64 <= BPS_DENOMINATOR, PusdError::InvalidConfig);
+    }
+
+    let config = &mut ctx.accounts.collateral_config;
+    config.price_source = price_source;
+    config.oracle = oracle;
+    config.max_price_age = max_price_age;
+    config.max_confidence_bps = max_confidence_bps;
+
+    msg!(
+        "Collateral {} price source set to {:?} ({}) - max age {}s, max confidence {} bps",
+        config.collateral_mint,
+        price_source,
+        oracle,
+        max_price_age,
+        max_confidence_bps
+    );
+    Ok(())
+}
+
+/// Push a price to the Owner-controlled feed for a collateral
+pub(crate) fn update_price_feed(ctx: Context<UpdatePriceFeed>, price: u64, confidence: u64) -> Result<()> {
+    require_not_sunset!(ctx.accounts.sunset_state);
+
+    // Verify the caller has Owner role
+    require_role!(ctx.accounts.owner_role, Role::Owner);
+
+    require!(price > 0, PusdError::InvalidPrice);
+
+    let feed = &mut ctx.accounts.price_feed;
+    feed.collateral_mint = ctx.accounts.collateral_mint.key();
+    feed.price = price;
+    feed.confidence = confidence;
+    feed.publish_time = Clock::get()?.unix_timestamp;
+    feed.bump = ctx.bumps.price_feed;
+
+    msg!("Price feed {} updated: {} +/- {}", feed.key(), price, confidence);
+    Ok(())
+}
+
+/// Reads the collateral price from its configured source
+fn current_price(config: &CollateralConfig, oracle: Option<&AccountInfo>) -> Result<u64> {
+    let limits = PriceLimits {
+        now: Clock::get()?.unix_timestamp,
+        max_age: config.max_price_age,
+        max_confidence_bps: config.max_confidence_bps,
+    };
+
+    if config.price_source == PriceSourceKind::Fixed {
+        return FixedPrice(config.price).get_price(&limits);
+    }
+
+    let oracle = oracle.ok_or(PusdError::InvalidOracleAccount)?;
+    require!(oracle.key() == config.oracle, PusdError::InvalidOracleAccount);
+    let data = oracle.try_borrow_data()?;
+
+    match config.price_source {
+        PriceSourceKind::Feed => {
+            require!(oracle.owner == &crate::ID, PusdError::InvalidOracleAccount);
+            let feed = PriceFeed::try_deserialize(&mut &data[..])?;
+            require!(
+                feed.collateral_mint == config.collateral_mint,
+                PusdError::InvalidOracleAccount
+            );
+            feed.get_price(&limits)
+        }
+        PriceSourceKind::Pyth => PythPrice::parse(&data)?.get_price(&limits),
+        // Fixed prices return above; never panic on a config that reaches here
+        PriceSourceKind::Fixed => err!(PusdError::InvalidConfig),
+    }
+}
+
+/// Deposit collateral into the vault and receive PUSD minus the swap-in fee
+pub(crate) fn swap_in(ctx: Context<SwapIn>, amount: u64) -> Result<()> {
+    let config = &ctx.accounts.collateral_config;
+    require!(config.enabled, PusdError::CollateralDisabled);
+    require!(amount > 0, PusdError::InvalidAmount);
+
+    // Value the deposit in PUSD, rounding down what the user receives
+    let price = current_price(config, ctx.accounts.oracle.as_ref().map(|o| o.as_ref()))?;
+    let value = collateral_to_pusd(
+        amount,
+        config.collateral_decimals,
+        config.pusd_decimals,
+        price,
+        Rounding::Down
+    )?;
+    let (fee, minted) = split_fee(value, ctx.accounts.program_state.psm_fee_in_bps)?;
+    require!(minted > 0, PusdError::InvalidAmount);
+
+    // Debt covers both the user's PUSD and the fee minted to the treasury
+    let new_debt = config.debt.checked_add(value).ok_or(PusdError::MathOverflow)?;
+    require!(new_debt <= config.debt_ceiling, PusdError::DebtCeilingExceeded);
+
+    msg!("PSM swap in: {} collateral for {} PUSD (fee {})", amount, minted, fee);
+
+    // Pull the full collateral amount into the vault
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.user_collateral.to_account_info(),
+        mint: ctx.accounts.collateral_mint.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.collateral_token_program.to_account_info(), cpi_accounts);
+    transfer_checked(cpi_ctx, amount, ctx.accounts.collateral_mint.decimals)?;
+
+    _mint_unattested(
+        &ctx.accounts.program_state,
+        &ctx.accounts.mint,
+        &ctx.accounts.recipient,
+        &ctx.accounts.mint_authority,
+        &ctx.accounts.token_program,
+        ctx.bumps.mint_authority,
+        minted
+    )?;
+
+    if fee > 0 {
+        _mint_unattested(
+            &ctx.accounts.program_state,
+            &ctx.accounts.mint,
+            &ctx.accounts.treasury_token.to_account_info(),
+            &ctx.accounts.mint_authority,
+            &ctx.accounts.token_program,
+            ctx.bumps.mint_authority,
+            fee
+        )?;
+    }
+
+    ctx.accounts.collateral_config.debt = new_debt;
+    Ok(())
+}
+
+/// Burn PUSD minus the swap-out fee and withdraw the matching collateral from the vault
+pub(crate) fn swap_out(ctx: Context<SwapOut>, amount: u64) -> Result<()> {
+    let config = &ctx.accounts.collateral_config;
+    require!(config.enabled, PusdError::CollateralDisabled);
+    require!(amount > 0, PusdError::InvalidAmount);
+
+    // Fee is taken in PUSD, then the remainder is converted rounding down the payout
+    let (fee, net) = split_fee(amount, ctx.accounts.program_state.psm_fee_out_bps)?;
+    let price = current_price(config, ctx.accounts.oracle.as_ref().map(|o| o.as_ref()))?;
+    let payout = pusd_to_collateral(
+        net,
+        config.collateral_decimals,
+        config.pusd_decimals,
+        price,
+        Rounding::Down
+    )?;
+    require!(payout > 0, PusdError::InvalidAmount);
+
+    let new_debt = config.debt.checked_sub(net).ok_or(PusdError::InsufficientPsmLiquidity)?;
+    require!(payout <= ctx.accounts.vault.amount, PusdError::InsufficientPsmLiquidity);
+
+    msg!("PSM swap out: {} PUSD for {} collateral (fee {})", amount, payout, fee);
+
+    _transfer_fee_to_treasury(
+        &ctx.accounts.user_pusd,
+        &ctx.accounts.mint,
+        &ctx.accounts.treasury_token.to_account_info(),
+        &ctx.accounts.user,
+        &ctx.accounts.token_program,
+        config.pusd_decimals,
+        fee
+    )?;
+
+    // Burn the PUSD being redeemed from the user's account
+    let cpi_accounts = Burn {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.user_pusd.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    burn(cpi_ctx, net)?;
+
+    // Release collateral from the vault, signed by the config PDA
+    let collateral_mint_key = config.collateral_mint;
+    let seeds = &[b"collateral".as_ref(), collateral_mint_key.as_ref(), &[config.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.vault.to_account_info(),
+        mint: ctx.accounts.collateral_mint.to_account_info(),
+        to: ctx.accounts.user_collateral.to_account_info(),
+        authority: ctx.accounts.collateral_config.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.collateral_token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds
+    );
+    transfer_checked(cpi_ctx, payout, config.collateral_decimals)?;
+
+    ctx.accounts.collateral_config.debt = new_debt;
+    Ok(())
+}
+
+// ============================================================================
+// Account Validation Structs
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct AddCollateral<'info> {
+    /// The owner registering the collateral
+    #[account(
+        seeds = [b"user_role", owner.key().as_ref()],
+        bump = owner_role.bump,
+        constraint = owner_role.role == Role::Owner @ PusdError::Unauthorized
+    )]
+    pub owner_role: Account<'info, UserRole>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Configuration for the new collateral; also the vault authority
+    #[account(
+        init,
+        payer = owner,
+        space = CollateralConfig::LEN,
+        seeds = [b"collateral", collateral_mint.key().as_ref()],
+        bump
+    )]
+    pub collateral_config: Account<'info, CollateralConfig>,
+
+    /// The collateral mint being accepted
+    #[account(mint::token_program = collateral_token_program)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+
+    /// The PUSD mint issued against this collateral
+    #[account(mint::token_program = token_program)]
+    pub pusd_mint: InterfaceAccount<'info, Mint>,
+
+    /// Vault holding deposited collateral, owned by the config PDA
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"psm_vault", collateral_mint.key().as_ref()],
+        bump,
+        token::mint = collateral_mint,
+        token::authority = collateral_config,
+        token::token_program = collateral_token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Sunset tombstone; rejects the instruction once executed
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+
+    /// The token program owning the collateral mint (SPL Token or Token-2022)
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCollateral<'info> {
+    /// The owner updating the collateral
+    #[account(
+        seeds = [b"user_role", owner.key().as_ref()],
+        bump = owner_role.bump,
+        constraint = owner_role.role == Role::Owner @ PusdError::Unauthorized
+    )]
+    pub owner_role: Account<'info, UserRole>,
+
+    pub owner: Signer<'info>,
+
+    /// The collateral configuration being updated
+    #[account(
+        mut,
+        seeds = [b"collateral", collateral_config.collateral_mint.as_ref()],
+        bump = collateral_config.bump
+    )]
+    pub collateral_config: Account<'info, CollateralConfig>,
+
+    /// Sunset tombstone; rejects the instruction once executed
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SwapIn<'info> {
+    pub user: Signer<'info>,
+
+    /// Program state holding the fee configuration
+    #[account(seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Configuration of the collateral being deposited
+    #[account(
+        mut,
+        seeds = [b"collateral", collateral_mint.key().as_ref()],
+        bump = collateral_config.bump,
+        has_one = collateral_mint,
+        has_one = vault,
+        constraint = collateral_config.pusd_mint == mint.key() @ PusdError::InvalidConfig
+    )]
+    pub collateral_config: Account<'info, CollateralConfig>,
+
+    /// Price account for Feed/Pyth sources; omitted for fixed prices
+    /// CHECK: Must match collateral_config.oracle; parsed according to collateral_config.price_source
+    pub oracle: Option<UncheckedAccount<'info>>,
+
+    /// The collateral mint
+    #[account(mint::token_program = collateral_token_program)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+
+    /// Vault receiving the collateral
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// The user's collateral token account
+    #[account(
+        mut,
+        token::mint = collateral_mint,
+        token::authority = user,
+        token::token_program = collateral_token_program
+    )]
+    pub user_collateral: InterfaceAccount<'info, TokenAccount>,
+
+    /// The PUSD Token-2022 mint account
+    /// CHECK: Must match collateral_config.pusd_mint; validated by Token-2022 program
+    #[account(mut)]
+    pub mint: AccountInfo<'info>,
+
+    /// The token account receiving PUSD
+    /// CHECK: Validated by Token-2022 program
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+
+    /// The mint authority PDA controlled by this program
+    /// CHECK: PDA derived from "mint_authority" seed
+    #[account(seeds = [b"mint_authority"], bump)]
+    pub mint_authority: AccountInfo<'info>,
+
+    /// The treasury PDA that owns collected fees
+    /// CHECK: PDA derived from "treasury" seed
+    #[account(seeds = [b"treasury"], bump)]
+    pub treasury: AccountInfo<'info>,
+
+    /// The treasury's PUSD associated token account receiving fees
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// The token program owning the collateral mint
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct SwapOut<'info> {
+    pub user: Signer<'info>,
+
+    /// Program state holding the fee configuration
+    #[account(seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Configuration of the collateral being withdrawn
+    #[account(
+        mut,
+        seeds = [b"collateral", collateral_mint.key().as_ref()],
+        bump = collateral_config.bump,
+        has_one = collateral_mint,
+        has_one = vault,
+        constraint = collateral_config.pusd_mint == mint.key() @ PusdError::InvalidConfig
+    )]
+    pub collateral_config: Account<'info, CollateralConfig>,
+
+    /// Price account for Feed/Pyth sources; omitted for fixed prices
+    /// CHECK: Must match collateral_config.oracle; parsed according to collateral_config.price_source
+    pub oracle: Option<UncheckedAccount<'info>>,
+
+    /// The collateral mint
+    #[account(mint::token_program = collateral_token_program)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+
+    /// Vault releasing the collateral
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// The user's collateral token account
+    #[account(
+        mut,
+        token::mint = collateral_mint,
+        token::token_program = collateral_token_program
+    )]
+    pub user_collateral: InterfaceAccount<'info, TokenAccount>,
+
+    /// The PUSD Token-2022 mint account
+    /// CHECK: Must match collateral_config.pusd_mint; validated by Token-2022 program
+    #[account(mut)]
+    pub mint: AccountInfo<'info>,
+
+    /// The user's PUSD token account being burned from
+    /// CHECK: Validated by Token-2022 program (owner must be the signing user)
+    #[account(mut)]
+    pub user_pusd: AccountInfo<'info>,
+
+    /// The treasury PDA that owns collected fees
+    /// CHECK: PDA derived from "treasury" seed
+    #[account(seeds = [b"treasury"], bump)]
+    pub treasury: AccountInfo<'info>,
+
+    /// The treasury's PUSD associated token account receiving fees
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// The token program owning the collateral mint
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePriceFeed<'info> {
+    /// The owner pushing the price
+    #[account(
+        seeds = [b"user_role", owner.key().as_ref()],
+        bump = owner_role.bump,
+        constraint = owner_role.role == Role::Owner @ PusdError::Unauthorized
+    )]
+    pub owner_role: Account<'info, UserRole>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The collateral mint being priced
+    /// CHECK: Only used as a seed for the price feed
+    pub collateral_mint: AccountInfo<'info>,
+
+    /// The price feed for this collateral, created on first push
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = PriceFeed::LEN,
+        seeds = [b"price_feed", collateral_mint.key().as_ref()],
+        bump
+    )]
+    pub price_feed: Account<'info, PriceFeed>,
+
+    /// Sunset tombstone; rejects the instruction once executed
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -6,37 +6,374 @@ pub enum Role {
     Owner,           // ADMIN_ROLE / DEFAULT_ADMIN_ROLE in Solidity
     AuthorizedContract,  // AUTHORIZED_CONTRACT_ROLE in Solidity
     Operator,        // OPERATOR_ROLE in Solidity
+    Approver,        // confirms mint requests proposed by operators
+    Attestor,        // publishes proof-of-reserves attestations
+    RateManager,     // updates the rate of interest-bearing mints
+    Compliance,      // approves token accounts for confidential transfers
+    KycOfficer,      // thaws and re-freezes holder accounts in KYC mode
+    AuthorizedSigner, // signs off-chain mint permits (e.g. custody HSM key)
+    Guardian,        // emergency only: pause minting and revoke minters, never grant
+}
+
+// Lifecycle of a granted role
+// Pending roles become usable once role_active_time passes; Suspended roles
+// keep their account and configuration but fail every role check
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RoleStatus {
+    Pending,         // granted, waiting out the activation delay
+    Active,          // usable
+    Suspended,       // temporarily disabled by an Owner
 }
 
 // Account to store user roles
 // Matches Solidity BaseStorage mapping and role system
 #[account]
 pub struct UserRole {
+    pub version: u8,               // 1 byte - account layout version
     pub user: Pubkey,              // 32 bytes - the user's public key
-    pub role: Role,                // 1 byte - Owner, AuthorizedContract, or Operator
+    pub role: Role,                // 1 byte - Owner, AuthorizedContract, Operator, Approver, Attestor, RateManager, Compliance, KycOfficer, AuthorizedSigner, or Guardian
     pub bump: u8,                  // 1 byte - PDA bump seed
     pub role_active_time: i64,     // 8 bytes - timestamp when role becomes active (matches roleActiveTime in Solidity)
+    pub status: RoleStatus,        // 1 byte - Pending, Active, or Suspended
 }
 
 impl UserRole {
-    pub const LEN: usize = 8 + 32 + 1 + 1 + 8; // discriminator + user + role + bump + role_active_time
-}
+    pub const LEN: usize = 8 + 1 + 32 + 1 + 1 + 8 + 1; // discriminator + version + user + role + bump + role_active_time + status
+    pub const VERSION: u8 = 2;
+    pub const V1_LEN: usize = 8 + 32 + 1 + 1 + 8; // unversioned layout: discriminator + user + role + bump + role_active_time
 
-// Program data account structure for upgrade authority verification
-// This is owned by the BPF Loader Upgradeable program, not our program
-#[derive(Debug, AnchorSerialize, AnchorDeserialize)]
-pub struct UpgradeableData {
-    pub slot: u64,
-    pub upgrade_authority_address: Option<Pubkey>,
+    /// Returns the status at the given time; Pending roles past their activation time are Active
+    pub fn effective_status(&self, current_time: i64) -> RoleStatus {
+        match self.status {
+            RoleStatus::Pending if current_time >= self.role_active_time => RoleStatus::Active,
+            status => status,
+        }
+    }
+
+    /// Returns true if the user holds `role` and it is Active at the given time
+    pub fn has_active_role(&self, role: Role, current_time: i64) -> bool {
+        self.role == role && self.effective_status(current_time) == RoleStatus::Active
+    }
 }
 
 // Program state to track initialization
 #[account]
 pub struct ProgramState {
+    pub version: u8,               // 1 byte - account layout version
     pub is_initialized: bool,      // 1 byte - tracks if program has been initialized
     pub bump: u8,                  // 1 byte - PDA bump seed
+    pub direct_mint_disabled: bool, // 1 byte - when set, operators must go through request_mint
+    pub large_mint_threshold: u64, // 8 bytes - an operator's direct mints above this per window need approval (0 = no limit)
+    pub psm_fee_in_bps: u16,       // 2 bytes - fee on PSM swap_in, minted to the treasury
+    pub psm_fee_out_bps: u16,      // 2 bytes - fee on PSM swap_out, transferred to the treasury
+    pub redemption_fee_bps: u16,   // 2 bytes - fee on redeem, transferred to the treasury
+    pub kyc_required: bool,        // 1 byte - accounts start frozen and are thawed by KycOfficers
+    pub admin: Pubkey,             // 32 bytes - governance account (e.g. multisig) for post-init privileged actions
+    pub upgrade_authority_renounced: bool, // 1 byte - when set, the upgrade authority has no program privileges
+    pub paused: bool,              // 1 byte - set by a Guardian or Owner; blocks all minting
+    pub renounce_rent_recipient: Pubkey, // 32 bytes - receives rent of renounced roles (default = the holder)
+    pub mint: Pubkey,              // 32 bytes - canonical PUSD mint, recorded when its authority moves to the PDA
+    pub pending_admin: Pubkey,     // 32 bytes - proposed governance admin awaiting acceptance (default = none)
 }
 
 impl ProgramState {
-    pub const LEN: usize = 8 + 1 + 1; // discriminator + is_initialized + bump
+    pub const LEN: usize = 8 + 1 + 1 + 1 + 1 + 8 + 2 + 2 + 2 + 1 + 32 + 1 + 1 + 32 + 32 + 32; // discriminator + version + is_initialized + bump + direct_mint_disabled + large_mint_threshold + psm_fee_in_bps + psm_fee_out_bps + redemption_fee_bps + kyc_required + admin + upgrade_authority_renounced + paused + renounce_rent_recipient + mint + pending_admin
+    pub const VERSION: u8 = 2;
+    pub const V1_LEN: usize = 8 + 1 + 1; // unversioned layout: discriminator + is_initialized + bump
+
+    /// Returns the account that receives the rent of a renounced role
+    pub fn renounce_rent_recipient_for(&self, holder: Pubkey) -> Pubkey {
+        if self.renounce_rent_recipient == Pubkey::default() {
+            holder
+        } else {
+            self.renounce_rent_recipient
+        }
+    }
+
+    /// Returns true if the amount is above the large mint threshold
+    pub fn requires_approval(&self, amount: u64) -> bool {
+        self.large_mint_threshold != 0 && amount > self.large_mint_threshold
+    }
+}
+
+// Receipt for an idempotent mint keyed by an off-chain reference
+// The PDA is seeded by the calling contract and the reference, so a retried
+// mint cannot execute twice and no contract can consume another's references.
+// Receipts can be closed once their reference is too old to be minted again
+#[account]
+pub struct MintReceipt {
+    pub reference: [u8; 32],       // 32 bytes - off-chain reference (e.g. bank wire ID hash)
+    pub minter: Pubkey,            // 32 bytes - signer that executed the mint
+    pub recipient: Pubkey,         // 32 bytes - recipient token account
+    pub amount: u64,               // 8 bytes - amount minted
+    pub slot: u64,                 // 8 bytes - slot in which the mint executed
+    pub bump: u8,                  // 1 byte - PDA bump seed
+}
+
+impl MintReceipt {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 1; // discriminator + reference + minter + recipient + amount + slot + bump
+}
+
+// Direct mints of one operator within the current large mint window
+// Summed against large_mint_threshold so a large mint cannot be split into smaller calls
+#[account]
+pub struct OperatorMintWindow {
+    pub operator: Pubkey,          // 32 bytes - operator whose mints are tracked
+    pub window_start: i64,         // 8 bytes - timestamp the current window started
+    pub minted: u64,               // 8 bytes - minted directly since window_start
+    pub bump: u8,                  // 1 byte - PDA bump seed
+}
+
+impl OperatorMintWindow {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 1; // discriminator + operator + window_start + minted + bump
+
+    /// Adds a mint to the window, starting a new window once LARGE_MINT_WINDOW has passed
+    /// Returns the total minted in the window including this mint
+    pub fn record(&mut self, amount: u64, current_time: i64) -> Result<u64> {
+        if current_time.saturating_sub(self.window_start) >= crate::LARGE_MINT_WINDOW {
+            self.window_start = current_time;
+            self.minted = 0;
+        }
+        self.minted = self.minted.checked_add(amount).ok_or(crate::PusdError::MathOverflow)?;
+        Ok(self.minted)
+    }
+}
+
+// Pending mint proposed by an operator, executed by a distinct approver
+#[account]
+pub struct MintRequest {
+    pub requester: Pubkey,         // 32 bytes - operator that proposed the mint
+    pub mint: Pubkey,              // 32 bytes - Token-2022 mint to issue from
+    pub recipient: Pubkey,         // 32 bytes - recipient token account
+    pub amount: u64,               // 8 bytes - amount to mint
+    pub request_id: u64,           // 8 bytes - requester-chosen identifier
+    pub expiry: i64,               // 8 bytes - timestamp after which the request can no longer be approved
+    pub bump: u8,                  // 1 byte - PDA bump seed
+}
+
+impl MintRequest {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 1; // discriminator + requester + mint + recipient + amount + request_id + expiry + bump
+}
+
+// Latest proof-of-reserves attestation for a mint
+// Fiat-backed minting is refused when it would push supply above reserve_amount or the attestation is stale
+#[account]
+pub struct ReserveAttestation {
+    pub mint: Pubkey,              // 32 bytes - mint whose supply is backed by these reserves
+    pub attestor: Pubkey,          // 32 bytes - attestor that published the latest figures
+    pub reserve_amount: u64,       // 8 bytes - attested reserves in mint base units
+    pub timestamp: i64,            // 8 bytes - when the attestation was published
+    pub staleness_window: i64,     // 8 bytes - maximum attestation age in seconds before minting is blocked
+    pub report_hash: [u8; 32],     // 32 bytes - hash of the auditor report
+    pub report_uri: String,        // 4 + MAX_REPORT_URI_LEN bytes - where the report is published
+    pub bump: u8,                  // 1 byte - PDA bump seed
+}
+
+impl ReserveAttestation {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 8 + 32 + (4 + crate::MAX_REPORT_URI_LEN) + 1; // discriminator + mint + attestor + reserve_amount + timestamp + staleness_window + report_hash + report_uri + bump
+}
+
+// Peg stability module configuration for one accepted collateral mint
+// The config PDA is also the authority of the collateral vault
+#[account]
+pub struct CollateralConfig {
+    pub collateral_mint: Pubkey,   // 32 bytes - accepted collateral mint
+    pub pusd_mint: Pubkey,         // 32 bytes - PUSD mint issued against this collateral
+    pub vault: Pubkey,             // 32 bytes - PDA-owned token account holding the collateral
+    pub debt: u64,                 // 8 bytes - PUSD currently outstanding against this collateral
+    pub debt_ceiling: u64,         // 8 bytes - maximum PUSD that may be outstanding
+    pub enabled: bool,             // 1 byte - swaps are rejected when false
+    pub bump: u8,                  // 1 byte - PDA bump seed
+    pub collateral_decimals: u8,   // 1 byte - decimals of the collateral mint
+    pub pusd_decimals: u8,         // 1 byte - decimals of the PUSD mint
+    pub price: u64,                // 8 bytes - fixed USD price of one collateral unit, scaled by PRICE_SCALE
+    pub price_source: PriceSourceKind, // 1 byte - where swaps read the collateral price from
+    pub oracle: Pubkey,            // 32 bytes - PriceFeed or Pyth account (unused for Fixed)
+    pub max_price_age: i64,        // 8 bytes - maximum oracle price age in seconds
+    pub max_confidence_bps: u16,   // 2 bytes - maximum oracle confidence interval relative to price
+}
+
+impl CollateralConfig {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 1 + 1 + 1 + 1 + 8 + 1 + 32 + 8 + 2; // discriminator + collateral_mint + pusd_mint + vault + debt + debt_ceiling + enabled + bump + collateral_decimals + pusd_decimals + price + price_source + oracle + max_price_age + max_confidence_bps
+}
+
+// Where a collateral's USD price comes from
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PriceSourceKind {
+    Fixed,           // CollateralConfig.price
+    Feed,            // Owner-pushed PriceFeed account
+    Pyth,            // Pyth-style price account
+}
+
+// Owner-pushed price for a collateral mint
+#[account]
+pub struct PriceFeed {
+    pub collateral_mint: Pubkey,   // 32 bytes - collateral this feed prices
+    pub price: u64,                // 8 bytes - USD price scaled by PRICE_SCALE
+    pub confidence: u64,           // 8 bytes - confidence interval scaled by PRICE_SCALE
+    pub publish_time: i64,         // 8 bytes - when the price was pushed
+    pub bump: u8,                  // 1 byte - PDA bump seed
+}
+
+impl PriceFeed {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 1; // discriminator + collateral_mint + price + confidence + publish_time + bump
+}
+
+// Limits on how a RateManager may move an interest-bearing mint's rate
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RateBounds {
+    pub min_rate: i16,             // 2 bytes - lowest allowed rate in bps
+    pub max_rate: i16,             // 2 bytes - highest allowed rate in bps
+    pub max_rate_change: u16,      // 2 bytes - largest change per update in bps
+    pub min_update_interval: i64,  // 8 bytes - minimum seconds between updates
+}
+
+impl RateBounds {
+    pub const LEN: usize = 2 + 2 + 2 + 8;
+
+    /// Rejects inverted ranges and negative intervals
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.min_rate <= self.max_rate && self.min_update_interval >= 0,
+            crate::PusdError::InvalidConfig
+        );
+        Ok(())
+    }
+}
+
+// Rate configuration of an interest-bearing mint
+#[account]
+pub struct RateConfig {
+    pub mint: Pubkey,              // 32 bytes - the interest-bearing mint
+    pub pusd_mint: Pubkey,         // 32 bytes - PUSD mint backing the yield token
+    pub reserve: Pubkey,           // 32 bytes - PUSD token account backing issued yield tokens
+    pub bounds: RateBounds,        // 14 bytes - limits on rate updates
+    pub current_rate: i16,         // 2 bytes - rate last set on the mint in bps
+    pub last_update: i64,          // 8 bytes - when the rate was last set
+    pub bump: u8,                  // 1 byte - PDA bump seed
+}
+
+impl RateConfig {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + RateBounds::LEN + 2 + 8 + 1; // discriminator + mint + pusd_mint + reserve + bounds + current_rate + last_update + bump
+}
+
+// Staking vault that issues shares against deposited PUSD
+// The PDA owns the vault token account and is the share mint authority
+#[account]
+pub struct StakingVault {
+    pub pusd_mint: Pubkey,         // 32 bytes - the staked mint
+    pub share_mint: Pubkey,        // 32 bytes - the share mint issued to depositors
+    pub vault_token: Pubkey,       // 32 bytes - token account holding staked PUSD
+    pub total_assets: u64,         // 8 bytes - PUSD owned by shareholders (donations excluded)
+    pub bump: u8,                  // 1 byte - PDA bump seed
+}
+
+impl StakingVault {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 1; // discriminator + pusd_mint + share_mint + vault_token + total_assets + bump
+}
+
+// KYC status of a token account in allowlist mode
+// Kept after revocation so the approval history stays on-chain
+#[account]
+pub struct KycRecord {
+    pub token_account: Pubkey,     // 32 bytes - the thawed token account
+    pub holder: Pubkey,            // 32 bytes - owner of the token account
+    pub mint: Pubkey,              // 32 bytes - mint of the token account
+    pub officer: Pubkey,           // 32 bytes - KycOfficer of the latest decision
+    pub approved: bool,            // 1 byte - false once revoked
+    pub updated_at: i64,           // 8 bytes - unix timestamp of the latest decision
+    pub bump: u8,                  // 1 byte - PDA bump seed
+}
+
+impl KycRecord {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 1 + 8 + 1; // discriminator + token_account + holder + mint + officer + approved + updated_at + bump
+}
+
+// Bridge configuration: local chain ID, guardian set and outbound nonce
+#[account]
+pub struct BridgeConfig {
+    pub local_chain_id: u16,       // 2 bytes - this chain's ID in bridge messages
+    pub threshold: u8,             // 1 byte - guardian signatures required per message
+    pub outbound_nonce: u64,       // 8 bytes - nonce of the next outbound message
+    pub guardians: Vec<Pubkey>,    // 4 + 32 * MAX_BRIDGE_GUARDIANS bytes - guardian ed25519 keys
+    pub bump: u8,                  // 1 byte - PDA bump seed
+}
+
+impl BridgeConfig {
+    pub const LEN: usize = 8 + 2 + 1 + 8 + 4 + 32 * crate::MAX_BRIDGE_GUARDIANS + 1; // discriminator + local_chain_id + threshold + outbound_nonce + guardians + bump
+}
+
+// Outbound bridge message recorded when PUSD is burned on this chain
+#[account]
+pub struct OutboundBridgeMessage {
+    pub nonce: u64,                // 8 bytes - unique outbound nonce
+    pub mint: Pubkey,              // 32 bytes - mint the tokens were burned from
+    pub sender: Pubkey,            // 32 bytes - holder that burned the tokens
+    pub destination_chain: u16,    // 2 bytes - chain the tokens are minted on
+    pub recipient: [u8; 32],       // 32 bytes - recipient address on the destination chain
+    pub amount: u64,               // 8 bytes - amount burned
+    pub timestamp: i64,            // 8 bytes - unix timestamp of the burn
+    pub bump: u8,                  // 1 byte - PDA bump seed
+}
+
+impl OutboundBridgeMessage {
+    pub const LEN: usize = 8 + 8 + 32 + 32 + 2 + 32 + 8 + 8 + 1; // discriminator + nonce + mint + sender + destination_chain + recipient + amount + timestamp + bump
+}
+
+// Inbound bridge message; its existence marks (source_chain, nonce) as consumed
+#[account]
+pub struct InboundBridgeMessage {
+    pub source_chain: u16,         // 2 bytes - chain the tokens were burned on
+    pub nonce: u64,                // 8 bytes - source chain message nonce
+    pub recipient: Pubkey,         // 32 bytes - recipient token account
+    pub amount: u64,               // 8 bytes - amount minted
+    pub timestamp: i64,            // 8 bytes - unix timestamp of the mint
+    pub bump: u8,                  // 1 byte - PDA bump seed
+}
+
+impl InboundBridgeMessage {
+    pub const LEN: usize = 8 + 2 + 8 + 32 + 8 + 8 + 1; // discriminator + source_chain + nonce + recipient + amount + timestamp + bump
+}
+
+// Marks a permit nonce as used by its signer; existence prevents replays
+#[account]
+pub struct PermitNonce {
+    pub signer: Pubkey,            // 32 bytes - AuthorizedSigner that signed the permit
+    pub nonce: u64,                // 8 bytes - permit nonce
+    pub recipient: Pubkey,         // 32 bytes - recipient token account
+    pub amount: u64,               // 8 bytes - amount minted
+    pub bump: u8,                  // 1 byte - PDA bump seed
+}
+
+impl PermitNonce {
+    pub const LEN: usize = 8 + 32 + 8 + 32 + 8 + 1; // discriminator + signer + nonce + recipient + amount + bump
+}
+
+// Registration of a program-derived AuthorizedContract authority
+// The authority is the PDA of (seeds, program_id); only program_id can sign for it
+#[account]
+pub struct ContractRegistration {
+    pub authority: Pubkey,         // 32 bytes - PDA holding the AuthorizedContract role
+    pub program_id: Pubkey,        // 32 bytes - program that signs for the authority
+    pub authority_bump: u8,        // 1 byte - bump of the authority PDA
+    pub bump: u8,                  // 1 byte - PDA bump seed
+}
+
+impl ContractRegistration {
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 1; // discriminator + authority + program_id + authority_bump + bump
+}
+
+// Sunset schedule; once executed it is the program's permanent tombstone
+#[account]
+pub struct SunsetState {
+    pub owner: Pubkey,             // 32 bytes - Owner that scheduled the sunset; receives reclaimed rent
+    pub eta: i64,                  // 8 bytes - earliest execution timestamp
+    pub executed: bool,            // 1 byte - irreversible once set
+    pub executed_at: i64,          // 8 bytes - unix timestamp of execution
+    pub bump: u8,                  // 1 byte - PDA bump seed
+    pub mint: Pubkey,              // 32 bytes - PUSD mint retired at execution; redemptions are pinned to it
+}
+
+impl SunsetState {
+    pub const LEN: usize = 8 + 32 + 8 + 1 + 8 + 1 + 32; // discriminator + owner + eta + executed + executed_at + bump + mint
 }
@@ -0,0 +1,407 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::Discriminator;
+use anchor_spl::token_interface::{
+    burn,
+    set_authority,
+    transfer_checked,
+    Burn,
+    Mint,
+    SetAuthority,
+    Token2022,
+    TokenAccount,
+    TokenInterface,
+    TransferChecked,
+    spl_token_2022::instruction::AuthorityType,
+};
+
+use crate::*;
+use crate::math::{mul_div, Rounding};
+
+// ============================================================================
+// Sunset
+// An Owner schedules the sunset; after SUNSET_TIMELOCK an Owner executes it
+// once the treasury has been emptied. Execution removes the mint authority
+// (None), closes ProgramState and the executing Owner's role, and marks the
+// SunsetState PDA as a permanent tombstone recording the retired mint.
+// Instructions that read ProgramState fail once it is closed; every other
+// instruction checks the tombstone with require_not_sunset!, and initialize
+// refuses to run while it exists. Leftover role accounts can be swept by
+// anyone, as can expired mint requests. Holders keep two fee-free exits that
+// need no ProgramState: burning for off-chain redemption, and a pro-rata share
+// of a PSM collateral vault. Staked PUSD can still be unstaked.
+// ============================================================================
+
+/// Fails once the sunset has executed; a pending schedule does not block
+pub fn check_not_sunset(sunset_state: &AccountInfo) -> Result<()> {
+    if sunset_state.data_is_empty() {
+        return Ok(());
+    }
+    require_keys_eq!(*sunset_state.owner, crate::ID, PusdError::ProgramSunset);
+    let sunset_state = SunsetState::try_deserialize(&mut &sunset_state.try_borrow_data()?[..])?;
+    require!(!sunset_state.executed, PusdError::ProgramSunset);
+    Ok(())
+}
+
+/// Start the sunset timelock
+pub(crate) fn schedule_sunset(ctx: Context<ScheduleSunset>) -> Result<()> {
+    // Verify the caller has Owner role
+    require_role!(ctx.accounts.owner_role, Role::Owner);
+
+    let sunset_state = &mut ctx.accounts.sunset_state;
+    sunset_state.owner = ctx.accounts.owner.key();
+    sunset_state.eta = Clock::get()?.unix_timestamp
+        .checked_add(SUNSET_TIMELOCK)
+        .ok_or(PusdError::MathOverflow)?;
+    sunset_state.executed = false;
+    sunset_state.executed_at = 0;
+    sunset_state.bump = ctx.bumps.sunset_state;
+
+    msg!("Sunset scheduled by {} - executable at {}", sunset_state.owner, sunset_state.eta);
+    Ok(())
+}
+
+/// Cancel a pending sunset
+pub(crate) fn cancel_sunset(ctx: Context<CancelSunset>) -> Result<()> {
+    // Verify the caller has Owner role
+    require_role!(ctx.accounts.owner_role, Role::Owner);
+
+    require!(!ctx.accounts.sunset_state.executed, PusdError::ProgramSunset);
+
+    // The account will be closed automatically by Anchor's close constraint
+    msg!("Sunset cancelled by {}", ctx.accounts.owner.key());
+    Ok(())
+}
+
+/// Execute the sunset once the timelock has elapsed
+pub(crate) fn execute_sunset(ctx: Context<ExecuteSunset>) -> Result<()> {
+    // Verify the caller has Owner role
+    require_role!(ctx.accounts.owner_role, Role::Owner);
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let sunset_state = &mut ctx.accounts.sunset_state;
+    require!(!sunset_state.executed, PusdError::ProgramSunset);
+    require!(current_time >= sunset_state.eta, PusdError::SunsetNotReady);
+
+    // Collected fees cannot be withdrawn without an Owner, so they go first
+    require!(ctx.accounts.treasury_token.amount == 0, PusdError::TreasuryNotEmpty);
+
+    // Permanently remove the mint authority
+    let seeds = &[b"mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
+    let signer_seeds = &[&seeds[..]];
+    let cpi_accounts = SetAuthority {
+        current_authority: ctx.accounts.mint_authority.to_account_info(),
+        account_or_mint: ctx.accounts.mint.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds
+    );
+    set_authority(cpi_ctx, AuthorityType::MintTokens, None)?;
+
+    sunset_state.owner = ctx.accounts.owner.key();
+    sunset_state.executed = true;
+    sunset_state.executed_at = current_time;
+    sunset_state.mint = ctx.accounts.mint.key();
+
+    // ProgramState and the Owner role are closed by Anchor's close constraint
+    msg!("Program sunset executed by {} - minting permanently disabled", sunset_state.owner);
+    Ok(())
+}
+
+/// Close role accounts (remaining_accounts) left over after sunset
+pub(crate) fn close_roles_after_sunset<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CloseRolesAfterSunset<'info>>
+) -> Result<()> {
+    require!(!ctx.remaining_accounts.is_empty(), PusdError::EmptyBatch);
+
+    let rent_recipient = &ctx.accounts.rent_recipient;
+    for account in ctx.remaining_accounts.iter() {
+        require!(
+            account.owner == ctx.program_id && account.is_writable,
+            PusdError::UnsupportedAccountLayout
+        );
+        require!(
+            account.try_borrow_data()?.starts_with(UserRole::DISCRIMINATOR),
+            PusdError::UnsupportedAccountLayout
+        );
+
+        let lamports = account.lamports();
+        **rent_recipient.try_borrow_mut_lamports()? = rent_recipient
+            .lamports()
+            .checked_add(lamports)
+            .ok_or(PusdError::MathOverflow)?;
+        **account.try_borrow_mut_lamports()? = 0;
+        account.assign(&system_program::ID);
+        account.resize(0)?;
+    }
+
+    msg!("Closed {} role accounts after sunset", ctx.remaining_accounts.len());
+    Ok(())
+}
+
+/// Burn PUSD for off-chain redemption once the program is sunset
+pub(crate) fn redeem_after_sunset(ctx: Context<RedeemAfterSunset>, amount: u64) -> Result<()> {
+    require!(amount > 0, PusdError::InvalidAmount);
+
+    let cpi_accounts = Burn {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.holder_token.to_account_info(),
+        authority: ctx.accounts.holder.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    burn(cpi_ctx, amount)?;
+
+    emit!(RedemptionRequested {
+        mint: ctx.accounts.mint.key(),
+        holder: ctx.accounts.holder.key(),
+        amount,
+        fee: 0,
+        burned: amount,
+    });
+    Ok(())
+}
+
+/// Burn PUSD for its pro-rata share of a PSM collateral vault once the program is sunset
+/// Prices and fees live in ProgramState and oracles that may no longer be maintained, so the
+/// vault is shared among the PUSD still outstanding against it instead
+pub(crate) fn swap_out_after_sunset(ctx: Context<SwapOutAfterSunset>, amount: u64) -> Result<()> {
+    let config = &ctx.accounts.collateral_config;
+    require!(amount > 0, PusdError::InvalidAmount);
+    let new_debt = config.debt.checked_sub(amount).ok_or(PusdError::InsufficientPsmLiquidity)?;
+
+    let payout = mul_div(ctx.accounts.vault.amount, amount, config.debt, Rounding::Down)?;
+    require!(payout > 0, PusdError::InvalidAmount);
+
+    msg!("PSM exit after sunset: {} PUSD for {} collateral", amount, payout);
+
+    let cpi_accounts = Burn {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.user_pusd.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    burn(cpi_ctx, amount)?;
+
+    // Release collateral from the vault, signed by the config PDA
+    let collateral_mint_key = config.collateral_mint;
+    let seeds = &[b"collateral".as_ref(), collateral_mint_key.as_ref(), &[config.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.vault.to_account_info(),
+        mint: ctx.accounts.collateral_mint.to_account_info(),
+        to: ctx.accounts.user_collateral.to_account_info(),
+        authority: ctx.accounts.collateral_config.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.collateral_token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds
+    );
+    transfer_checked(cpi_ctx, payout, ctx.accounts.collateral_mint.decimals)?;
+
+    ctx.accounts.collateral_config.debt = new_debt;
+    Ok(())
+}
+
+// ============================================================================
+// Account Validation Structs
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct ScheduleSunset<'info> {
+    /// The owner scheduling the sunset
+    #[account(
+        seeds = [b"user_role", owner.key().as_ref()],
+        bump = owner_role.bump,
+        constraint = owner_role.role == Role::Owner @ PusdError::Unauthorized
+    )]
+    pub owner_role: Account<'info, UserRole>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Sunset schedule; `init` fails if one is already pending or executed
+    #[account(
+        init,
+        payer = owner,
+        space = SunsetState::LEN,
+        seeds = [b"sunset"],
+        bump
+    )]
+    pub sunset_state: Account<'info, SunsetState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelSunset<'info> {
+    /// The owner cancelling the sunset
+    #[account(
+        seeds = [b"user_role", owner.key().as_ref()],
+        bump = owner_role.bump,
+        constraint = owner_role.role == Role::Owner @ PusdError::Unauthorized
+    )]
+    pub owner_role: Account<'info, UserRole>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The pending sunset schedule
+    #[account(mut, close = owner, seeds = [b"sunset"], bump = sunset_state.bump)]
+    pub sunset_state: Account<'info, SunsetState>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteSunset<'info> {
+    /// The owner executing the sunset; the role is closed
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"user_role", owner.key().as_ref()],
+        bump = owner_role.bump,
+        constraint = owner_role.role == Role::Owner @ PusdError::Unauthorized
+    )]
+    pub owner_role: Account<'info, UserRole>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Program state; closed and its rent reclaimed
+    #[account(mut, close = owner, seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Sunset schedule, kept as the tombstone
+    #[account(mut, seeds = [b"sunset"], bump = sunset_state.bump)]
+    pub sunset_state: Account<'info, SunsetState>,
+
+    /// The canonical PUSD Token-2022 mint
+    /// CHECK: Must match program_state.mint; validated by Token-2022 program
+    #[account(mut, address = program_state.mint @ PusdError::InvalidMint)]
+    pub mint: AccountInfo<'info>,
+
+    /// The mint authority PDA controlled by this program
+    /// CHECK: PDA derived from "mint_authority" seed
+    #[account(seeds = [b"mint_authority"], bump)]
+    pub mint_authority: AccountInfo<'info>,
+
+    /// The treasury PDA that owns collected fees
+    /// CHECK: PDA derived from "treasury" seed
+    #[account(seeds = [b"treasury"], bump)]
+    pub treasury: AccountInfo<'info>,
+
+    /// The treasury's PUSD associated token account; must be empty
+    #[account(
+        associated_token::mint = mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct CloseRolesAfterSunset<'info> {
+    /// Executed sunset tombstone
+    #[account(
+        seeds = [b"sunset"],
+        bump = sunset_state.bump,
+        constraint = sunset_state.executed @ PusdError::SunsetNotExecuted
+    )]
+    pub sunset_state: Account<'info, SunsetState>,
+
+    /// The Owner that executed the sunset, receiving reclaimed rent
+    /// CHECK: Must match the owner recorded in the tombstone
+    #[account(mut, address = sunset_state.owner)]
+    pub rent_recipient: AccountInfo<'info>,
+    // remaining_accounts: UserRole accounts to close (writable)
+}
+
+#[derive(Accounts)]
+pub struct RedeemAfterSunset<'info> {
+    pub holder: Signer<'info>,
+
+    /// Executed sunset tombstone
+    #[account(
+        seeds = [b"sunset"],
+        bump = sunset_state.bump,
+        constraint = sunset_state.executed @ PusdError::SunsetNotExecuted
+    )]
+    pub sunset_state: Account<'info, SunsetState>,
+
+    /// The PUSD Token-2022 mint retired by the sunset
+    #[account(
+        mut,
+        address = sunset_state.mint @ PusdError::InvalidMint,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The holder's PUSD token account
+    /// CHECK: Validated by Token-2022 program (owner must be the signing holder)
+    #[account(mut)]
+    pub holder_token: AccountInfo<'info>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct SwapOutAfterSunset<'info> {
+    pub user: Signer<'info>,
+
+    /// Executed sunset tombstone
+    #[account(
+        seeds = [b"sunset"],
+        bump = sunset_state.bump,
+        constraint = sunset_state.executed @ PusdError::SunsetNotExecuted
+    )]
+    pub sunset_state: Account<'info, SunsetState>,
+
+    /// Configuration of the collateral being withdrawn
+    #[account(
+        mut,
+        seeds = [b"collateral", collateral_mint.key().as_ref()],
+        bump = collateral_config.bump,
+        has_one = collateral_mint,
+        has_one = vault,
+        constraint = collateral_config.pusd_mint == mint.key() @ PusdError::InvalidConfig
+    )]
+    pub collateral_config: Account<'info, CollateralConfig>,
+
+    /// The collateral mint
+    #[account(mint::token_program = collateral_token_program)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+
+    /// Vault releasing the collateral
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// The user's collateral token account
+    #[account(
+        mut,
+        token::mint = collateral_mint,
+        token::token_program = collateral_token_program
+    )]
+    pub user_collateral: InterfaceAccount<'info, TokenAccount>,
+
+    /// The PUSD Token-2022 mint retired by the sunset
+    /// CHECK: Must match sunset_state.mint; validated by Token-2022 program
+    #[account(mut, address = sunset_state.mint @ PusdError::InvalidMint)]
+    pub mint: AccountInfo<'info>,
+
+    /// The user's PUSD token account being burned from
+    /// CHECK: Validated by Token-2022 program (owner must be the signing user)
+    #[account(mut)]
+    pub user_pusd: AccountInfo<'info>,
+
+    /// The token program owning the collateral mint
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+}
@@ -0,0 +1,245 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    harvest_withheld_tokens_to_mint,
+    set_authority,
+    transfer_fee_set,
+    withdraw_withheld_tokens_from_mint,
+    HarvestWithheldTokensToMint,
+    SetAuthority,
+    Token2022,
+    TokenAccount,
+    TransferFeeSetTransferFee,
+    WithdrawWithheldTokensFromMint,
+    spl_token_2022::instruction::AuthorityType,
+};
+
+use crate::*;
+
+// ============================================================================
+// Token-2022 TransferFee extension management
+// The mint_authority PDA doubles as transfer-fee config authority and
+// withdraw-withheld authority so fee operations never depend on a human key.
+// Every instruction here is Owner-gated.
+// ============================================================================
+
+/// Hand the transfer-fee config and withdraw-withheld authorities to the program PDA
+pub(crate) fn transfer_fee_authorities_to_pda(ctx: Context<TransferFeeAuthoritiesToPda>) -> Result<()> {
+    require_not_sunset!(ctx.accounts.sunset_state);
+
+    // Verify the caller has Owner role
+    require_role!(ctx.accounts.owner_role, Role::Owner);
+
+    let pda = ctx.accounts.mint_authority.key();
+
+    for authority_type in [AuthorityType::TransferFeeConfig, AuthorityType::WithheldWithdraw] {
+        let cpi_accounts = SetAuthority {
+            current_authority: ctx.accounts.current_authority.to_account_info(),
+            account_or_mint: ctx.accounts.mint.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        set_authority(cpi_ctx, authority_type, Some(pda))?;
+    }
+
+    msg!("Transfer fee authorities transferred to PDA: {}", pda);
+    Ok(())
+}
+
+/// Update the mint's transfer fee, signed by the program PDA
+pub(crate) fn set_transfer_fee(
+    ctx: Context<ManageTransferFee>,
+    transfer_fee_basis_points: u16,
+    maximum_fee: u64
+) -> Result<()> {
+    require_not_sunset!(ctx.accounts.sunset_state);
+
+    // Verify the caller has Owner role
+    require_role!(ctx.accounts.owner_role, Role::Owner);
+
+    require!(transfer_fee_basis_points <= MAX_FEE_BPS, PusdError::FeeTooHigh);
+
+    let seeds = &[b"mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = TransferFeeSetTransferFee {
+        token_program_id: ctx.accounts.token_program.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        authority: ctx.accounts.mint_authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds
+    );
+    transfer_fee_set(cpi_ctx, transfer_fee_basis_points, maximum_fee)?;
+
+    msg!(
+        "Transfer fee set to {} bps (max {}) - takes effect after two epochs",
+        transfer_fee_basis_points,
+        maximum_fee
+    );
+    Ok(())
+}
+
+/// Move withheld fees from token accounts (remaining_accounts) into the mint
+pub(crate) fn harvest_withheld_to_mint<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ManageTransferFee<'info>>
+) -> Result<()> {
+    require_not_sunset!(ctx.accounts.sunset_state);
+
+    // Verify the caller has Owner role
+    require_role!(ctx.accounts.owner_role, Role::Owner);
+
+    require!(!ctx.remaining_accounts.is_empty(), PusdError::EmptyBatch);
+
+    let cpi_accounts = HarvestWithheldTokensToMint {
+        token_program_id: ctx.accounts.token_program.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    harvest_withheld_tokens_to_mint(cpi_ctx, ctx.remaining_accounts.to_vec())?;
+
+    msg!("Harvested withheld fees from {} accounts", ctx.remaining_accounts.len());
+    Ok(())
+}
+
+/// Withdraw fees withheld in the mint to the treasury, signed by the program PDA
+pub(crate) fn withdraw_withheld_to_treasury(ctx: Context<WithdrawWithheldToTreasury>) -> Result<()> {
+    require_not_sunset!(ctx.accounts.sunset_state);
+
+    // Verify the caller has Owner role
+    require_role!(ctx.accounts.owner_role, Role::Owner);
+
+    let seeds = &[b"mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = WithdrawWithheldTokensFromMint {
+        token_program_id: ctx.accounts.token_program.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        destination: ctx.accounts.treasury_token.to_account_info(),
+        authority: ctx.accounts.mint_authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds
+    );
+    withdraw_withheld_tokens_from_mint(cpi_ctx)?;
+
+    msg!("Withdrew withheld fees to treasury {}", ctx.accounts.treasury_token.key());
+    Ok(())
+}
+
+// ============================================================================
+// Account Validation Structs
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct TransferFeeAuthoritiesToPda<'info> {
+    /// The owner authorizing the handover
+    #[account(
+        seeds = [b"user_role", owner.key().as_ref()],
+        bump = owner_role.bump,
+        constraint = owner_role.role == Role::Owner @ PusdError::Unauthorized
+    )]
+    pub owner_role: Account<'info, UserRole>,
+
+    pub owner: Signer<'info>,
+
+    /// The current transfer-fee config and withdraw-withheld authority
+    pub current_authority: Signer<'info>,
+
+    /// The Token-2022 mint with the TransferFee extension
+    /// CHECK: Validated by Token-2022 program
+    #[account(mut)]
+    pub mint: AccountInfo<'info>,
+
+    /// The PDA that becomes the new authority
+    /// CHECK: PDA derived from "mint_authority" seed
+    #[account(seeds = [b"mint_authority"], bump)]
+    pub mint_authority: AccountInfo<'info>,
+
+    /// Sunset tombstone; rejects the instruction once executed
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct ManageTransferFee<'info> {
+    /// The owner managing transfer fees
+    #[account(
+        seeds = [b"user_role", owner.key().as_ref()],
+        bump = owner_role.bump,
+        constraint = owner_role.role == Role::Owner @ PusdError::Unauthorized
+    )]
+    pub owner_role: Account<'info, UserRole>,
+
+    pub owner: Signer<'info>,
+
+    /// The Token-2022 mint with the TransferFee extension
+    /// CHECK: Validated by Token-2022 program
+    #[account(mut)]
+    pub mint: AccountInfo<'info>,
+
+    /// The PDA holding the transfer-fee config authority
+    /// CHECK: PDA derived from "mint_authority" seed
+    #[account(seeds = [b"mint_authority"], bump)]
+    pub mint_authority: AccountInfo<'info>,
+
+    /// Sunset tombstone; rejects the instruction once executed
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+    // remaining_accounts (harvest only): token accounts holding withheld fees (writable)
+}
+
+#[derive(Accounts)]
+pub struct WithdrawWithheldToTreasury<'info> {
+    /// The owner withdrawing withheld fees
+    #[account(
+        seeds = [b"user_role", owner.key().as_ref()],
+        bump = owner_role.bump,
+        constraint = owner_role.role == Role::Owner @ PusdError::Unauthorized
+    )]
+    pub owner_role: Account<'info, UserRole>,
+
+    pub owner: Signer<'info>,
+
+    /// The Token-2022 mint with the TransferFee extension
+    /// CHECK: Validated by Token-2022 program
+    #[account(mut)]
+    pub mint: AccountInfo<'info>,
+
+    /// The PDA holding the withdraw-withheld authority
+    /// CHECK: PDA derived from "mint_authority" seed
+    #[account(seeds = [b"mint_authority"], bump)]
+    pub mint_authority: AccountInfo<'info>,
+
+    /// The treasury PDA that owns collected fees
+    /// CHECK: PDA derived from "treasury" seed
+    #[account(seeds = [b"treasury"], bump)]
+    pub treasury: AccountInfo<'info>,
+
+    /// The treasury's PUSD associated token account receiving the withheld fees
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// Sunset tombstone; rejects the instruction once executed
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+}
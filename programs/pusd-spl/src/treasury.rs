@@ -0,0 +1,201 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    burn,
+    transfer_checked,
+    Burn,
+    Mint,
+    Token2022,
+    TokenAccount,
+    TransferChecked,
+};
+
+use crate::*;
+use crate::math::split_fee;
+
+// ============================================================================
+// Treasury
+// Fees from redemptions and PSM swaps accrue in the PUSD associated token
+// account of the "treasury" PDA. Only the Owner can withdraw them.
+// ============================================================================
+
+/// Burn PUSD for off-chain redemption after moving the fee to the treasury
+pub(crate) fn redeem(ctx: Context<Redeem>, amount: u64) -> Result<()> {
+    require!(amount > 0, PusdError::InvalidAmount);
+
+    let (fee, burned) = split_fee(amount, ctx.accounts.program_state.redemption_fee_bps)?;
+    require!(burned > 0, PusdError::InvalidAmount);
+
+    msg!("Redeeming {} tokens (fee {}) for {}", burned, fee, ctx.accounts.holder.key());
+
+    _transfer_fee_to_treasury(
+        &ctx.accounts.holder_token,
+        &ctx.accounts.mint.to_account_info(),
+        &ctx.accounts.treasury_token.to_account_info(),
+        &ctx.accounts.holder,
+        &ctx.accounts.token_program,
+        ctx.accounts.mint.decimals,
+        fee
+    )?;
+
+    let cpi_accounts = Burn {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.holder_token.to_account_info(),
+        authority: ctx.accounts.holder.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    burn(cpi_ctx, burned)?;
+
+    emit!(RedemptionRequested {
+        mint: ctx.accounts.mint.key(),
+        holder: ctx.accounts.holder.key(),
+        amount,
+        fee,
+        burned,
+    });
+    Ok(())
+}
+
+/// Move collected fees out of the treasury, signed by the treasury PDA
+pub(crate) fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+    require_not_sunset!(ctx.accounts.sunset_state);
+
+    // Verify the caller has Owner role
+    require_role!(ctx.accounts.owner_role, Role::Owner);
+
+    require!(amount > 0, PusdError::InvalidAmount);
+    require!(
+        amount <= ctx.accounts.treasury_token.amount,
+        PusdError::InsufficientTreasuryBalance
+    );
+
+    let seeds = &[b"treasury".as_ref(), &[ctx.bumps.treasury]];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.treasury_token.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.destination.to_account_info(),
+        authority: ctx.accounts.treasury.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds
+    );
+    transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+
+    msg!("Withdrew {} fees to {}", amount, ctx.accounts.destination.key());
+    Ok(())
+}
+
+/// Transfers a fee from a holder's token account to the treasury
+/// Shared by every path that charges a fee on existing PUSD
+pub(crate) fn _transfer_fee_to_treasury<'info>(
+    from: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    treasury_token: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    decimals: u8,
+    fee: u64
+) -> Result<()> {
+    if fee == 0 {
+        return Ok(());
+    }
+
+    let cpi_accounts = TransferChecked {
+        from: from.to_account_info(),
+        mint: mint.to_account_info(),
+        to: treasury_token.to_account_info(),
+        authority: authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+    transfer_checked(cpi_ctx, fee, decimals)
+}
+
+// ============================================================================
+// Account Validation Structs
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct Redeem<'info> {
+    pub holder: Signer<'info>,
+
+    /// Program state holding the fee configuration
+    #[account(seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// The canonical PUSD Token-2022 mint
+    #[account(
+        mut,
+        address = program_state.mint @ PusdError::InvalidMint,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The holder's PUSD token account
+    /// CHECK: Validated by Token-2022 program (owner must be the signing holder)
+    #[account(mut)]
+    pub holder_token: AccountInfo<'info>,
+
+    /// The treasury PDA that owns collected fees
+    /// CHECK: PDA derived from "treasury" seed
+    #[account(seeds = [b"treasury"], bump)]
+    pub treasury: AccountInfo<'info>,
+
+    /// The treasury's PUSD associated token account receiving fees
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    /// The owner withdrawing fees
+    #[account(
+        seeds = [b"user_role", owner.key().as_ref()],
+        bump = owner_role.bump,
+        constraint = owner_role.role == Role::Owner @ PusdError::Unauthorized
+    )]
+    pub owner_role: Account<'info, UserRole>,
+
+    pub owner: Signer<'info>,
+
+    /// The PUSD Token-2022 mint
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The treasury PDA that owns collected fees
+    /// CHECK: PDA derived from "treasury" seed
+    #[account(seeds = [b"treasury"], bump)]
+    pub treasury: AccountInfo<'info>,
+
+    /// The treasury's PUSD associated token account
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token account receiving the fees
+    /// CHECK: Validated by Token-2022 program
+    #[account(mut)]
+    pub destination: AccountInfo<'info>,
+
+    /// Sunset tombstone; rejects the instruction once executed
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+}
@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+use crate::PusdError;
+
+// ============================================================================
+// Upgrade authority checks
+// ProgramData accounts are typed as Account<ProgramData> and seed-checked
+// against this program, so Anchor verifies the loader owner and layout.
+// ============================================================================
+
+/// Requires `signer` to be the program's upgrade authority
+pub fn check_upgrade_authority(upgrade_authority: Option<Pubkey>, signer: Pubkey) -> Result<()> {
+    match upgrade_authority {
+        Some(authority) if authority == signer => Ok(()),
+        Some(_) => err!(PusdError::OnlyUpgradeAuthority),
+        None => err!(PusdError::ProgramImmutable),
+    }
+}
@@ -0,0 +1,369 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    burn,
+    mint_to,
+    transfer_checked,
+    Burn,
+    Mint,
+    MintTo,
+    Token2022,
+    TokenAccount,
+    TransferChecked,
+};
+
+use crate::*;
+use crate::math::{assets_to_shares, shares_to_assets, Rounding};
+
+// ============================================================================
+// Staking vault
+// Holders stake PUSD into a PDA-owned vault and receive shares of a share
+// mint controlled by the StakingVault PDA. Operators distribute yield by
+// minting PUSD into the vault, raising the assets per share. Assets are
+// tracked internally (donations to the vault token account are ignored) and
+// the share math uses virtual shares/assets to resist inflation attacks.
+// ============================================================================
+
+/// Create the staking vault, its PUSD token account, and its share mint
+pub(crate) fn initialize_staking_vault(ctx: Context<InitializeStakingVault>) -> Result<()> {
+    require_not_sunset!(ctx.accounts.sunset_state);
+
+    // Verify the caller has Owner role
+    require_role!(ctx.accounts.owner_role, Role::Owner);
+
+    let staking_vault = &mut ctx.accounts.staking_vault;
+    staking_vault.pusd_mint = ctx.accounts.pusd_mint.key();
+    staking_vault.share_mint = ctx.accounts.share_mint.key();
+    staking_vault.vault_token = ctx.accounts.vault_token.key();
+    staking_vault.total_assets = 0;
+    staking_vault.bump = ctx.bumps.staking_vault;
+
+    msg!(
+        "Staking vault created - share mint {}, vault {}",
+        staking_vault.share_mint,
+        staking_vault.vault_token
+    );
+    Ok(())
+}
+
+/// Shares issued for assets received by the vault, and the vault's new total assets
+/// Shares round down so depositors can never mint value out of the vault
+pub fn shares_for_stake(assets: u64, total_assets: u64, total_shares: u64) -> Result<(u64, u64)> {
+    let shares = assets_to_shares(assets, total_assets, total_shares, Rounding::Down)?;
+    require!(shares > 0, PusdError::ZeroShares);
+    let new_total_assets = total_assets.checked_add(assets).ok_or(PusdError::MathOverflow)?;
+    Ok((shares, new_total_assets))
+}
+
+/// Assets paid out for burned shares, and the vault's new total assets
+/// Assets round down so the remaining shareholders are never diluted
+pub fn assets_for_unstake(shares: u64, total_assets: u64, total_shares: u64) -> Result<(u64, u64)> {
+    let assets = shares_to_assets(shares, total_assets, total_shares, Rounding::Down)?;
+    require!(assets > 0, PusdError::InvalidAmount);
+    let new_total_assets = total_assets.checked_sub(assets).ok_or(PusdError::MathOverflow)?;
+    Ok((assets, new_total_assets))
+}
+
+/// Stake PUSD and receive vault shares for the amount the vault actually received
+pub(crate) fn stake(ctx: Context<StakingVaultUser>, assets: u64) -> Result<()> {
+    require_not_sunset!(ctx.accounts.sunset_state);
+
+    require!(assets > 0, PusdError::InvalidAmount);
+
+    let balance_before = ctx.accounts.vault_token.amount;
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.user_pusd.to_account_info(),
+        mint: ctx.accounts.pusd_mint.to_account_info(),
+        to: ctx.accounts.vault_token.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    transfer_checked(cpi_ctx, assets, ctx.accounts.pusd_mint.decimals)?;
+
+    // A TransferFee withholds part of the transfer, so credit only what arrived
+    ctx.accounts.vault_token.reload()?;
+    let received = ctx.accounts.vault_token.amount
+        .checked_sub(balance_before)
+        .ok_or(PusdError::MathOverflow)?;
+    let (shares, new_total_assets) = shares_for_stake(
+        received,
+        ctx.accounts.staking_vault.total_assets,
+        ctx.accounts.share_mint.supply
+    )?;
+
+    msg!("Staking {} PUSD ({} received) for {} shares", assets, received, shares);
+
+    let pusd_mint_key = ctx.accounts.pusd_mint.key();
+    let seeds = &[b"staking_vault".as_ref(), pusd_mint_key.as_ref(), &[ctx.accounts.staking_vault.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = MintTo {
+        mint: ctx.accounts.share_mint.to_account_info(),
+        to: ctx.accounts.user_shares.to_account_info(),
+        authority: ctx.accounts.staking_vault.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds
+    );
+    mint_to(cpi_ctx, shares)?;
+
+    ctx.accounts.staking_vault.total_assets = new_total_assets;
+    Ok(())
+}
+
+/// Burn vault shares and unstake PUSD, rounding assets down
+pub(crate) fn unstake(ctx: Context<StakingVaultUser>, shares: u64) -> Result<()> {
+    require!(shares > 0, PusdError::InvalidAmount);
+
+    let (assets, new_total_assets) = assets_for_unstake(
+        shares,
+        ctx.accounts.staking_vault.total_assets,
+        ctx.accounts.share_mint.supply
+    )?;
+
+    msg!("Unstaking {} shares for {} PUSD", shares, assets);
+
+    let cpi_accounts = Burn {
+        mint: ctx.accounts.share_mint.to_account_info(),
+        from: ctx.accounts.user_shares.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    burn(cpi_ctx, shares)?;
+
+    let pusd_mint_key = ctx.accounts.pusd_mint.key();
+    let seeds = &[b"staking_vault".as_ref(), pusd_mint_key.as_ref(), &[ctx.accounts.staking_vault.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.vault_token.to_account_info(),
+        mint: ctx.accounts.pusd_mint.to_account_info(),
+        to: ctx.accounts.user_pusd.to_account_info(),
+        authority: ctx.accounts.staking_vault.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds
+    );
+    transfer_checked(cpi_ctx, assets, ctx.accounts.pusd_mint.decimals)?;
+
+    ctx.accounts.staking_vault.total_assets = new_total_assets;
+    Ok(())
+}
+
+/// Mint PUSD yield into the vault, raising the assets per share
+/// Yield is fiat-backed issuance, so it is held to the same reserve and large mint checks as mint_by_operator
+pub(crate) fn distribute_yield(ctx: Context<DistributeYield>, amount: u64) -> Result<()> {
+    // Verify the caller has Operator role
+    require_role!(ctx.accounts.operator_role, Role::Operator);
+
+    // Direct minting can be switched off in favour of the request/approve flow
+    require!(!ctx.accounts.program_state.direct_mint_disabled, PusdError::DirectMintDisabled);
+
+    require!(amount > 0, PusdError::InvalidAmount);
+    let new_total_assets = ctx.accounts.staking_vault.total_assets
+        .checked_add(amount)
+        .ok_or(PusdError::MathOverflow)?;
+
+    // Large distributions, alone or summed with the operator's other mints over the window, need approval
+    _record_operator_mint(
+        &ctx.accounts.program_state,
+        &mut ctx.accounts.mint_window,
+        ctx.accounts.operator.key(),
+        ctx.bumps.mint_window,
+        amount
+    )?;
+
+    msg!("Distributing {} PUSD of yield to the staking vault", amount);
+
+    _mint(
+        &ctx.accounts.program_state,
+        &ctx.accounts.mint,
+        &ctx.accounts.vault_token.to_account_info(),
+        &ctx.accounts.mint_authority,
+        &ctx.accounts.token_program,
+        &ctx.accounts.reserve_attestation,
+        ctx.bumps.mint_authority,
+        amount
+    )?;
+
+    ctx.accounts.staking_vault.total_assets = new_total_assets;
+    Ok(())
+}
+
+// ============================================================================
+// Account Validation Structs
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializeStakingVault<'info> {
+    /// The owner creating the vault
+    #[account(
+        seeds = [b"user_role", owner.key().as_ref()],
+        bump = owner_role.bump,
+        constraint = owner_role.role == Role::Owner @ PusdError::Unauthorized
+    )]
+    pub owner_role: Account<'info, UserRole>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Vault state; also the vault token owner and share mint authority
+    #[account(
+        init,
+        payer = owner,
+        space = StakingVault::LEN,
+        seeds = [b"staking_vault", pusd_mint.key().as_ref()],
+        bump
+    )]
+    pub staking_vault: Account<'info, StakingVault>,
+
+    /// The PUSD mint being staked
+    #[account(mint::token_program = token_program)]
+    pub pusd_mint: InterfaceAccount<'info, Mint>,
+
+    /// Share mint controlled by the vault PDA
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"share_mint", pusd_mint.key().as_ref()],
+        bump,
+        mint::decimals = pusd_mint.decimals,
+        mint::authority = staking_vault,
+        mint::token_program = token_program
+    )]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
+    /// PUSD token account holding staked assets
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"staking_vault_token", pusd_mint.key().as_ref()],
+        bump,
+        token::mint = pusd_mint,
+        token::authority = staking_vault,
+        token::token_program = token_program
+    )]
+    pub vault_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// Sunset tombstone; rejects the instruction once executed
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakingVaultUser<'info> {
+    pub user: Signer<'info>,
+
+    /// Vault state
+    #[account(
+        mut,
+        seeds = [b"staking_vault", pusd_mint.key().as_ref()],
+        bump = staking_vault.bump,
+        has_one = pusd_mint,
+        has_one = share_mint,
+        has_one = vault_token
+    )]
+    pub staking_vault: Account<'info, StakingVault>,
+
+    /// The PUSD mint
+    #[account(mint::token_program = token_program)]
+    pub pusd_mint: InterfaceAccount<'info, Mint>,
+
+    /// The vault share mint
+    #[account(mut)]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
+    /// PUSD token account holding staked assets
+    #[account(mut)]
+    pub vault_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// The user's PUSD token account
+    #[account(mut, token::mint = pusd_mint, token::token_program = token_program)]
+    pub user_pusd: InterfaceAccount<'info, TokenAccount>,
+
+    /// The user's share token account
+    #[account(mut, token::mint = share_mint, token::token_program = token_program)]
+    pub user_shares: InterfaceAccount<'info, TokenAccount>,
+
+    /// Sunset tombstone; stake is rejected once executed, unstake stays open as an exit
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct DistributeYield<'info> {
+    /// The operator's role account
+    #[account(
+        seeds = [b"user_role", operator.key().as_ref()],
+        bump = operator_role.bump,
+        constraint = operator_role.role == Role::Operator @ PusdError::Unauthorized
+    )]
+    pub operator_role: Account<'info, UserRole>,
+
+    /// Pays for the mint window account on first use
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    /// Running total of this operator's direct mints in the current window
+    #[account(
+        init_if_needed,
+        payer = operator,
+        space = OperatorMintWindow::LEN,
+        seeds = [b"operator_mint_window", operator.key().as_ref()],
+        bump
+    )]
+    pub mint_window: Account<'info, OperatorMintWindow>,
+
+    /// Vault state
+    #[account(
+        mut,
+        seeds = [b"staking_vault", mint.key().as_ref()],
+        bump = staking_vault.bump,
+        has_one = vault_token
+    )]
+    pub staking_vault: Account<'info, StakingVault>,
+
+    /// PUSD token account holding staked assets
+    #[account(mut)]
+    pub vault_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// The PUSD Token-2022 mint account
+    /// CHECK: Validated by Token-2022 program and the staking_vault seeds
+    #[account(mut)]
+    pub mint: AccountInfo<'info>,
+
+    /// Program state holding mint configuration
+    #[account(seeds = [b"program_state"], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Latest proof-of-reserves attestation for the mint
+    #[account(
+        seeds = [b"reserve_attestation", mint.key().as_ref()],
+        bump = reserve_attestation.bump
+    )]
+    pub reserve_attestation: Account<'info, ReserveAttestation>,
+
+    /// The mint authority PDA controlled by this program
+    /// CHECK: PDA derived from "mint_authority" seed
+    #[account(seeds = [b"mint_authority"], bump)]
+    pub mint_authority: AccountInfo<'info>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,481 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{create_account, CreateAccount};
+use anchor_spl::token_interface::{
+    burn,
+    find_mint_account_size,
+    initialize_mint2,
+    interest_bearing_mint_initialize,
+    interest_bearing_mint_update_rate,
+    mint_to,
+    transfer_checked,
+    Burn,
+    InitializeMint2,
+    InterestBearingMintInitialize,
+    InterestBearingMintUpdateRate,
+    Mint,
+    MintTo,
+    Token2022,
+    TokenAccount,
+    TransferChecked,
+    spl_token_2022::{
+        amount_to_ui_amount_string_trimmed,
+        extension::{
+            interest_bearing_mint::InterestBearingConfig,
+            BaseStateWithExtensions,
+            ExtensionType,
+            StateWithExtensions,
+        },
+        state::Mint as MintState,
+        try_ui_amount_into_amount,
+    },
+};
+
+use crate::*;
+
+// ============================================================================
+// Interest-bearing sibling token (e.g. sPUSD)
+// The mint carries the Token-2022 InterestBearingConfig extension with the
+// "rate_authority" PDA as rate authority; the mint authority is the
+// "yield_mint_authority" PDA, kept apart from the PUSD mint_authority so the
+// PUSD mint paths can never sign for it. A RateConfig account bounds how far
+// and how often a RateManager may move the rate.
+// Supply is only issued against PUSD: deposit_yield_token locks PUSD in the
+// mint's "yield_reserve" token account and mints the yield token worth the
+// amount received, and redeem_yield_token burns it for the PUSD its UI
+// amount shows. Both conversions use Token-2022's own interest math. Interest
+// accrued beyond the deposits is paid out of PUSD sent to the reserve, so a
+// redemption the reserve cannot cover is refused.
+// ============================================================================
+
+/// Create an interest-bearing mint controlled by the program PDAs, with the PUSD reserve backing it
+/// The mint takes the decimals of PUSD so deposits and redemptions convert at the UI amount
+pub(crate) fn initialize_yield_mint(
+    ctx: Context<InitializeYieldMint>,
+    initial_rate: i16,
+    bounds: RateBounds
+) -> Result<()> {
+    require_not_sunset!(ctx.accounts.sunset_state);
+
+    // Verify the caller has Owner role
+    require_role!(ctx.accounts.owner_role, Role::Owner);
+
+    bounds.validate()?;
+    require!(
+        initial_rate >= bounds.min_rate && initial_rate <= bounds.max_rate,
+        PusdError::RateOutOfBounds
+    );
+
+    // Allocate the mint with room for the InterestBearingConfig extension
+    let space = find_mint_account_size(Some(&vec![ExtensionType::InterestBearingConfig]))?;
+    let lamports = Rent::get()?.minimum_balance(space);
+    let cpi_accounts = CreateAccount {
+        from: ctx.accounts.owner.to_account_info(),
+        to: ctx.accounts.mint.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+    create_account(cpi_ctx, lamports, space as u64, &ctx.accounts.token_program.key())?;
+
+    // The extension must be initialized before the mint itself
+    let cpi_accounts = InterestBearingMintInitialize {
+        token_program_id: ctx.accounts.token_program.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    interest_bearing_mint_initialize(cpi_ctx, Some(ctx.accounts.rate_authority.key()), initial_rate)?;
+
+    let cpi_accounts = InitializeMint2 {
+        mint: ctx.accounts.mint.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    initialize_mint2(cpi_ctx, ctx.accounts.pusd_mint.decimals, &ctx.accounts.mint_authority.key(), None)?;
+
+    let rate_config = &mut ctx.accounts.rate_config;
+    rate_config.mint = ctx.accounts.mint.key();
+    rate_config.pusd_mint = ctx.accounts.pusd_mint.key();
+    rate_config.reserve = ctx.accounts.reserve.key();
+    rate_config.bounds = bounds;
+    rate_config.current_rate = initial_rate;
+    rate_config.last_update = Clock::get()?.unix_timestamp;
+    rate_config.bump = ctx.bumps.rate_config;
+
+    msg!(
+        "Interest-bearing mint {} created at {} bps (bounds {}..={})",
+        rate_config.mint,
+        initial_rate,
+        bounds.min_rate,
+        bounds.max_rate
+    );
+    Ok(())
+}
+
+/// Replace the rate bounds of an interest-bearing mint
+pub(crate) fn set_rate_bounds(ctx: Context<SetRateBounds>, bounds: RateBounds) -> Result<()> {
+    require_not_sunset!(ctx.accounts.sunset_state);
+
+    // Verify the caller has Owner role
+    require_role!(ctx.accounts.owner_role, Role::Owner);
+
+    bounds.validate()?;
+    ctx.accounts.rate_config.bounds = bounds;
+
+    msg!(
+        "Rate bounds for {} set to {}..={} bps, max change {} bps every {}s",
+        ctx.accounts.rate_config.mint,
+        bounds.min_rate,
+        bounds.max_rate,
+        bounds.max_rate_change,
+        bounds.min_update_interval
+    );
+    Ok(())
+}
+
+/// Change the interest rate within the configured bounds
+pub(crate) fn update_rate(ctx: Context<UpdateRate>, rate: i16) -> Result<()> {
+    require_not_sunset!(ctx.accounts.sunset_state);
+
+    // Verify the caller has RateManager role
+    require_role!(ctx.accounts.rate_manager_role, Role::RateManager);
+
+    let rate_config = &ctx.accounts.rate_config;
+    let bounds = rate_config.bounds;
+    require!(
+        rate >= bounds.min_rate && rate <= bounds.max_rate,
+        PusdError::RateOutOfBounds
+    );
+    require!(
+        (rate as i32 - rate_config.current_rate as i32).unsigned_abs() <= bounds.max_rate_change as u32,
+        PusdError::RateChangeTooLarge
+    );
+
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        current_time.saturating_sub(rate_config.last_update) >= bounds.min_update_interval,
+        PusdError::RateUpdateTooSoon
+    );
+
+    let seeds = &[b"rate_authority".as_ref(), &[ctx.bumps.rate_authority]];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = InterestBearingMintUpdateRate {
+        token_program_id: ctx.accounts.token_program.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        rate_authority: ctx.accounts.rate_authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds
+    );
+    interest_bearing_mint_update_rate(cpi_ctx, rate)?;
+
+    let rate_config = &mut ctx.accounts.rate_config;
+    msg!("Rate for {} updated from {} to {} bps", rate_config.mint, rate_config.current_rate, rate);
+    rate_config.current_rate = rate;
+    rate_config.last_update = current_time;
+    Ok(())
+}
+
+/// Yield tokens issued for PUSD received by the reserve, at the mint's accrued interest
+pub fn yield_tokens_for_deposit(
+    config: &InterestBearingConfig,
+    assets: u64,
+    decimals: u8,
+    unix_timestamp: i64
+) -> Result<u64> {
+    let ui_amount = amount_to_ui_amount_string_trimmed(assets, decimals);
+    let amount = config.try_ui_amount_into_amount(&ui_amount, decimals, unix_timestamp)?;
+    require!(amount > 0, PusdError::ZeroShares);
+    Ok(amount)
+}
+
+/// PUSD paid out for burned yield tokens: the UI amount they show at the given time
+pub fn assets_for_yield_redemption(
+    config: &InterestBearingConfig,
+    amount: u64,
+    decimals: u8,
+    unix_timestamp: i64
+) -> Result<u64> {
+    let ui_amount = config
+        .amount_to_ui_amount(amount, decimals, unix_timestamp)
+        .ok_or(PusdError::MathOverflow)?;
+    let assets = try_ui_amount_into_amount(ui_amount, decimals)?;
+    require!(assets > 0, PusdError::InvalidAmount);
+    Ok(assets)
+}
+
+/// Lock PUSD in the reserve and mint the yield token worth the amount the reserve received
+pub(crate) fn deposit_yield_token(ctx: Context<YieldTokenUser>, assets: u64) -> Result<()> {
+    require_not_sunset!(ctx.accounts.sunset_state);
+
+    require!(assets > 0, PusdError::InvalidAmount);
+
+    let balance_before = ctx.accounts.reserve.amount;
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.user_pusd.to_account_info(),
+        mint: ctx.accounts.pusd_mint.to_account_info(),
+        to: ctx.accounts.reserve.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    transfer_checked(cpi_ctx, assets, ctx.accounts.pusd_mint.decimals)?;
+
+    // A TransferFee withholds part of the transfer, so credit only what arrived
+    ctx.accounts.reserve.reload()?;
+    let received = ctx.accounts.reserve.amount
+        .checked_sub(balance_before)
+        .ok_or(PusdError::MathOverflow)?;
+    let amount = yield_tokens_for_deposit(
+        &_interest_bearing_config(&ctx.accounts.mint)?,
+        received,
+        ctx.accounts.mint.decimals,
+        Clock::get()?.unix_timestamp
+    )?;
+
+    msg!("Depositing {} PUSD ({} received) for {} yield tokens", assets, received, amount);
+
+    let seeds = &[b"yield_mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = MintTo {
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.user_yield.to_account_info(),
+        authority: ctx.accounts.mint_authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds
+    );
+    mint_to(cpi_ctx, amount)?;
+    Ok(())
+}
+
+/// Burn yield tokens and pay out the PUSD their UI amount shows
+pub(crate) fn redeem_yield_token(ctx: Context<YieldTokenUser>, amount: u64) -> Result<()> {
+    require!(amount > 0, PusdError::InvalidAmount);
+
+    let assets = assets_for_yield_redemption(
+        &_interest_bearing_config(&ctx.accounts.mint)?,
+        amount,
+        ctx.accounts.mint.decimals,
+        Clock::get()?.unix_timestamp
+    )?;
+    require!(assets <= ctx.accounts.reserve.amount, PusdError::InsufficientYieldReserve);
+
+    msg!("Redeeming {} yield tokens for {} PUSD", amount, assets);
+
+    let cpi_accounts = Burn {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.user_yield.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    burn(cpi_ctx, amount)?;
+
+    let seeds = &[b"yield_mint_authority".as_ref(), &[ctx.bumps.mint_authority]];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.reserve.to_account_info(),
+        mint: ctx.accounts.pusd_mint.to_account_info(),
+        to: ctx.accounts.user_pusd.to_account_info(),
+        authority: ctx.accounts.mint_authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds
+    );
+    transfer_checked(cpi_ctx, assets, ctx.accounts.pusd_mint.decimals)?;
+    Ok(())
+}
+
+/// Private helper function to read the InterestBearingConfig of a yield mint
+fn _interest_bearing_config(mint: &InterfaceAccount<Mint>) -> Result<InterestBearingConfig> {
+    let mint_info = mint.to_account_info();
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<MintState>::unpack(&mint_data)?;
+    Ok(*mint.get_extension::<InterestBearingConfig>()?)
+}
+
+// ============================================================================
+// Account Validation Structs
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializeYieldMint<'info> {
+    /// The owner creating the mint
+    #[account(
+        seeds = [b"user_role", owner.key().as_ref()],
+        bump = owner_role.bump,
+        constraint = owner_role.role == Role::Owner @ PusdError::Unauthorized
+    )]
+    pub owner_role: Account<'info, UserRole>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The new interest-bearing mint (fresh keypair)
+    #[account(mut)]
+    pub mint: Signer<'info>,
+
+    /// The PUSD mint backing the yield token
+    #[account(mint::token_program = token_program)]
+    pub pusd_mint: InterfaceAccount<'info, Mint>,
+
+    /// Rate limits and history for this mint
+    #[account(
+        init,
+        payer = owner,
+        space = RateConfig::LEN,
+        seeds = [b"rate_config", mint.key().as_ref()],
+        bump
+    )]
+    pub rate_config: Account<'info, RateConfig>,
+
+    /// The PDA set as interest rate authority
+    /// CHECK: PDA derived from "rate_authority" seed
+    #[account(seeds = [b"rate_authority"], bump)]
+    pub rate_authority: AccountInfo<'info>,
+
+    /// The PDA set as mint authority; also the reserve owner
+    /// CHECK: PDA derived from "yield_mint_authority" seed
+    #[account(seeds = [b"yield_mint_authority"], bump)]
+    pub mint_authority: AccountInfo<'info>,
+
+    /// PUSD token account backing issued yield tokens
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"yield_reserve", mint.key().as_ref()],
+        bump,
+        token::mint = pusd_mint,
+        token::authority = mint_authority,
+        token::token_program = token_program
+    )]
+    pub reserve: InterfaceAccount<'info, TokenAccount>,
+
+    /// Sunset tombstone; rejects the instruction once executed
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetRateBounds<'info> {
+    /// The owner updating the bounds
+    #[account(
+        seeds = [b"user_role", owner.key().as_ref()],
+        bump = owner_role.bump,
+        constraint = owner_role.role == Role::Owner @ PusdError::Unauthorized
+    )]
+    pub owner_role: Account<'info, UserRole>,
+
+    pub owner: Signer<'info>,
+
+    /// Rate limits being updated
+    #[account(
+        mut,
+        seeds = [b"rate_config", rate_config.mint.as_ref()],
+        bump = rate_config.bump
+    )]
+    pub rate_config: Account<'info, RateConfig>,
+
+    /// Sunset tombstone; rejects the instruction once executed
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRate<'info> {
+    /// The rate manager's role account
+    #[account(
+        seeds = [b"user_role", rate_manager.key().as_ref()],
+        bump = rate_manager_role.bump,
+        constraint = rate_manager_role.role == Role::RateManager @ PusdError::Unauthorized
+    )]
+    pub rate_manager_role: Account<'info, UserRole>,
+
+    pub rate_manager: Signer<'info>,
+
+    /// Rate limits and history for the mint
+    #[account(
+        mut,
+        seeds = [b"rate_config", mint.key().as_ref()],
+        bump = rate_config.bump
+    )]
+    pub rate_config: Account<'info, RateConfig>,
+
+    /// The interest-bearing mint
+    /// CHECK: Validated by Token-2022 program
+    #[account(mut)]
+    pub mint: AccountInfo<'info>,
+
+    /// The PDA holding the interest rate authority
+    /// CHECK: PDA derived from "rate_authority" seed
+    #[account(seeds = [b"rate_authority"], bump)]
+    pub rate_authority: AccountInfo<'info>,
+
+    /// Sunset tombstone; rejects the instruction once executed
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct YieldTokenUser<'info> {
+    pub user: Signer<'info>,
+
+    /// Configuration of the yield mint, recording its PUSD mint and reserve
+    #[account(
+        seeds = [b"rate_config", mint.key().as_ref()],
+        bump = rate_config.bump,
+        has_one = mint,
+        has_one = pusd_mint,
+        has_one = reserve
+    )]
+    pub rate_config: Account<'info, RateConfig>,
+
+    /// The interest-bearing mint
+    #[account(mut, mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The PUSD mint
+    #[account(mint::token_program = token_program)]
+    pub pusd_mint: InterfaceAccount<'info, Mint>,
+
+    /// PUSD token account backing issued yield tokens
+    #[account(mut)]
+    pub reserve: InterfaceAccount<'info, TokenAccount>,
+
+    /// The user's PUSD token account
+    #[account(mut, token::mint = pusd_mint, token::token_program = token_program)]
+    pub user_pusd: InterfaceAccount<'info, TokenAccount>,
+
+    /// The user's yield token account
+    #[account(mut, token::mint = mint, token::token_program = token_program)]
+    pub user_yield: InterfaceAccount<'info, TokenAccount>,
+
+    /// The PDA holding the mint authority and owning the reserve
+    /// CHECK: PDA derived from "yield_mint_authority" seed
+    #[account(seeds = [b"yield_mint_authority"], bump)]
+    pub mint_authority: AccountInfo<'info>,
+
+    /// Sunset tombstone; deposits are rejected once executed, redemptions stay open as an exit
+    /// CHECK: PDA derived from "sunset" seed, checked by require_not_sunset!
+    #[account(seeds = [b"sunset"], bump)]
+    pub sunset_state: UncheckedAccount<'info>,
+
+    /// The Token-2022 program
+    pub token_program: Program<'info, Token2022>,
+}
@@ -0,0 +1,158 @@
+use pusd_spl::math::{
+    collateral_to_pusd,
+    fee_amount,
+    mul_div,
+    normalize_decimals,
+    pusd_to_collateral,
+    split_fee,
+    Rounding,
+};
+use pusd_spl::{PusdError, MAX_TOKEN_DECIMALS, PRICE_SCALE};
+
+// Amounts covering small values exhaustively plus boundary values
+fn sample_amounts() -> Vec<u64> {
+    let mut amounts: Vec<u64> = (0..=1_000).collect();
+    for exp in 0..=19u32 {
+        let p = 10u64.pow(exp);
+        amounts.extend([p - 1, p, p + 1]);
+    }
+    amounts.extend([u64::MAX / 2, u64::MAX - 1, u64::MAX]);
+    amounts
+}
+
+fn sample_prices() -> Vec<u64> {
+    vec![1, 3, 99_999_999, PRICE_SCALE, PRICE_SCALE + 1, 2 * PRICE_SCALE, 123_456_789, 1_000 * PRICE_SCALE]
+}
+
+#[test]
+fn mul_div_brackets_exact_result() {
+    for a in sample_amounts() {
+        for (b, c) in [(1, 3), (7, 10), (10_000, 9_999), (PRICE_SCALE, 3), (2, 1)] {
+            let exact = a as u128 * b as u128;
+            let down = mul_div(a, b, c, Rounding::Down);
+            let up = mul_div(a, b, c, Rounding::Up);
+            match (down, up) {
+                (Ok(down), Ok(up)) => {
+                    assert!(down as u128 * c as u128 <= exact);
+                    assert!(up as u128 * c as u128 >= exact);
+                    assert!(up - down <= 1);
+                    assert_eq!(up == down, exact % c as u128 == 0);
+                }
+                // Overflow must only happen when the rounded-up quotient does not fit
+                (_, Err(_)) => assert!(exact.div_ceil(c as u128) > u64::MAX as u128),
+                (Err(_), Ok(_)) => panic!("down overflowed while up did not"),
+            }
+        }
+    }
+}
+
+#[test]
+fn mul_div_rejects_zero_divisor() {
+    assert!(mul_div(1, 1, 0, Rounding::Down).is_err());
+    assert!(mul_div(1, 1, 0, Rounding::Up).is_err());
+}
+
+#[test]
+fn normalize_round_trip_never_creates_value() {
+    for from in 0..=MAX_TOKEN_DECIMALS {
+        for to in 0..=MAX_TOKEN_DECIMALS {
+            for amount in sample_amounts() {
+                let Ok(there) = normalize_decimals(amount, from, to, Rounding::Down) else {
+                    // Only scaling up can overflow
+                    assert!(to > from);
+                    continue;
+                };
+                let back = normalize_decimals(there, to, from, Rounding::Down).unwrap();
+                assert!(back <= amount, "{amount} {from}->{to} round-tripped to {back}");
+                if to >= from {
+                    assert_eq!(back, amount);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn normalize_rounding_directions() {
+    for from in 0..=MAX_TOKEN_DECIMALS {
+        for to in 0..from {
+            for amount in sample_amounts() {
+                let down = normalize_decimals(amount, from, to, Rounding::Down).unwrap();
+                let up = normalize_decimals(amount, from, to, Rounding::Up).unwrap();
+                assert!(up >= down && up - down <= 1);
+            }
+        }
+    }
+}
+
+#[test]
+fn normalize_rejects_unsupported_decimals() {
+    assert!(normalize_decimals(1, 0, MAX_TOKEN_DECIMALS + 1, Rounding::Down).is_err());
+    assert!(normalize_decimals(1, MAX_TOKEN_DECIMALS + 1, 0, Rounding::Down).is_err());
+}
+
+#[test]
+fn swap_round_trip_favors_protocol() {
+    for collateral_decimals in 0..=MAX_TOKEN_DECIMALS {
+        for pusd_decimals in [0, 6, 9, MAX_TOKEN_DECIMALS] {
+            for price in sample_prices() {
+                for amount in sample_amounts().into_iter().step_by(7) {
+                    let Ok(pusd) = collateral_to_pusd(amount, collateral_decimals, pusd_decimals, price, Rounding::Down) else {
+                        continue;
+                    };
+                    let Ok(back) = pusd_to_collateral(pusd, collateral_decimals, pusd_decimals, price, Rounding::Down) else {
+                        continue;
+                    };
+                    assert!(
+                        back <= amount,
+                        "{amount} collateral ({collateral_decimals}dp) at {price} -> {pusd} PUSD ({pusd_decimals}dp) -> {back}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn par_price_with_equal_decimals_is_one_to_one() {
+    for decimals in 0..=MAX_TOKEN_DECIMALS {
+        for amount in sample_amounts() {
+            assert_eq!(collateral_to_pusd(amount, decimals, decimals, PRICE_SCALE, Rounding::Down).unwrap(), amount);
+            assert_eq!(pusd_to_collateral(amount, decimals, decimals, PRICE_SCALE, Rounding::Down).unwrap(), amount);
+        }
+    }
+}
+
+#[test]
+fn pusd_to_collateral_rejects_zero_price() {
+    assert!(pusd_to_collateral(1, 6, 6, 0, Rounding::Down).is_err());
+}
+
+#[test]
+fn fee_rounds_up_and_never_exceeds_amount() {
+    for amount in sample_amounts() {
+        for bps in [0u16, 1, 5, 30, 499, 500, 9_999, 10_000] {
+            let fee = fee_amount(amount, bps).unwrap();
+            assert!(fee <= amount);
+            assert!(fee as u128 * 10_000 >= amount as u128 * bps as u128);
+            assert!((fee as u128).saturating_sub(1) * 10_000 < amount as u128 * bps as u128 || fee == 0);
+        }
+    }
+}
+
+#[test]
+fn split_fee_conserves_amount() {
+    for amount in sample_amounts() {
+        for bps in [0u16, 1, 30, 500] {
+            let (fee, net) = split_fee(amount, bps).unwrap();
+            assert_eq!(fee + net, amount);
+            assert_eq!(fee, fee_amount(amount, bps).unwrap());
+        }
+    }
+}
+
+#[test]
+fn split_fee_rejects_fees_above_the_amount() {
+    assert_eq!(split_fee(100, 10_000).unwrap(), (100, 0));
+    assert_eq!(split_fee(100, 10_001).unwrap_err(), PusdError::MathOverflow.into());
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::Pubkey;
+use pusd_spl::oracle::{FixedPrice, PriceLimits, PriceSource, PythPrice, PYTH_PRICE_ACCOUNT_MIN_LEN};
+use pusd_spl::{PriceFeed, PusdError, PRICE_SCALE};
+
+const NOW: i64 = 1_700_000_000;
+
+fn limits() -> PriceLimits {
+    PriceLimits { now: NOW, max_age: 60, max_confidence_bps: 100 }
+}
+
+fn price_feed(price: u64, confidence: u64, publish_time: i64) -> PriceFeed {
+    PriceFeed { collateral_mint: Pubkey::new_unique(), price, confidence, publish_time, bump: 255 }
+}
+
+// Builds a Pyth v2 price account with the given aggregate price
+fn pyth_account(price: i64, conf: u64, expo: i32, timestamp: i64, status: u32) -> Vec<u8> {
+    let mut data = vec![0u8; 3312];
+    data[0..4].copy_from_slice(&0xa1b2_c3d4u32.to_le_bytes());
+    data[4..8].copy_from_slice(&2u32.to_le_bytes());
+    data[8..12].copy_from_slice(&3u32.to_le_bytes());
+    data[20..24].copy_from_slice(&expo.to_le_bytes());
+    data[96..104].copy_from_slice(&timestamp.to_le_bytes());
+    data[208..216].copy_from_slice(&price.to_le_bytes());
+    data[216..224].copy_from_slice(&conf.to_le_bytes());
+    data[224..228].copy_from_slice(&status.to_le_bytes());
+    data
+}
+
+#[test]
+fn fixed_price_ignores_limits() {
+    assert_eq!(FixedPrice(PRICE_SCALE).get_price(&limits()).unwrap(), PRICE_SCALE);
+    assert!(FixedPrice(0).get_price(&limits()).is_err());
+}
+
+#[test]
+fn price_feed_accepts_fresh_tight_price() {
+    let feed = price_feed(PRICE_SCALE, PRICE_SCALE / 100, NOW - 60);
+    assert_eq!(feed.get_price(&limits()).unwrap(), PRICE_SCALE);
+}
+
+#[test]
+fn price_feed_rejects_stale_price() {
+    let feed = price_feed(PRICE_SCALE, 0, NOW - 61);
+    assert!(feed.get_price(&limits()).is_err());
+}
+
+#[test]
+fn price_feed_rejects_wide_confidence() {
+    let feed = price_feed(PRICE_SCALE, PRICE_SCALE / 100 + 1, NOW);
+    assert!(feed.get_price(&limits()).is_err());
+}
+
+#[test]
+fn pyth_parses_and_rescales_exponent() {
+    // 1.2345 USD at expo -4 becomes 1.2345 * PRICE_SCALE
+    let data = pyth_account(12_345, 10, -4, NOW, 1);
+    let parsed = PythPrice::parse(&data).unwrap();
+    assert_eq!(parsed.price, 12_345);
+    assert_eq!(parsed.expo, -4);
+    assert_eq!(parsed.get_price(&limits()).unwrap(), 123_450_000);
+
+    // Exponents finer than PRICE_SCALE round down
+    let data = pyth_account(1_999_999_999, 0, -10, NOW, 1);
+    assert_eq!(PythPrice::parse(&data).unwrap().get_price(&limits()).unwrap(), 19_999_999);
+}
+
+#[test]
+fn pyth_rejects_out_of_range_exponents() {
+    for expo in [i32::MAX, i32::MAX - 7, i32::MIN, 20] {
+        let data = pyth_account(100_000_000, 0, expo, NOW, 1);
+        assert_eq!(
+            PythPrice::parse(&data).unwrap().get_price(&limits()).unwrap_err(),
+            PusdError::MathOverflow.into()
+        );
+    }
+}
+
+#[test]
+fn pyth_rejects_stale_wide_or_halted_prices() {
+    let stale = pyth_account(100_000_000, 0, -8, NOW - 61, 1);
+    assert!(PythPrice::parse(&stale).unwrap().get_price(&limits()).is_err());
+
+    let wide = pyth_account(100_000_000, 1_000_001, -8, NOW, 1);
+    assert!(PythPrice::parse(&wide).unwrap().get_price(&limits()).is_err());
+
+    let halted = pyth_account(100_000_000, 0, -8, NOW, 2);
+    assert!(PythPrice::parse(&halted).unwrap().get_price(&limits()).is_err());
+
+    let negative = pyth_account(-1, 0, -8, NOW, 1);
+    assert!(PythPrice::parse(&negative).unwrap().get_price(&limits()).is_err());
+}
+
+#[test]
+fn pyth_rejects_malformed_accounts() {
+    let mut bad_magic = pyth_account(100_000_000, 0, -8, NOW, 1);
+    bad_magic[0] = 0;
+    assert!(PythPrice::parse(&bad_magic).is_err());
+
+    let mut product_account = pyth_account(100_000_000, 0, -8, NOW, 1);
+    product_account[8..12].copy_from_slice(&2u32.to_le_bytes());
+    assert!(PythPrice::parse(&product_account).is_err());
+
+    let short = pyth_account(100_000_000, 0, -8, NOW, 1)[..PYTH_PRICE_ACCOUNT_MIN_LEN - 1].to_vec();
+    assert!(PythPrice::parse(&short).is_err());
+}
@@ -2,6 +2,10 @@
 
 # Script to create a new Token-2022 token with PDA as mint authority
 # This ensures the program can mint tokens using the PDA
+#
+# Set KYC_MODE=true for a KYC allowlist deployment (initialize with kyc_required = true):
+# the mint is created with a freeze authority and DefaultAccountState=Frozen so every
+# new token account starts frozen until a KycOfficer approves it
 
 set -e
 
@@ -11,16 +15,26 @@ echo ""
 # Calculate the PDA that will be the mint authority
 PROGRAM_ID="9ftLECMyJfEk27kjxyrs3cPh8h6EtKET7gpj8v2RqN1e"
 PDA="4ph4YUFNV8pithois4scjnwyeRBQb92ksiCtEDj1WSvj"
+FREEZE_PDA="Eun7ao9YwGA4CFd4ZBUaHn6mrwcDaxSHDPrwSefrzgdr"
+KYC_MODE="${KYC_MODE:-false}"
 
 echo "Program ID: $PROGRAM_ID"
 echo "PDA (Mint Authority): $PDA"
+echo "KYC allowlist mode: $KYC_MODE"
 echo ""
 
+# DefaultAccountState can only be set up when the mint is created
+KYC_FLAGS=()
+if [ "$KYC_MODE" = "true" ]; then
+  KYC_FLAGS=(--enable-freeze --default-account-state frozen)
+fi
+
 # Create the token with metadata enabled
 echo "Step 1: Creating token..."
 TOKEN_MINT=$(spl-token create-token \
   --program-id TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb \
   --enable-metadata \
+  "${KYC_FLAGS[@]}" \
   --decimals 6 \
   2>&1 | grep "Creating token" | awk '{print $3}')
 
@@ -62,9 +76,19 @@ echo "Token Mint: $TOKEN_MINT"
 echo "Token Account: $TOKEN_ACCOUNT"
 echo "Mint Authority (PDA): $PDA"
 echo "Program ID: $PROGRAM_ID"
+if [ "$KYC_MODE" = "true" ]; then
+  echo "Freeze Authority: this wallet (hand over to $FREEZE_PDA)"
+fi
 echo "========================================"
 echo ""
 echo "Next steps:"
 echo "1. Update Makefile with the new token mint address"
 echo "2. Update scripts/mint-tokens.ts with the new addresses"
-echo "3. Run: make mint-tokens"
+if [ "$KYC_MODE" = "true" ]; then
+  echo "3. Call freeze_authority_to_pda as the Owner, co-signed by this wallet,"
+  echo "   to move the freeze authority to the freeze_authority PDA"
+  echo "4. Have a KycOfficer call kyc_approve for $TOKEN_ACCOUNT (new accounts start frozen)"
+  echo "5. Run: make mint-tokens"
+else
+  echo "3. Run: make mint-tokens"
+fi
@@ -42,19 +42,25 @@ async function main() {
   let ownerAddress: PublicKey;
   let operatorAddress: PublicKey;
 
-  if (args.length >= 2) {
-    ownerAddress = new PublicKey(args[0]);
-    operatorAddress = new PublicKey(args[1]);
-    console.log("\n📋 Using provided addresses:");
-  } else {
-    // Default: use payer as both owner and operator
-    ownerAddress = payer.publicKey;
-    operatorAddress = payer.publicKey;
-    console.log("\n📋 No addresses provided, using payer for both roles:");
+  // The guardian must be a separate key from both owner and operator, so all three
+  // addresses are required; the payer is no longer used as owner and operator by default
+  if (args.length < 3) {
+    console.error("❌ Usage: initialize.ts <owner> <operator> <guardian>");
+    process.exit(1);
   }
+  const guardianAddress = new PublicKey(args[2]);
+
+  ownerAddress = new PublicKey(args[0]);
+  operatorAddress = new PublicKey(args[1]);
+  console.log("\n📋 Using provided addresses:");
 
   console.log("Owner Address:", ownerAddress.toString());
   console.log("Operator Address:", operatorAddress.toString());
+  console.log("Guardian Address:", guardianAddress.toString());
+
+  // Permissioned deployments start every token account frozen until KYC
+  const kycRequired = process.env.KYC_REQUIRED === "true";
+  console.log("KYC Allowlist Mode:", kycRequired);
 
   // Derive PDAs
   const [programStatePDA] = PublicKey.findProgramAddressSync(
@@ -72,6 +78,16 @@ async function main() {
     program.programId
   );
 
+  const [guardianRolePDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from("user_role"), guardianAddress.toBuffer()],
+    program.programId
+  );
+
+  const [sunsetStatePDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from("sunset")],
+    program.programId
+  );
+
   // Get program data account for upgrade authority verification
   const [programDataAddress] = PublicKey.findProgramAddressSync(
     [program.programId.toBuffer()],
@@ -82,6 +98,7 @@ async function main() {
   console.log("Program State PDA:", programStatePDA.toString());
   console.log("Owner Role PDA:", ownerRolePDA.toString());
   console.log("Operator Role PDA:", operatorRolePDA.toString());
+  console.log("Guardian Role PDA:", guardianRolePDA.toString());
   console.log("Program Data Address:", programDataAddress.toString());
 
   try {
@@ -94,11 +111,13 @@ async function main() {
     console.log("⚠️  Note: The program will verify upgrade authority on-chain before initialization");
 
     const tx = await program.methods
-      .initialize(ownerAddress, operatorAddress)
+      .initialize(ownerAddress, operatorAddress, guardianAddress, kycRequired)
       .accountsStrict({
         programState: programStatePDA,
         ownerRole: ownerRolePDA,
         operatorRole: operatorRolePDA,
+        guardianRole: guardianRolePDA,
+        sunsetState: sunsetStatePDA,
         programData: programDataAddress,
         payer: payer.publicKey,
         systemProgram: SystemProgram.programId,
@@ -79,6 +79,16 @@ async function main() {
     program.programId
   );
 
+  const [programStatePDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from("program_state")],
+    program.programId
+  );
+
+  const [reserveAttestationPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from("reserve_attestation"), mintAddress.toBuffer()],
+    program.programId
+  );
+
   console.log("Operator Role PDA:", operatorRolePDA.toString());
   console.log("Mint Authority PDA:", mintAuthorityPDA.toString());
   console.log("");
@@ -108,8 +118,10 @@ async function main() {
       .mintByOperator(amountToMint)
       .accountsStrict({
         operatorRole: operatorRolePDA,
+        programState: programStatePDA,
         operator: operator.publicKey,
         mint: mintAddress,
+        reserveAttestation: reserveAttestationPDA,
         recipient: recipientTokenAccount,
         mintAuthority: mintAuthorityPDA,
         tokenProgram: new PublicKey("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"), // Token-2022 program